@@ -13,6 +13,8 @@ async fn main() {
     market_data().await;
     #[cfg(feature = "futures_api")]
     account().await;
+    #[cfg(feature = "futures_api")]
+    market_websocket().await;
 }
 
 #[cfg(feature = "futures_api")]
@@ -76,8 +78,11 @@ async fn market_data() {
         Err(e) => error!("Error: {:?}", e),
     }
 
-    match market.get_klines("btcusdt", "5m", 10u16, None, None).await {
-        Ok(KlineSummaries::AllKlineSummaries(answer)) => info!("First kline: {:?}", answer[0]),
+    match market
+        .get_klines("btcusdt", KlineInterval::FiveMinutes, 10u16, None, None)
+        .await
+    {
+        Ok(answer) => info!("First kline: {:?}", answer[0]),
         Err(e) => error!("Error: {:?}", e),
     }
 
@@ -130,3 +135,71 @@ async fn account() {
         Err(e) => error!("Error: {:?}", e),
     }
 }
+
+#[cfg(feature = "futures_api")]
+async fn market_websocket() {
+    use binance::futures::websockets::*;
+    use binance::futures::ws_model::FuturesWebsocketEvent;
+    use binance::websockets::WebSockets;
+    use binance::ws_model::CombinedStreamEvent;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let keep_running = AtomicBool::new(true);
+    let streams = vec![mark_price_stream("btcusdt", 1000), liquidation_order_stream("btcusdt")];
+    let mut web_socket: WebSockets<'_, CombinedStreamEvent<FuturesWebsocketEvent>> =
+        WebSockets::new_futures(|event: CombinedStreamEvent<FuturesWebsocketEvent>| {
+            match event.data {
+                FuturesWebsocketEvent::MarkPriceUpdate(mark_price) => {
+                    info!("Symbol: {}, mark price: {}", mark_price.symbol, mark_price.mark_price);
+                }
+                FuturesWebsocketEvent::LiquidationOrder(liquidation) => {
+                    info!("Liquidation: {:?}", liquidation.order);
+                    keep_running.store(false, Ordering::Relaxed);
+                }
+                _ => (),
+            }
+            Ok(())
+        });
+
+    web_socket.connect_multiple(streams).await.unwrap(); // check error
+    if let Err(e) = web_socket.event_loop(&keep_running).await {
+        error!("Error: {:?}", e);
+    }
+    web_socket.disconnect().await.unwrap();
+}
+
+#[cfg(feature = "futures_api")]
+#[allow(dead_code)]
+async fn user_stream_websocket() {
+    use binance::api::*;
+    use binance::futures::userstream::FuturesUserStream;
+    use binance::futures::ws_model::FuturesWebsocketEvent;
+    use binance::websockets::WebSockets;
+    use std::sync::atomic::AtomicBool;
+
+    let keep_running = AtomicBool::new(true);
+    let user_stream: FuturesUserStream = Binance::new(Some("YOUR_KEY".into()), None);
+
+    if let Ok(answer) = user_stream.start().await {
+        let listen_key = answer.listen_key;
+
+        let mut web_socket: WebSockets<'_, FuturesWebsocketEvent> =
+            WebSockets::new_futures(|event: FuturesWebsocketEvent| {
+                match event {
+                    FuturesWebsocketEvent::OrderTradeUpdate(update) => info!("Order update: {:?}", update.order),
+                    FuturesWebsocketEvent::AccountUpdate(update) => info!("Positions: {:?}", update.data.positions),
+                    _ => (),
+                }
+                Ok(())
+            });
+
+        web_socket.connect(&listen_key).await.unwrap(); // check error
+        if let Err(e) = web_socket.event_loop(&keep_running).await {
+            error!("Error: {:?}", e);
+        }
+        user_stream.close(&listen_key).await.unwrap();
+        web_socket.disconnect().await.unwrap();
+    } else {
+        error!("Not able to start an User Stream (Check your API_KEY)");
+    }
+}
@@ -2,6 +2,7 @@
 extern crate tokio;
 
 use binance::api::*;
+use binance::rest_model::KlineInterval;
 use binance::userstream::*;
 use binance::websockets::*;
 use binance::ws_model::{CombinedStreamEvent, WebsocketEvent, WebsocketEventUntag};
@@ -36,7 +37,8 @@ async fn main() {
         Box::pin(last_price(logger_tx.clone())),
         Box::pin(book_ticker(logger_tx.clone())),
         Box::pin(combined_orderbook(logger_tx.clone())),
-        Box::pin(custom_event_loop(logger_tx)),
+        Box::pin(custom_event_loop(logger_tx.clone())),
+        Box::pin(event_stream(logger_tx)),
     ];
 
     for stream in streams {
@@ -169,7 +171,7 @@ async fn all_trades_websocket(logger_tx: UnboundedSender<WebsocketEvent>) {
 #[allow(dead_code)]
 async fn kline_websocket(logger_tx: UnboundedSender<WebsocketEvent>) {
     let keep_running = AtomicBool::new(true);
-    let kline = kline_stream("ethbtc", "1m");
+    let kline = kline_stream("ethbtc", KlineInterval::OneMinute);
     let mut web_socket: WebSockets<'_, WebsocketEvent> = WebSockets::new(|event: WebsocketEvent| {
         logger_tx.send(event.clone()).unwrap();
         if let WebsocketEvent::Kline(kline_event) = event {
@@ -202,8 +204,8 @@ async fn last_price(logger_tx: UnboundedSender<WebsocketEvent>) {
             if let WebsocketEvent::DayTicker(tick_event) = tick_events {
                 if tick_event.symbol == "BTCUSDT" {
                     let mut btcusdt = btcusdt.write().unwrap();
-                    *btcusdt = tick_event.average_price.parse::<f32>().unwrap();
-                    let btcusdt_close: f32 = tick_event.current_close.parse().unwrap();
+                    *btcusdt = tick_event.average_price.to_string().parse::<f32>().unwrap();
+                    let btcusdt_close: f32 = tick_event.current_close.to_string().parse().unwrap();
                     println!("{btcusdt} - {btcusdt_close}");
 
                     if btcusdt_close as i32 == 7000 {
@@ -314,3 +316,22 @@ async fn custom_event_loop(logger_tx: UnboundedSender<WebsocketEvent>) {
         }
     }
 }
+
+#[allow(dead_code)]
+async fn event_stream(logger_tx: UnboundedSender<WebsocketEvent>) {
+    let mut events: WebsocketStream<'_, WebsocketEvent> = WebsocketStream::new(Default::default());
+    events.connect(&trade_stream("btcusdt")).await.unwrap(); // check error
+    let mut trades = events
+        .filter_map(|event| async move {
+            match event {
+                Ok(WebsocketEvent::Trade(trade)) => Some(trade),
+                _ => None,
+            }
+        })
+        .boxed();
+    while let Some(trade) = trades.next().await {
+        println!("Symbol: {}, price: {}, qty: {}", trade.symbol, trade.price, trade.qty);
+        logger_tx.send(WebsocketEvent::Trade(trade)).unwrap();
+    }
+    println!("disconnected");
+}
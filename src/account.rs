@@ -1,7 +1,12 @@
 use crate::client::*;
 use crate::errors::*;
+use crate::pagination::*;
 use crate::rest_model::*;
 use crate::util::*;
+use futures::stream::BoxStream;
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
+use std::time::Duration;
 
 static API_V3_ACCOUNT: &str = "/api/v3/account";
 static API_V3_OPEN_ORDERS: &str = "/api/v3/openOrders";
@@ -9,10 +14,22 @@ static API_V3_ALL_ORDERS: &str = "/api/v3/allOrders";
 static API_V3_MYTRADES: &str = "/api/v3/myTrades";
 static API_V3_ORDER: &str = "/api/v3/order";
 static API_V3_CANCEL_REPLACE: &str = "/api/v3/order/cancelReplace";
+static API_V3_ORDER_OCO: &str = "/api/v3/order/oco";
+static API_V3_ORDER_LIST: &str = "/api/v3/orderList";
+static API_V3_ORDER_LIST_OCO: &str = "/api/v3/orderList/oco";
+static API_V3_ORDER_LIST_OTO: &str = "/api/v3/orderList/oto";
+static API_V3_ORDER_LIST_OTOCO: &str = "/api/v3/orderList/otoco";
+static API_V3_ALL_ORDER_LIST: &str = "/api/v3/allOrderList";
+static API_V3_OPEN_ORDER_LIST: &str = "/api/v3/openOrderList";
 /// Endpoint for test orders.
 /// Orders issued to this endpoint are validated, but not sent into the matching engine.
 static API_V3_ORDER_TEST: &str = "/api/v3/order/test";
 
+/// Binance code of "Order does not exist."
+const ORDER_DOES_NOT_EXIST: i32 = -2013;
+/// Time given to a slow order to land before querying its status again
+const ORDER_STATUS_RECHECK_DELAY: Duration = Duration::from_secs(1);
+
 /// Account API access, full example provided in examples/binance_endpoints.rs
 #[derive(Clone)]
 pub struct Account {
@@ -30,15 +47,15 @@ pub struct OrderRequest {
     #[serde(rename = "type")]
     pub order_type: OrderType,
     pub time_in_force: Option<TimeInForce>,
-    pub quantity: Option<f64>,
-    pub quote_order_qty: Option<f64>,
-    pub price: Option<f64>,
+    pub quantity: Option<Number>,
+    pub quote_order_qty: Option<Number>,
+    pub price: Option<Number>,
     /// A unique id for the order, automatically generated if not sent.
     pub new_client_order_id: Option<String>,
     /// Used with stop loss, stop loss limit, take profit and take profit limit order types.
-    pub stop_price: Option<f64>,
+    pub stop_price: Option<Number>,
     /// Used with limit, stop loss limit and take profit limit to create an iceberg order.
-    pub iceberg_qty: Option<f64>,
+    pub iceberg_qty: Option<Number>,
     /// Set the response json, market and limit default to full others to ack.
     pub new_order_resp_type: Option<OrderResponse>,
     /// Cannot be greater than 60000
@@ -46,7 +63,7 @@ pub struct OrderRequest {
 }
 
 impl OrderRequest {
-    fn valid(&self) -> Result<()> {
+    pub(crate) fn valid(&self) -> Result<()> {
         if self.iceberg_qty.is_some() && self.time_in_force != Some(TimeInForce::GTC) {
             return Err(Error::InvalidOrderError {
                 msg: "Time in force has to be GTC for iceberg orders".to_string(),
@@ -83,15 +100,15 @@ pub struct CancelReplaceRequest {
     pub order_type: OrderType,
     pub cancel_replace_mode: CancelReplaceMode,
     pub time_in_force: Option<TimeInForce>,
-    pub quantity: Option<f64>,
-    pub quote_order_qty: Option<f64>,
-    pub price: Option<f64>,
+    pub quantity: Option<Number>,
+    pub quote_order_qty: Option<Number>,
+    pub price: Option<Number>,
     pub cancel_new_client_order_id: Option<String>,
     pub cancel_orig_client_order_id: Option<String>,
     pub cancel_order_id: Option<u64>,
     pub new_client_order_id: Option<String>,
-    pub stop_price: Option<f64>,
-    pub iceberg_qty: Option<f64>,
+    pub stop_price: Option<Number>,
+    pub iceberg_qty: Option<Number>,
     pub new_order_resp_type: Option<OrderResponse>,
     /// Cannot be greater than 60000
     pub recv_window: Option<u64>,
@@ -108,7 +125,6 @@ impl CancelReplaceRequest {
     }
 }
 
-
 /// Order Status Request
 /// perform an order status request for the account
 #[derive(Default, Debug, Serialize, Deserialize, Clone)]
@@ -136,6 +152,471 @@ pub struct OrdersQuery {
     pub recv_window: Option<u64>,
 }
 
+/// OCO Order Request (deprecated endpoint, prefer `OrderListOCORequest`)
+/// places a limit order and a stop loss order, the execution of one cancels the other
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OCORequest {
+    pub symbol: String,
+    /// A unique id for the list, automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub side: OrderSide,
+    pub quantity: Number,
+    /// A unique id for the limit order, automatically generated if not sent.
+    pub limit_client_order_id: Option<String>,
+    pub price: Number,
+    pub limit_iceberg_qty: Option<Number>,
+    /// A unique id for the stop order, automatically generated if not sent.
+    pub stop_client_order_id: Option<String>,
+    pub stop_price: Number,
+    /// If provided, the stop order is a stop loss limit order and `stop_limit_time_in_force` is required.
+    pub stop_limit_price: Option<Number>,
+    pub stop_iceberg_qty: Option<Number>,
+    pub stop_limit_time_in_force: Option<TimeInForce>,
+    pub new_order_resp_type: Option<OrderResponse>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+impl OCORequest {
+    pub fn valid(&self) -> Result<()> {
+        let prices_ordered = match self.side {
+            OrderSide::Sell => self.price > self.stop_price,
+            OrderSide::Buy => self.price < self.stop_price,
+        };
+        if !prices_ordered {
+            return Err(Error::InvalidOrderError {
+                msg: "The limit price has to be above the stop price for sells and below it for buys".to_string(),
+            });
+        }
+        if self.stop_limit_price.is_some() && self.stop_limit_time_in_force.is_none() {
+            return Err(Error::InvalidOrderError {
+                msg: "Time in force is required with a stop limit price".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// OCO Order List Request
+/// places an order above and an order below the current price, the execution of one cancels the other
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListOCORequest {
+    pub symbol: String,
+    /// A unique id for the list, automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub side: OrderSide,
+    pub quantity: Number,
+    /// One of `StopLossLimit`, `StopLoss`, `LimitMaker`, `TakeProfit` or `TakeProfitLimit`
+    pub above_type: OrderType,
+    pub above_client_order_id: Option<String>,
+    pub above_iceberg_qty: Option<Number>,
+    pub above_price: Option<Number>,
+    pub above_stop_price: Option<Number>,
+    pub above_trailing_delta: Option<u64>,
+    pub above_time_in_force: Option<TimeInForce>,
+    /// One of `StopLossLimit`, `StopLoss`, `LimitMaker`, `TakeProfit` or `TakeProfitLimit`
+    pub below_type: OrderType,
+    pub below_client_order_id: Option<String>,
+    pub below_iceberg_qty: Option<Number>,
+    pub below_price: Option<Number>,
+    pub below_stop_price: Option<Number>,
+    pub below_trailing_delta: Option<u64>,
+    pub below_time_in_force: Option<TimeInForce>,
+    pub new_order_resp_type: Option<OrderResponse>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+impl OrderListOCORequest {
+    pub fn valid(&self) -> Result<()> {
+        validate_leg(
+            "above",
+            OCO_LEG_TYPES,
+            &self.above_type,
+            self.above_price,
+            self.above_stop_price,
+            self.above_trailing_delta,
+            self.above_time_in_force.as_ref(),
+        )?;
+        validate_leg(
+            "below",
+            OCO_LEG_TYPES,
+            &self.below_type,
+            self.below_price,
+            self.below_stop_price,
+            self.below_trailing_delta,
+            self.below_time_in_force.as_ref(),
+        )?;
+        let above_trigger = trigger_price(&self.above_type, self.above_price, self.above_stop_price);
+        let below_trigger = trigger_price(&self.below_type, self.below_price, self.below_stop_price);
+        validate_oco_legs(
+            &self.side,
+            (&self.above_type, above_trigger),
+            (&self.below_type, below_trigger),
+        )
+    }
+}
+
+/// Order types of the above and below orders of an OCO
+const OCO_LEG_TYPES: &[OrderType] = &[
+    OrderType::StopLossLimit,
+    OrderType::StopLoss,
+    OrderType::LimitMaker,
+    OrderType::TakeProfit,
+    OrderType::TakeProfitLimit,
+];
+
+/// Checks that an OCO pairs a limit maker or take profit order with a stop loss order, the first one above
+/// the second one for a sell and below it for a buy. The legs are given with their type and trigger price.
+fn validate_oco_legs(
+    side: &OrderSide,
+    above: (&OrderType, Option<Number>),
+    below: (&OrderType, Option<Number>),
+) -> Result<()> {
+    let is_stop_loss = |order_type: &OrderType| matches!(order_type, OrderType::StopLoss | OrderType::StopLossLimit);
+    let (above_stop_loss, below_stop_loss) = match side {
+        OrderSide::Sell => (false, true),
+        OrderSide::Buy => (true, false),
+    };
+    for (leg, (order_type, _), stop_loss) in [("above", above, above_stop_loss), ("below", below, below_stop_loss)] {
+        if is_stop_loss(order_type) != stop_loss {
+            let expected = if stop_loss {
+                "a stop loss"
+            } else {
+                "a limit maker or take profit"
+            };
+            return Err(Error::InvalidOrderError {
+                msg: format!("The {leg} order of a {side:?} OCO must be {expected} order, not {order_type:?}"),
+            });
+        }
+    }
+    match (above.1, below.1) {
+        (Some(above_price), Some(below_price)) if above_price <= below_price => Err(Error::InvalidOrderError {
+            msg: format!("The above price {above_price} must be greater than the below price {below_price}"),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Price at which an OCO leg executes or triggers, `None` for orders triggered by a trailing delta only
+fn trigger_price(order_type: &OrderType, price: Option<Number>, stop_price: Option<Number>) -> Option<Number> {
+    match order_type {
+        OrderType::LimitMaker => price,
+        _ => stop_price,
+    }
+}
+
+/// Order types of the working order of an OTO or OTOCO
+const WORKING_LEG_TYPES: &[OrderType] = &[OrderType::Limit, OrderType::LimitMaker];
+
+/// Order types of the pending order of an OTO
+const PENDING_LEG_TYPES: &[OrderType] = &[
+    OrderType::Limit,
+    OrderType::Market,
+    OrderType::StopLoss,
+    OrderType::StopLossLimit,
+    OrderType::TakeProfit,
+    OrderType::TakeProfitLimit,
+    OrderType::LimitMaker,
+];
+
+fn validate_leg(
+    leg: &str,
+    allowed: &[OrderType],
+    order_type: &OrderType,
+    price: Option<Number>,
+    stop_price: Option<Number>,
+    trailing_delta: Option<u64>,
+    time_in_force: Option<&TimeInForce>,
+) -> Result<()> {
+    if !allowed.contains(order_type) {
+        return Err(Error::InvalidOrderError {
+            msg: format!("{order_type:?} is not a valid type for the {leg} order"),
+        });
+    }
+    let (needs_price, needs_stop, needs_time_in_force) = match order_type {
+        OrderType::Limit => (true, false, true),
+        OrderType::LimitMaker => (true, false, false),
+        OrderType::StopLoss | OrderType::TakeProfit => (false, true, false),
+        OrderType::StopLossLimit | OrderType::TakeProfitLimit => (true, true, true),
+        _ => (false, false, false),
+    };
+    if needs_price && price.is_none() {
+        return Err(Error::InvalidOrderError {
+            msg: format!("A price is required for the {leg} order"),
+        });
+    }
+    if needs_stop && stop_price.is_none() && trailing_delta.is_none() {
+        return Err(Error::InvalidOrderError {
+            msg: format!("A stop price or a trailing delta is required for the {leg} order"),
+        });
+    }
+    if needs_time_in_force && time_in_force.is_none() {
+        return Err(Error::InvalidOrderError {
+            msg: format!("Time in force is required for the {leg} order"),
+        });
+    }
+    Ok(())
+}
+
+/// An order of an OTO or OTOCO order list, sent with the prefix of its position in the list
+#[derive(Default, Debug, Clone)]
+pub struct OrderListLeg {
+    pub order_type: OrderType,
+    /// A unique id for the order, automatically generated if not sent.
+    pub client_order_id: Option<String>,
+    pub price: Option<Number>,
+    pub stop_price: Option<Number>,
+    pub trailing_delta: Option<u64>,
+    pub iceberg_qty: Option<Number>,
+    pub time_in_force: Option<TimeInForce>,
+}
+
+impl OrderListLeg {
+    fn of_type(order_type: OrderType) -> Self {
+        Self {
+            order_type,
+            ..Self::default()
+        }
+    }
+
+    pub fn limit(price: Number, time_in_force: TimeInForce) -> Self {
+        Self {
+            price: Some(price),
+            time_in_force: Some(time_in_force),
+            ..Self::of_type(OrderType::Limit)
+        }
+    }
+
+    pub fn limit_maker(price: Number) -> Self {
+        Self {
+            price: Some(price),
+            ..Self::of_type(OrderType::LimitMaker)
+        }
+    }
+
+    pub fn market() -> Self { Self::of_type(OrderType::Market) }
+
+    pub fn stop_loss(stop_price: Number) -> Self {
+        Self {
+            stop_price: Some(stop_price),
+            ..Self::of_type(OrderType::StopLoss)
+        }
+    }
+
+    pub fn stop_loss_limit(price: Number, stop_price: Number, time_in_force: TimeInForce) -> Self {
+        Self {
+            price: Some(price),
+            stop_price: Some(stop_price),
+            time_in_force: Some(time_in_force),
+            ..Self::of_type(OrderType::StopLossLimit)
+        }
+    }
+
+    pub fn take_profit(stop_price: Number) -> Self {
+        Self {
+            stop_price: Some(stop_price),
+            ..Self::of_type(OrderType::TakeProfit)
+        }
+    }
+
+    pub fn take_profit_limit(price: Number, stop_price: Number, time_in_force: TimeInForce) -> Self {
+        Self {
+            price: Some(price),
+            stop_price: Some(stop_price),
+            time_in_force: Some(time_in_force),
+            ..Self::of_type(OrderType::TakeProfitLimit)
+        }
+    }
+
+    pub fn client_order_id(mut self, client_order_id: impl Into<String>) -> Self {
+        self.client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    pub fn trailing_delta(mut self, trailing_delta: u64) -> Self {
+        self.trailing_delta = Some(trailing_delta);
+        self
+    }
+
+    pub fn iceberg_qty(mut self, iceberg_qty: Number) -> Self {
+        self.iceberg_qty = Some(iceberg_qty);
+        self
+    }
+
+    fn valid(&self, leg: &str, allowed: &[OrderType]) -> Result<()> {
+        validate_leg(
+            leg,
+            allowed,
+            &self.order_type,
+            self.price,
+            self.stop_price,
+            self.trailing_delta,
+            self.time_in_force.as_ref(),
+        )
+    }
+
+    fn trigger_price(&self) -> Option<Number> { trigger_price(&self.order_type, self.price, self.stop_price) }
+
+    /// Adds the parameters of the order, named `{prefix}Type`, `{prefix}Price`...
+    fn serialize_into<M: SerializeMap>(&self, prefix: &str, map: &mut M) -> std::result::Result<(), M::Error> {
+        map.serialize_entry(&format!("{prefix}Type"), &self.order_type)?;
+        serialize_some(map, &format!("{prefix}ClientOrderId"), &self.client_order_id)?;
+        serialize_some(map, &format!("{prefix}Price"), &self.price)?;
+        serialize_some(map, &format!("{prefix}StopPrice"), &self.stop_price)?;
+        serialize_some(map, &format!("{prefix}TrailingDelta"), &self.trailing_delta)?;
+        serialize_some(map, &format!("{prefix}IcebergQty"), &self.iceberg_qty)?;
+        serialize_some(map, &format!("{prefix}TimeInForce"), &self.time_in_force)
+    }
+}
+
+fn serialize_some<M: SerializeMap, T: Serialize>(
+    map: &mut M,
+    key: &str,
+    value: &Option<T>,
+) -> std::result::Result<(), M::Error> {
+    match value {
+        Some(value) => map.serialize_entry(key, value),
+        None => Ok(()),
+    }
+}
+
+/// OTO Order List Request
+/// places a working order, the pending order is placed once the working order is fully filled
+#[derive(Default, Debug, Clone)]
+pub struct OrderListOTORequest {
+    pub symbol: String,
+    /// A unique id for the list, automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub working_side: OrderSide,
+    pub working_quantity: Number,
+    /// A `Limit` or `LimitMaker` order
+    pub working: OrderListLeg,
+    pub pending_side: OrderSide,
+    pub pending_quantity: Number,
+    /// Any order type, `Market` orders are sent with a quantity only
+    pub pending: OrderListLeg,
+    pub new_order_resp_type: Option<OrderResponse>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+impl OrderListOTORequest {
+    pub fn valid(&self) -> Result<()> {
+        self.working.valid("working", WORKING_LEG_TYPES)?;
+        self.pending.valid("pending", PENDING_LEG_TYPES)
+    }
+}
+
+impl Serialize for OrderListOTORequest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("symbol", &self.symbol)?;
+        serialize_some(&mut map, "listClientOrderId", &self.list_client_order_id)?;
+        serialize_some(&mut map, "newOrderRespType", &self.new_order_resp_type)?;
+        map.serialize_entry("workingSide", &self.working_side)?;
+        map.serialize_entry("workingQuantity", &self.working_quantity)?;
+        self.working.serialize_into("working", &mut map)?;
+        map.serialize_entry("pendingSide", &self.pending_side)?;
+        map.serialize_entry("pendingQuantity", &self.pending_quantity)?;
+        self.pending.serialize_into("pending", &mut map)?;
+        map.end()
+    }
+}
+
+/// OTOCO Order List Request
+/// places a working order, the pending above and below orders form an OCO once the working order is filled
+#[derive(Default, Debug, Clone)]
+pub struct OrderListOTOCORequest {
+    pub symbol: String,
+    /// A unique id for the list, automatically generated if not sent.
+    pub list_client_order_id: Option<String>,
+    pub working_side: OrderSide,
+    pub working_quantity: Number,
+    /// A `Limit` or `LimitMaker` order
+    pub working: OrderListLeg,
+    pub pending_side: OrderSide,
+    pub pending_quantity: Number,
+    /// One of `StopLossLimit`, `StopLoss`, `LimitMaker`, `TakeProfit` or `TakeProfitLimit`
+    pub pending_above: OrderListLeg,
+    /// One of `StopLossLimit`, `StopLoss`, `LimitMaker`, `TakeProfit` or `TakeProfitLimit`
+    pub pending_below: OrderListLeg,
+    pub new_order_resp_type: Option<OrderResponse>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+impl OrderListOTOCORequest {
+    pub fn valid(&self) -> Result<()> {
+        self.working.valid("working", WORKING_LEG_TYPES)?;
+        self.pending_above.valid("pending above", OCO_LEG_TYPES)?;
+        self.pending_below.valid("pending below", OCO_LEG_TYPES)?;
+        validate_oco_legs(
+            &self.pending_side,
+            (&self.pending_above.order_type, self.pending_above.trigger_price()),
+            (&self.pending_below.order_type, self.pending_below.trigger_price()),
+        )
+    }
+}
+
+impl Serialize for OrderListOTOCORequest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("symbol", &self.symbol)?;
+        serialize_some(&mut map, "listClientOrderId", &self.list_client_order_id)?;
+        serialize_some(&mut map, "newOrderRespType", &self.new_order_resp_type)?;
+        map.serialize_entry("workingSide", &self.working_side)?;
+        map.serialize_entry("workingQuantity", &self.working_quantity)?;
+        self.working.serialize_into("working", &mut map)?;
+        map.serialize_entry("pendingSide", &self.pending_side)?;
+        map.serialize_entry("pendingQuantity", &self.pending_quantity)?;
+        self.pending_above.serialize_into("pendingAbove", &mut map)?;
+        self.pending_below.serialize_into("pendingBelow", &mut map)?;
+        map.end()
+    }
+}
+
+/// Order List Cancellation Request
+/// either order_list_id or list_client_order_id must be set
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListCancellation {
+    pub symbol: String,
+    pub order_list_id: Option<u64>,
+    pub list_client_order_id: Option<String>,
+    /// Used to uniquely identify this cancel. Automatically generated by default.
+    pub new_client_order_id: Option<String>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+/// Order List Status Request
+/// either order_list_id or orig_client_order_id must be set
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListStatusRequest {
+    pub order_list_id: Option<u64>,
+    pub orig_client_order_id: Option<String>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
+/// Order Lists Query
+/// from_id cannot be combined with start_time and end_time
+#[derive(Default, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListsQuery {
+    pub from_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    /// Default 500 max 1000
+    pub limit: Option<u32>,
+    /// Cannot be greater than 60000
+    pub recv_window: Option<u64>,
+}
+
 impl Account {
     /// General account information
     /// # Examples
@@ -216,6 +697,39 @@ impl Account {
         self.client.get_signed(API_V3_ALL_ORDERS, &request).await
     }
 
+    /// Every order of the account on `symbol` created between `start_time` and `end_time` (inclusive, in ms),
+    /// in time order
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// use futures::TryStreamExt;
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let orders = account.all_orders_stream("BTCUSDT", 1_672_531_200_000, 1_675_209_600_000);
+    /// let orders: Vec<_> = tokio_test::block_on(orders.try_collect()).unwrap();
+    /// ```
+    pub fn all_orders_stream<S>(&self, symbol: S, start_time: u64, end_time: u64) -> BoxStream<'_, Result<Order>>
+    where
+        S: Into<String>,
+    {
+        let paging = Paging {
+            max_window: Some(DAY_MS),
+            limit: 1000,
+            by_offset: false,
+        };
+        let symbol = symbol.into();
+        let fetch = move |page: Page| {
+            let query = OrdersQuery {
+                symbol: symbol.clone(),
+                start_time: Some(page.start_time),
+                end_time: Some(page.end_time),
+                limit: Some(page.limit as u32),
+                ..OrdersQuery::default()
+            };
+            self.get_all_orders(query)
+        };
+        paginate(paging, start_time, end_time, fetch, |o| o.time, |o| o.order_id)
+    }
+
     /// All currently open orders for the account
     /// # Examples
     /// ```rust,no_run
@@ -297,8 +811,8 @@ impl Account {
     /// let account: Account = Binance::new_with_env(&Config::testnet());
     /// let limit_buy = OrderRequest {
     ///         symbol: "BTCUSDT".to_string(),
-    ///         quantity: Some(10.0),
-    ///         price: Some(0.014000),
+    ///         quantity: Some("10.0".parse().unwrap()),
+    ///         price: Some("0.014000".parse().unwrap()),
     ///         order_type: OrderType::Limit,
     ///         side: OrderSide::Buy,
     ///         time_in_force: Some(TimeInForce::FOK),
@@ -307,11 +821,65 @@ impl Account {
     /// let transaction = tokio_test::block_on(account.place_order(limit_buy));
     /// assert!(transaction.is_ok(), "{:?}", transaction);
     /// ```
-    pub async fn place_order(&self, order: OrderRequest) -> Result<Transaction> {
+    ///
+    /// When the retry policy allows order retries, a `new_client_order_id` is generated if missing,
+    /// and the order is sent again only if no order with this id was found after a failure.
+    /// The order status is queried twice, one second apart, as a slow order may not be visible yet.
+    /// An order taking longer than that to land can still be placed twice, in which case the second one
+    /// is rejected by Binance for reusing the client order id if the first one is still open.
+    pub async fn place_order(&self, mut order: OrderRequest) -> Result<Transaction> {
         order.valid()?;
         let recv_window = order.recv_window.unwrap_or(self.recv_window);
-        let request = build_signed_request_p(order, recv_window)?;
-        self.client.post_signed(API_V3_ORDER, &request).await
+        let retry_policy = match self.client.retry_policy() {
+            Some(retry_policy) if retry_policy.retry_orders => retry_policy.clone(),
+            _ => {
+                let request = build_signed_request_p(order, recv_window)?;
+                return self.client.post_signed(API_V3_ORDER, &request).await;
+            }
+        };
+        let client_order_id = match &order.new_client_order_id {
+            Some(client_order_id) => client_order_id.clone(),
+            None => new_client_order_id()?,
+        };
+        order.new_client_order_id = Some(client_order_id.clone());
+        let mut attempt = 1;
+        loop {
+            let request = build_signed_request_p(&order, recv_window)?;
+            let error = match self.client.post_signed(API_V3_ORDER, &request).await {
+                Err(e) => e,
+                result => return result,
+            };
+            let delay = match retry_policy.retry_delay(attempt, &error) {
+                Some(delay) => delay,
+                None => return Err(error),
+            };
+            tokio::time::sleep(delay).await;
+            match self.find_order(&order.symbol, &client_order_id).await {
+                Ok(Some(placed)) => return Ok(placed.into()),
+                Ok(None) => attempt += 1,
+                Err(_) => return Err(error),
+            }
+        }
+    }
+
+    /// Find an order by client order id, querying again after a delay if it does not exist yet
+    async fn find_order(&self, symbol: &str, client_order_id: &str) -> Result<Option<Order>> {
+        for recheck in [false, true] {
+            if recheck {
+                tokio::time::sleep(ORDER_STATUS_RECHECK_DELAY).await;
+            }
+            let query = OrderStatusRequest {
+                symbol: symbol.to_string(),
+                orig_client_order_id: Some(client_order_id.to_string()),
+                ..OrderStatusRequest::default()
+            };
+            match self.order_status(query).await {
+                Ok(placed) => return Ok(Some(placed)),
+                Err(Error::BinanceError { response }) if response.code == ORDER_DOES_NOT_EXIST => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(None)
     }
 
     /// Place a test order
@@ -324,8 +892,8 @@ impl Account {
     /// let account: Account = Binance::new_with_env(&Config::testnet());
     /// let limit_buy = OrderRequest {
     ///         symbol: "BTCUSDT".to_string(),
-    ///         quantity: Some(10.0),
-    ///         price: Some(0.014000),
+    ///         quantity: Some("10.0".parse().unwrap()),
+    ///         price: Some("0.014000".parse().unwrap()),
     ///         order_type: OrderType::Limit,
     ///         side: OrderSide::Buy,
     ///         time_in_force: Some(TimeInForce::FOK),
@@ -408,4 +976,422 @@ impl Account {
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client.get_signed(API_V3_MYTRADES, &request).await
     }
+
+    /// Every trade of the account on `symbol` between `start_time` and `end_time` (inclusive, in ms), in time order
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// use futures::TryStreamExt;
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let trades = account.trade_history_stream("BTCUSDT", 1_672_531_200_000, 1_675_209_600_000);
+    /// let trades: Vec<_> = tokio_test::block_on(trades.try_collect()).unwrap();
+    /// ```
+    pub fn trade_history_stream<S>(
+        &self,
+        symbol: S,
+        start_time: u64,
+        end_time: u64,
+    ) -> BoxStream<'_, Result<TradeHistory>>
+    where
+        S: Into<String>,
+    {
+        let paging = Paging {
+            max_window: Some(DAY_MS),
+            limit: 1000,
+            by_offset: false,
+        };
+        let symbol = symbol.into();
+        let fetch = move |page: Page| {
+            let parameters = [
+                ("symbol", symbol.clone()),
+                ("startTime", page.start_time.to_string()),
+                ("endTime", page.end_time.to_string()),
+                ("limit", page.limit.to_string()),
+            ];
+            async move {
+                let request = build_signed_request(parameters, self.recv_window)?;
+                self.client.get_signed(API_V3_MYTRADES, &request).await
+            }
+        };
+        paginate(paging, start_time, end_time, fetch, |t| t.time, |t| t.id)
+    }
+
+    /// Place an OCO order with the deprecated `order/oco` endpoint
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*, rest_model::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let oco = OCORequest {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     side: OrderSide::Sell,
+    ///     quantity: "0.01".parse().unwrap(),
+    ///     price: "40000.0".parse().unwrap(),
+    ///     stop_price: "30000.0".parse().unwrap(),
+    ///     stop_limit_price: Some("29900.0".parse().unwrap()),
+    ///     stop_limit_time_in_force: Some(TimeInForce::GTC),
+    ///     ..OCORequest::default()
+    /// };
+    /// let order_list = tokio_test::block_on(account.place_oco_order(oco));
+    /// assert!(order_list.is_ok(), "{:?}", order_list);
+    /// ```
+    pub async fn place_oco_order(&self, order: OCORequest) -> Result<OrderList> {
+        order.valid()?;
+        let recv_window = order.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(order, recv_window)?;
+        self.client.post_signed(API_V3_ORDER_OCO, &request).await
+    }
+
+    /// Place an OCO order list
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*, rest_model::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let oco = OrderListOCORequest {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     side: OrderSide::Sell,
+    ///     quantity: "0.01".parse().unwrap(),
+    ///     above_type: OrderType::LimitMaker,
+    ///     above_price: Some("40000.0".parse().unwrap()),
+    ///     below_type: OrderType::StopLoss,
+    ///     below_stop_price: Some("30000.0".parse().unwrap()),
+    ///     ..OrderListOCORequest::default()
+    /// };
+    /// let order_list = tokio_test::block_on(account.place_order_list_oco(oco));
+    /// assert!(order_list.is_ok(), "{:?}", order_list);
+    /// ```
+    pub async fn place_order_list_oco(&self, order: OrderListOCORequest) -> Result<OrderList> {
+        order.valid()?;
+        let recv_window = order.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(order, recv_window)?;
+        self.client.post_signed(API_V3_ORDER_LIST_OCO, &request).await
+    }
+
+    /// Place an OTO order list
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*, rest_model::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let oto = OrderListOTORequest {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     working_side: OrderSide::Buy,
+    ///     working_quantity: "0.01".parse().unwrap(),
+    ///     working: OrderListLeg::limit("30000.0".parse().unwrap(), TimeInForce::GTC),
+    ///     pending_side: OrderSide::Sell,
+    ///     pending_quantity: "0.01".parse().unwrap(),
+    ///     pending: OrderListLeg::limit_maker("40000.0".parse().unwrap()),
+    ///     ..OrderListOTORequest::default()
+    /// };
+    /// let order_list = tokio_test::block_on(account.place_order_list_oto(oto));
+    /// assert!(order_list.is_ok(), "{:?}", order_list);
+    /// ```
+    pub async fn place_order_list_oto(&self, order: OrderListOTORequest) -> Result<OrderList> {
+        order.valid()?;
+        let recv_window = order.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(order, recv_window)?;
+        self.client.post_signed(API_V3_ORDER_LIST_OTO, &request).await
+    }
+
+    /// Place an OTOCO order list
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*, rest_model::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let otoco = OrderListOTOCORequest {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     working_side: OrderSide::Buy,
+    ///     working_quantity: "0.01".parse().unwrap(),
+    ///     working: OrderListLeg::limit("35000.0".parse().unwrap(), TimeInForce::GTC),
+    ///     pending_side: OrderSide::Sell,
+    ///     pending_quantity: "0.01".parse().unwrap(),
+    ///     pending_above: OrderListLeg::limit_maker("40000.0".parse().unwrap()),
+    ///     pending_below: OrderListLeg::stop_loss("30000.0".parse().unwrap()),
+    ///     ..OrderListOTOCORequest::default()
+    /// };
+    /// let order_list = tokio_test::block_on(account.place_order_list_otoco(otoco));
+    /// assert!(order_list.is_ok(), "{:?}", order_list);
+    /// ```
+    pub async fn place_order_list_otoco(&self, order: OrderListOTOCORequest) -> Result<OrderList> {
+        order.valid()?;
+        let recv_window = order.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(order, recv_window)?;
+        self.client.post_signed(API_V3_ORDER_LIST_OTOCO, &request).await
+    }
+
+    /// Cancel an entire order list
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let cancellation = OrderListCancellation {
+    ///     symbol: "BTCUSDT".to_string(),
+    ///     order_list_id: Some(1),
+    ///     ..OrderListCancellation::default()
+    /// };
+    /// let order_list = tokio_test::block_on(account.cancel_order_list(cancellation));
+    /// assert!(order_list.is_ok(), "{:?}", order_list);
+    /// ```
+    pub async fn cancel_order_list(&self, cancellation: OrderListCancellation) -> Result<OrderList> {
+        let recv_window = cancellation.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(cancellation, recv_window)?;
+        self.client.delete_signed(API_V3_ORDER_LIST, &request).await
+    }
+
+    /// Check an order list's status
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let query = OrderListStatusRequest {
+    ///     order_list_id: Some(1),
+    ///     ..OrderListStatusRequest::default()
+    /// };
+    /// let order_list = tokio_test::block_on(account.order_list_status(query));
+    /// assert!(order_list.is_ok(), "{:?}", order_list);
+    /// ```
+    pub async fn order_list_status(&self, query: OrderListStatusRequest) -> Result<OrderList> {
+        let recv_window = query.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(query, recv_window)?;
+        self.client.get_signed(API_V3_ORDER_LIST, &request).await
+    }
+
+    /// All order lists of the account
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let order_lists = tokio_test::block_on(account.all_order_lists(OrderListsQuery::default()));
+    /// assert!(order_lists.is_ok(), "{:?}", order_lists);
+    /// ```
+    pub async fn all_order_lists(&self, query: OrderListsQuery) -> Result<Vec<OrderList>> {
+        let recv_window = query.recv_window.unwrap_or(self.recv_window);
+        let request = build_signed_request_p(query, recv_window)?;
+        self.client.get_signed(API_V3_ALL_ORDER_LIST, &request).await
+    }
+
+    /// Open order lists of the account
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, account::*, config::*};
+    /// let account: Account = Binance::new_with_env(&Config::testnet());
+    /// let order_lists = tokio_test::block_on(account.open_order_lists());
+    /// assert!(order_lists.is_ok(), "{:?}", order_lists);
+    /// ```
+    pub async fn open_order_lists(&self) -> Result<Vec<OrderList>> {
+        let request = build_signed_request([("", "")], self.recv_window)?;
+        self.client.get_signed(API_V3_OPEN_ORDER_LIST, &request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oco_request() {
+        let oco = OrderListOCORequest {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Sell,
+            quantity: num(0.01),
+            above_type: OrderType::LimitMaker,
+            above_price: Some(num(40000.0)),
+            below_type: OrderType::StopLoss,
+            below_stop_price: Some(num(30000.0)),
+            ..OrderListOCORequest::default()
+        };
+        assert!(oco.valid().is_ok());
+        let buy = OrderListOCORequest {
+            side: OrderSide::Buy,
+            ..oco.clone()
+        };
+        assert!(buy.valid().is_err());
+        let inverted = OrderListOCORequest {
+            above_price: Some(num(20000.0)),
+            ..oco
+        };
+        assert!(inverted.valid().is_err());
+    }
+
+    #[test]
+    fn otoco_request() {
+        let mut otoco = OrderListOTOCORequest {
+            symbol: "BTCUSDT".to_string(),
+            working_side: OrderSide::Buy,
+            working_quantity: num(0.01),
+            working: OrderListLeg::limit(num(35000.0), TimeInForce::GTC).client_order_id("entry"),
+            pending_side: OrderSide::Sell,
+            pending_quantity: num(0.01),
+            pending_above: OrderListLeg::limit_maker(num(40000.0)),
+            pending_below: OrderListLeg::stop_loss(num(30000.0)).trailing_delta(100),
+            ..OrderListOTOCORequest::default()
+        };
+        assert!(otoco.valid().is_ok());
+        assert_eq!(
+            build_request_p(&otoco).unwrap(),
+            concat!(
+                "symbol=BTCUSDT&workingSide=BUY&workingQuantity=0.01&workingType=LIMIT",
+                "&workingClientOrderId=entry&workingPrice=35000&workingTimeInForce=GTC",
+                "&pendingSide=SELL&pendingQuantity=0.01&pendingAboveType=LIMIT_MAKER&pendingAbovePrice=40000",
+                "&pendingBelowType=STOP_LOSS&pendingBelowStopPrice=30000&pendingBelowTrailingDelta=100"
+            )
+        );
+        otoco.working = OrderListLeg::market();
+        assert!(otoco.valid().is_err());
+        otoco.working = OrderListLeg::limit_maker(num(35000.0));
+        otoco.pending_below = OrderListLeg::stop_loss_limit(num(29000.0), num(30000.0), TimeInForce::GTC);
+        assert!(otoco.valid().is_ok());
+        otoco.pending_below.time_in_force = None;
+        assert!(otoco.valid().is_err());
+
+        // a sell places the stop loss below, a buy above
+        otoco.pending_below = OrderListLeg::stop_loss(num(30000.0));
+        otoco.pending_above = OrderListLeg::take_profit(num(30000.0));
+        assert!(otoco.valid().is_err(), "prices on the wrong side");
+        otoco.pending_above = OrderListLeg::stop_loss(num(40000.0));
+        assert!(otoco.valid().is_err(), "two stop losses");
+        otoco.pending_below = OrderListLeg::limit_maker(num(30000.0));
+        assert!(otoco.valid().is_err(), "stop loss above for a sell");
+        otoco.pending_side = OrderSide::Buy;
+        assert!(otoco.valid().is_ok());
+        otoco.pending_below = OrderListLeg::take_profit(num(45000.0));
+        assert!(otoco.valid().is_err(), "prices on the wrong side");
+        otoco.pending_below = OrderListLeg::take_profit(num(45000.0)).trailing_delta(100);
+        otoco.pending_below.stop_price = None;
+        assert!(otoco.valid().is_ok(), "trailing orders have no price to compare");
+    }
+
+    #[cfg(feature = "mock")]
+    mod retries {
+        use super::*;
+        use crate::api::Binance;
+        use crate::mock::{Method, MockError, MockRequest, MockServer};
+        use crate::retry::RetryPolicy;
+
+        static ORDER: &str = r#"{
+            "symbol": "BTCUSDT", "orderId": 7, "orderListId": -1, "clientOrderId": "placed", "price": "30000.0",
+            "origQty": "0.01", "executedQty": "0.0", "cummulativeQuoteQty": "0.0", "status": "NEW",
+            "timeInForce": "GTC", "type": "LIMIT", "side": "BUY", "stopPrice": "0.0", "icebergQty": "0.0",
+            "time": 1, "updateTime": 1, "isWorking": true, "origQuoteOrderQty": "0.0"
+        }"#;
+        static TRANSACTION: &str = r#"{
+            "symbol": "BTCUSDT", "orderId": 8, "clientOrderId": "sent", "transactTime": 1, "price": "30000.0",
+            "origQty": "0.01", "executedQty": "0.0", "cummulativeQuoteQty": "0.0", "status": "NEW",
+            "timeInForce": "GTC", "type": "LIMIT", "side": "BUY", "fills": []
+        }"#;
+
+        async fn account(retry_orders: bool) -> (MockServer, Account) {
+            let server = MockServer::start().await.unwrap();
+            server.set_credentials("api_key", "secret_key");
+            let config = server.config().set_retry_policy(RetryPolicy {
+                initial_backoff: Duration::from_millis(20),
+                retry_orders,
+                ..RetryPolicy::default()
+            });
+            let account =
+                Account::new_with_config(Some("api_key".to_string()), Some("secret_key".to_string()), &config);
+            (server, account)
+        }
+
+        fn limit_buy() -> OrderRequest {
+            OrderRequest {
+                symbol: "BTCUSDT".to_string(),
+                quantity: Some(num(0.01)),
+                price: Some(num(30000.0)),
+                order_type: OrderType::Limit,
+                side: OrderSide::Buy,
+                time_in_force: Some(TimeInForce::GTC),
+                ..OrderRequest::default()
+            }
+        }
+
+        fn requests(server: &MockServer, path: &str) -> Vec<MockRequest> {
+            server
+                .requests()
+                .into_iter()
+                .filter(|request| request.path == path)
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn get_retried_with_new_timestamp() {
+            let (server, account) = account(false).await;
+            server.mock_signed(Method::GET, API_V3_OPEN_ORDERS, format!("[{ORDER}]"));
+            server.inject_error(API_V3_OPEN_ORDERS, MockError::ServiceUnavailable);
+            server.inject_error(API_V3_OPEN_ORDERS, MockError::Binance {
+                code: crate::retry::DISCONNECTED,
+                msg: "Internal error; unable to process your request. Please try again.".to_string(),
+            });
+            let orders = account.get_open_orders("BTCUSDT").await.unwrap();
+            assert_eq!(orders.len(), 1);
+
+            let sent = requests(&server, API_V3_OPEN_ORDERS);
+            assert_eq!(sent.len(), 3);
+            for (previous, next) in sent.iter().zip(&sent[1..]) {
+                let timestamp = |request: &MockRequest| request.param("timestamp").unwrap().parse::<u64>().unwrap();
+                assert!(timestamp(previous) < timestamp(next));
+                assert_ne!(previous.param("signature"), next.param("signature"));
+            }
+        }
+
+        #[tokio::test]
+        async fn other_errors_not_retried() {
+            let (server, account) = account(false).await;
+            server.mock_signed(Method::GET, API_V3_OPEN_ORDERS, "[]");
+            server.inject_error(API_V3_OPEN_ORDERS, MockError::Binance {
+                code: -1121,
+                msg: "Invalid symbol.".to_string(),
+            });
+            let result = account.get_open_orders("BTCUSDT").await;
+            assert!(matches!(result, Err(Error::BinanceError { ref response }) if response.code == -1121));
+            assert_eq!(requests(&server, API_V3_OPEN_ORDERS).len(), 1);
+
+            // orders are not retried unless `retry_orders` is set
+            server.mock_signed(Method::POST, API_V3_ORDER, TRANSACTION);
+            server.inject_error(API_V3_ORDER, MockError::ServiceUnavailable);
+            assert!(matches!(
+                account.place_order(limit_buy()).await,
+                Err(Error::ServiceUnavailable)
+            ));
+            assert_eq!(requests(&server, API_V3_ORDER).len(), 1);
+        }
+
+        #[tokio::test]
+        async fn landed_order_not_sent_again() {
+            let (server, account) = account(true).await;
+            server.mock_signed(Method::POST, API_V3_ORDER, TRANSACTION);
+            server.mock_signed(Method::GET, API_V3_ORDER, ORDER);
+            server.inject_error(API_V3_ORDER, MockError::ServiceUnavailable);
+            let transaction = account.place_order(limit_buy()).await.unwrap();
+            assert_eq!(transaction.order_id, 7);
+
+            let sent = requests(&server, API_V3_ORDER);
+            let methods: Vec<_> = sent.iter().map(|request| request.method.clone()).collect();
+            assert_eq!(methods, vec![Method::POST, Method::GET]);
+            assert!(sent[0].param("newClientOrderId").is_some());
+            assert_eq!(sent[1].param("origClientOrderId"), sent[0].param("newClientOrderId"));
+        }
+
+        #[tokio::test]
+        async fn missing_order_sent_again() {
+            let (server, account) = account(true).await;
+            server.mock_signed(Method::POST, API_V3_ORDER, TRANSACTION);
+            server.inject_error(API_V3_ORDER, MockError::ServiceUnavailable);
+            for _ in 0..2 {
+                server.inject_error(API_V3_ORDER, MockError::Binance {
+                    code: ORDER_DOES_NOT_EXIST,
+                    msg: "Order does not exist.".to_string(),
+                });
+            }
+            let transaction = account.place_order(limit_buy()).await.unwrap();
+            assert_eq!(transaction.order_id, 8);
+
+            let sent = requests(&server, API_V3_ORDER);
+            let methods: Vec<_> = sent.iter().map(|request| request.method.clone()).collect();
+            assert_eq!(methods, vec![Method::POST, Method::GET, Method::GET, Method::POST]);
+            let client_order_id = sent[0].param("newClientOrderId");
+            assert!(client_order_id.is_some());
+            assert_eq!(sent[1].param("origClientOrderId"), client_order_id);
+            assert_eq!(sent[3].param("newClientOrderId"), client_order_id);
+            assert_ne!(sent[3].param("signature"), sent[0].param("signature"));
+        }
+    }
 }
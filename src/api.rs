@@ -1,8 +1,12 @@
+use std::sync::Arc;
+
 use crate::account::*;
 use crate::client::*;
 use crate::config::Config;
+use crate::errors::*;
 use crate::general::*;
 use crate::market::*;
+use crate::signer::{self, Signer};
 use crate::userstream::*;
 
 pub trait Binance: Sized {
@@ -19,13 +23,45 @@ pub trait Binance: Sized {
         Self::new_with_config(api_key, secret, config)
     }
 
+    /// Create a binance API signing requests with a custom signer
+    fn new_with_signer(api_key: Option<String>, signer: Arc<dyn Signer>, config: &Config) -> Self {
+        Self::new_with_config(api_key, None, &config.clone().set_signer(signer))
+    }
+
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self;
+
+    /// Like [`Binance::new_with_config`], failing with [`Error::InvalidSigningKey`] if the secret key
+    /// is not a valid key of the signature type of the config.
+    /// Other constructors defer this error to the first signed request.
+    fn try_new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Result<Self> {
+        signer::try_configured_signer(config, secret_key.as_deref())?;
+        Ok(Self::new_with_config(api_key, secret_key, config))
+    }
+}
+
+/// A client of `host` with the signer, rate limiter and retry policy of the config
+fn configured_client(api_key: Option<String>, secret_key: Option<String>, host: &str, config: &Config) -> Client {
+    Client::new(api_key, None, host.to_string(), config.timeout)
+        .with_signer(signer::configured_signer(config, secret_key.as_deref()))
+        .with_retry_policy(config.retry_policy.as_ref())
+        .with_time_sync(config.time_sync_interval)
+}
+
+fn client(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Client {
+    configured_client(api_key, secret_key, &config.rest_api_endpoint, config)
+        .with_rate_limiter(config.rate_limiter.as_ref())
+}
+
+#[cfg(feature = "futures_api")]
+fn futures_client(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Client {
+    configured_client(api_key, secret_key, &config.futures_rest_api_endpoint, config)
+        .with_rate_limiter(config.futures_rate_limiter.as_ref())
 }
 
 impl Binance for General {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> General {
         General {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: client(api_key, secret_key, config),
         }
     }
 }
@@ -33,7 +69,7 @@ impl Binance for General {
 impl Binance for Account {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Account {
         Account {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
@@ -43,7 +79,7 @@ impl Binance for Account {
 impl Binance for crate::savings::Savings {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
         Self {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
@@ -52,7 +88,7 @@ impl Binance for crate::savings::Savings {
 impl Binance for Market {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Market {
         Market {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
@@ -61,7 +97,7 @@ impl Binance for Market {
 impl Binance for UserStream {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> UserStream {
         UserStream {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
@@ -71,12 +107,7 @@ impl Binance for UserStream {
 impl Binance for crate::futures::general::FuturesGeneral {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
         Self {
-            client: Client::new(
-                api_key,
-                secret_key,
-                config.futures_rest_api_endpoint.clone(),
-                config.timeout,
-            ),
+            client: futures_client(api_key, secret_key, config),
         }
     }
 }
@@ -85,12 +116,7 @@ impl Binance for crate::futures::general::FuturesGeneral {
 impl Binance for crate::futures::market::FuturesMarket {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
         Self {
-            client: Client::new(
-                api_key,
-                secret_key,
-                config.futures_rest_api_endpoint.clone(),
-                config.timeout,
-            ),
+            client: futures_client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
@@ -100,12 +126,17 @@ impl Binance for crate::futures::market::FuturesMarket {
 impl Binance for crate::futures::account::FuturesAccount {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
         Self {
-            client: Client::new(
-                api_key,
-                secret_key,
-                config.futures_rest_api_endpoint.clone(),
-                config.timeout,
-            ),
+            client: futures_client(api_key, secret_key, config),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+#[cfg(feature = "futures_api")]
+impl Binance for crate::futures::userstream::FuturesUserStream {
+    fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
+        Self {
+            client: futures_client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
@@ -115,7 +146,7 @@ impl Binance for crate::futures::account::FuturesAccount {
 impl Binance for crate::margin::Margin {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
         Self {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: client(api_key, secret_key, config),
             recv_window: config.recv_window,
         }
     }
@@ -125,7 +156,7 @@ impl Binance for crate::margin::Margin {
 impl Binance for crate::wallet::Wallet {
     fn new_with_config(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Self {
         Self {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone(), config.timeout),
+            client: client(api_key, secret_key, config),
             recv_window: config.recv_window,
             binance_us_api: config.binance_us_api,
         }
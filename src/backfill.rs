@@ -0,0 +1,466 @@
+//! Kline history backfill.
+//!
+//! The time range is split in chunks of the maximum number of klines of a request, which are fetched
+//! concurrently. Requests go through the client of the market and its rate limiter, configure one with
+//! [`RateLimitMode::Delay`](crate::rate_limit::RateLimitMode::Delay) to wait rather than fail on long ranges.
+//! Klines missing from the exchange history, usually because of an exchange outage, are requested once more
+//! and otherwise filled with flat klines at the previous close, so that the series has a kline for every
+//! interval. The series can then be kept current with the klines of
+//! [`kline_stream`](crate::websockets::kline_stream).
+//!
+//! # Examples
+//! ```rust,no_run
+//! use binance::{api::*, backfill::*, config::*, market::*, rest_model::KlineInterval, websockets::*,
+//!               ws_model::WebsocketEvent};
+//! use futures::StreamExt;
+//!
+//! tokio_test::block_on(async {
+//!     let market: Market = Binance::new(None, None);
+//!     let backfill = KlineBackfill::new(market).with_concurrency(8);
+//!     let interval = KlineInterval::OneMinute;
+//!     let mut series = backfill.fetch("BTCUSDT", interval, 1_672_531_200_000, 1_675_209_599_999).await?;
+//!     println!("{} klines, missing from the exchange: {:?}", series.klines.len(), series.gaps);
+//!
+//!     let mut events: WebsocketStream<'_, WebsocketEvent> = WebsocketStream::new(Config::default());
+//!     events.connect(&kline_stream("btcusdt", interval)).await?;
+//!     while let Some(event) = events.next().await {
+//!         if let WebsocketEvent::Kline(event) = event? {
+//!             backfill.merge(&mut series, &event.kline).await?;
+//!         }
+//!     }
+//!     Ok::<(), binance::errors::Error>(())
+//! });
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{Months, TimeZone, Utc};
+use futures::future::BoxFuture;
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+use crate::errors::*;
+use crate::market::Market;
+use crate::rest_model::{KlineInterval, KlineSummary, Number};
+use crate::ws_model::Kline;
+
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Where klines are fetched from
+pub trait KlineSource: Send + Sync {
+    /// Maximum number of klines of a request
+    fn max_limit(&self) -> u16;
+
+    /// Klines of `symbol` opened between `start_time` and `end_time` (inclusive, in ms)
+    fn fetch_klines<'a>(
+        &'a self,
+        symbol: &'a str,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+        limit: u16,
+    ) -> BoxFuture<'a, Result<Vec<KlineSummary>>>;
+}
+
+impl KlineSource for Market {
+    fn max_limit(&self) -> u16 { 1000 }
+
+    fn fetch_klines<'a>(
+        &'a self,
+        symbol: &'a str,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+        limit: u16,
+    ) -> BoxFuture<'a, Result<Vec<KlineSummary>>> {
+        Box::pin(self.get_klines(symbol, interval, limit, start_time, end_time))
+    }
+}
+
+#[cfg(feature = "futures_api")]
+impl KlineSource for crate::futures::market::FuturesMarket {
+    fn max_limit(&self) -> u16 { 1500 }
+
+    fn fetch_klines<'a>(
+        &'a self,
+        symbol: &'a str,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+        limit: u16,
+    ) -> BoxFuture<'a, Result<Vec<KlineSummary>>> {
+        Box::pin(self.get_klines(symbol, interval, limit, start_time, end_time))
+    }
+}
+
+/// Time range without klines in the exchange history, inclusive in ms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KlineGap {
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+/// Klines of a symbol in time order, with a kline for every interval between the first and the last one
+#[derive(Debug, Clone, PartialEq)]
+pub struct KlineSeries {
+    pub symbol: String,
+    pub interval: KlineInterval,
+    pub klines: Vec<KlineSummary>,
+    /// Klines missing from the exchange history, filled in `klines` at the previous close without volume.
+    /// Gaps at the start or at the end of a fetched range are not filled, and include the time before a listing.
+    pub gaps: Vec<KlineGap>,
+}
+
+impl KlineSeries {
+    pub fn new(symbol: &str, interval: KlineInterval) -> Self {
+        KlineSeries {
+            symbol: symbol.to_string(),
+            interval,
+            klines: vec![],
+            gaps: vec![],
+        }
+    }
+
+    /// Open time of the kline following the last one of the series
+    pub fn next_open_time(&self) -> Option<i64> {
+        self.klines
+            .last()
+            .map(|kline| next_open_time(self.interval, kline.open_time))
+    }
+
+    /// Appends a kline, filling the gap since the last kline of the series. A kline with the same open time
+    /// as the last one replaces it, older klines are ignored.
+    pub fn push(&mut self, kline: KlineSummary) {
+        let Some(last) = self.klines.last_mut() else {
+            self.klines.push(kline);
+            return;
+        };
+        if kline.open_time <= last.open_time {
+            if kline.open_time == last.open_time {
+                *last = kline;
+            }
+            return;
+        }
+        let close = last.close;
+        let mut open_time = next_open_time(self.interval, last.open_time);
+        if open_time < kline.open_time {
+            self.gaps.push(KlineGap {
+                start_time: open_time,
+                end_time: kline.open_time - 1,
+            });
+            while open_time < kline.open_time {
+                let next = next_open_time(self.interval, open_time);
+                self.klines.push(flat_kline(open_time, next - 1, close));
+                open_time = next;
+            }
+        }
+        self.klines.push(kline);
+    }
+}
+
+/// Fetches contiguous kline series
+pub struct KlineBackfill<T> {
+    source: T,
+    concurrency: usize,
+}
+
+impl<T: KlineSource> KlineBackfill<T> {
+    pub fn new(source: T) -> Self {
+        KlineBackfill {
+            source,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+
+    /// Maximum number of requests in flight, 4 by default
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// The klines of `symbol` opened between `start_time` and `end_time` (inclusive, in ms). The series starts
+    /// at the first kline of the exchange in the range, gaps are requested once more before being filled.
+    pub async fn fetch(
+        &self,
+        symbol: &str,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<KlineSeries> {
+        let mut klines: BTreeMap<i64, KlineSummary> = self
+            .fetch_range(symbol, interval, start_time, end_time)
+            .await?
+            .into_iter()
+            .map(|kline| (kline.open_time, kline))
+            .collect();
+        let (start, end) = (start_time as i64, end_time.min(i64::MAX as u64) as i64);
+        let gaps = gaps(interval, start, end, klines.keys().copied());
+        let repaired: Vec<Vec<KlineSummary>> = stream::iter(gaps)
+            .map(|gap| self.fetch_range(symbol, interval, gap.start_time as u64, gap.end_time as u64))
+            .buffered(self.concurrency)
+            .try_collect()
+            .await?;
+        klines.extend(repaired.into_iter().flatten().map(|kline| (kline.open_time, kline)));
+        let mut series = KlineSeries::new(symbol, interval);
+        let gaps = self::gaps(interval, start, end, klines.keys().copied());
+        for kline in klines.into_values() {
+            series.push(kline);
+        }
+        // The gaps filled by `push` along with the gaps at the edges
+        series.gaps = gaps;
+        Ok(series)
+    }
+
+    /// Appends a kline of [`kline_stream`](crate::websockets::kline_stream) to the series, updates of the last
+    /// kline replace it. Klines missed since the end of the series, for instance while the stream was
+    /// reconnecting, are fetched first.
+    pub async fn merge(&self, series: &mut KlineSeries, kline: &Kline) -> Result<()> {
+        if kline.interval != series.interval || !kline.symbol.eq_ignore_ascii_case(&series.symbol) {
+            return Err(Error::Msg(format!(
+                "kline of {} {} merged in the series of {} {}",
+                kline.symbol, kline.interval, series.symbol, series.interval
+            )));
+        }
+        if let Some(next) = series.next_open_time().filter(|next| *next < kline.start_time) {
+            let (start_time, end_time) = (next as u64, kline.start_time as u64 - 1);
+            let missed = self
+                .fetch_range(&series.symbol, series.interval, start_time, end_time)
+                .await?;
+            for missed in missed {
+                series.push(missed);
+            }
+        }
+        series.push(KlineSummary::from(kline));
+        Ok(())
+    }
+
+    /// Fetches the chunks of the range concurrently, in time order
+    async fn fetch_range(
+        &self,
+        symbol: &str,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<KlineSummary>> {
+        let limit = self.source.max_limit();
+        let span = interval
+            .duration_ms()
+            .map_or(u64::MAX, |duration| duration * limit as u64);
+        let mut chunks = vec![];
+        let mut start = start_time;
+        while start <= end_time {
+            let end = start.saturating_add(span - 1).min(end_time);
+            chunks.push((start, end));
+            if end == end_time {
+                break;
+            }
+            start = end + 1;
+        }
+        let pages: Vec<Vec<KlineSummary>> = stream::iter(chunks)
+            .map(|(start, end)| self.source.fetch_klines(symbol, interval, start, end, limit))
+            .buffered(self.concurrency)
+            .try_collect()
+            .await?;
+        Ok(pages.into_iter().flatten().collect())
+    }
+}
+
+/// Ranges of `start_time..=end_time` without klines, between klines that are not consecutive and before the
+/// first or after the last kline. Klines are not expected to open after the current time.
+fn gaps(
+    interval: KlineInterval,
+    start_time: i64,
+    end_time: i64,
+    open_times: impl Iterator<Item = i64>,
+) -> Vec<KlineGap> {
+    let mut gaps = vec![];
+    let mut next = None;
+    for open_time in open_times {
+        let gap_start = match next {
+            Some(next) => next,
+            None if previous_open_time(interval, open_time) >= start_time => start_time,
+            None => open_time,
+        };
+        if gap_start < open_time {
+            gaps.push(KlineGap {
+                start_time: gap_start,
+                end_time: open_time - 1,
+            });
+        }
+        next = Some(next_open_time(interval, open_time));
+    }
+    let end_time = end_time.min(Utc::now().timestamp_millis());
+    let gap_start = next.unwrap_or(start_time);
+    if gap_start <= end_time {
+        gaps.push(KlineGap {
+            start_time: gap_start,
+            end_time,
+        });
+    }
+    gaps
+}
+
+/// Months vary in length, other intervals have a fixed duration
+fn next_open_time(interval: KlineInterval, open_time: i64) -> i64 {
+    match interval.duration_ms() {
+        Some(duration) => open_time + duration as i64,
+        None => Utc
+            .timestamp_millis_opt(open_time)
+            .single()
+            .and_then(|time| time.checked_add_months(Months::new(1)))
+            .map_or(i64::MAX, |time| time.timestamp_millis()),
+    }
+}
+
+fn previous_open_time(interval: KlineInterval, open_time: i64) -> i64 {
+    match interval.duration_ms() {
+        Some(duration) => open_time - duration as i64,
+        None => Utc
+            .timestamp_millis_opt(open_time)
+            .single()
+            .and_then(|time| time.checked_sub_months(Months::new(1)))
+            .map_or(i64::MIN, |time| time.timestamp_millis()),
+    }
+}
+
+/// A kline without trades, at `price`
+fn flat_kline(open_time: i64, close_time: i64, price: Number) -> KlineSummary {
+    KlineSummary {
+        open_time,
+        open: price,
+        high: price,
+        low: price,
+        close: price,
+        volume: Number::default(),
+        close_time,
+        quote_asset_volume: Number::default(),
+        number_of_trades: 0,
+        taker_buy_base_asset_volume: Number::default(),
+        taker_buy_quote_asset_volume: Number::default(),
+        ignore: "0".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::util::num;
+
+    const MINUTE: i64 = 60_000;
+
+    fn kline(minute: i64, close: f64) -> KlineSummary {
+        KlineSummary {
+            volume: num(1.0),
+            number_of_trades: 1,
+            ..flat_kline(minute * MINUTE, (minute + 1) * MINUTE - 1, num(close))
+        }
+    }
+
+    /// Serves `klines` with pages of 2 klines, `flaky` minutes are missing from the first response including them
+    struct FakeSource {
+        klines: Vec<KlineSummary>,
+        flaky: Mutex<Vec<i64>>,
+        requests: Mutex<Vec<(u64, u64)>>,
+    }
+
+    impl KlineSource for FakeSource {
+        fn max_limit(&self) -> u16 { 2 }
+
+        fn fetch_klines<'a>(
+            &'a self,
+            _symbol: &'a str,
+            _interval: KlineInterval,
+            start_time: u64,
+            end_time: u64,
+            limit: u16,
+        ) -> BoxFuture<'a, Result<Vec<KlineSummary>>> {
+            self.requests.lock().unwrap().push((start_time, end_time));
+            let mut flaky = self.flaky.lock().unwrap();
+            let klines = self
+                .klines
+                .iter()
+                .filter(|k| (start_time as i64..=end_time as i64).contains(&k.open_time))
+                .filter(
+                    |k| match flaky.iter().position(|minute| *minute == k.open_time / MINUTE) {
+                        Some(i) => flaky.remove(i) < 0,
+                        None => true,
+                    },
+                )
+                .take(limit as usize)
+                .cloned()
+                .collect();
+            Box::pin(async move { Ok(klines) })
+        }
+    }
+
+    #[tokio::test]
+    async fn fills_and_repairs_gaps() {
+        // Minutes 0, 4 and 5 are missing from the exchange, minutes 2 and 7 from the first response
+        let source = FakeSource {
+            klines: vec![
+                kline(1, 10.0),
+                kline(2, 11.0),
+                kline(3, 12.0),
+                kline(6, 13.0),
+                kline(7, 14.0),
+            ],
+            flaky: Mutex::new(vec![2, 7]),
+            requests: Mutex::new(vec![]),
+        };
+        let backfill = KlineBackfill::new(source).with_concurrency(2);
+        let series = backfill
+            .fetch("BTCUSDT", KlineInterval::OneMinute, 0, 8 * MINUTE as u64 - 1)
+            .await
+            .unwrap();
+        let requests = backfill.source.requests.lock().unwrap().clone();
+        // 4 chunks and 4 gaps
+        assert_eq!(requests.len(), 8, "{requests:?}");
+        let open_times: Vec<i64> = series.klines.iter().map(|k| k.open_time / MINUTE).collect();
+        assert_eq!(open_times, vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(series.klines[1], kline(2, 11.0));
+        assert_eq!(series.klines[4].close, num(12.0));
+        assert_eq!(series.klines[4].volume, Number::default());
+        let gap = |start: i64, end: i64| KlineGap {
+            start_time: start * MINUTE,
+            end_time: end * MINUTE - 1,
+        };
+        assert_eq!(series.gaps, vec![gap(0, 1), gap(4, 6)]);
+    }
+
+    #[test]
+    fn gaps_at_the_edges() {
+        let open_times = || [2, 3, 5].into_iter().map(|minute| minute * MINUTE);
+        let gap = |start: i64, end: i64| KlineGap {
+            start_time: start * MINUTE,
+            end_time: end * MINUTE - 1,
+        };
+        let interval = KlineInterval::OneMinute;
+        let gaps = gaps(interval, 0, 8 * MINUTE - 1, open_times());
+        assert_eq!(gaps, vec![gap(0, 2), gap(4, 5), gap(6, 8)]);
+        // no kline opens between the start of the range and the first kline
+        let gaps = self::gaps(interval, MINUTE + 1, 6 * MINUTE - 1, open_times());
+        assert_eq!(gaps, vec![gap(4, 5)]);
+        let gaps = self::gaps(interval, 0, 10 * MINUTE - 1, std::iter::empty());
+        assert_eq!(gaps, vec![gap(0, 10)]);
+    }
+
+    #[test]
+    fn push_replaces_updates() {
+        let mut series = KlineSeries::new("BTCUSDT", KlineInterval::OneMinute);
+        series.push(kline(1, 10.0));
+        series.push(kline(2, 11.0));
+        series.push(kline(2, 12.0));
+        series.push(kline(1, 13.0));
+        assert_eq!(series.klines, vec![kline(1, 10.0), kline(2, 12.0)]);
+        assert_eq!(series.next_open_time(), Some(3 * MINUTE));
+        assert!(series.gaps.is_empty());
+    }
+
+    #[test]
+    fn months_vary_in_length() {
+        let february = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap().timestamp_millis();
+        let march = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap().timestamp_millis();
+        assert_eq!(next_open_time(KlineInterval::OneMonth, february), march);
+    }
+}
@@ -1,24 +1,41 @@
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use boolinator::Boolinator;
-use hex::encode as hex_encode;
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, USER_AGENT};
-use reqwest::Response;
+use chrono::Utc;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, RETRY_AFTER, USER_AGENT};
 use reqwest::StatusCode;
-use ring::hmac;
+use reqwest::{Method, Response};
 use serde::de;
 use serde::de::DeserializeOwned;
+use url::form_urlencoded;
 
 use crate::errors::error_messages;
 use crate::errors::*;
-use crate::util::{build_request_p, build_signed_request_p};
+use crate::rate_limit::{self, RateLimiter, RateLimiterConfig};
+use crate::rest_model::ServerTime;
+use crate::retry::RetryPolicy;
+use crate::signer::{HmacSigner, Signer};
+use crate::util::{build_request_p, build_signed_request_p, refresh_timestamp, set_time_offset};
+
+/// Binance code of "Timestamp for this request is outside of the recvWindow."
+const TIMESTAMP_OUTSIDE_RECV_WINDOW: i32 = -1021;
+
+lazy_static! {
+    /// The clock offset is shared by all the clients of a host
+    static ref LAST_TIME_SYNC: Mutex<HashMap<String, Instant>> = Mutex::new(HashMap::new());
+}
 
 #[derive(Clone)]
 pub struct Client {
     api_key: String,
-    secret_key: String,
+    signer: Arc<dyn Signer>,
     inner: reqwest::Client,
     host: String,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    retry_policy: Option<RetryPolicy>,
+    time_sync_interval: Option<Duration>,
 }
 
 impl Client {
@@ -33,17 +50,46 @@ impl Client {
         Client {
             // Does it ever make sense for api_key and secret_key to be ""?
             api_key: api_key.unwrap_or_else(|| "".into()),
-            secret_key: secret_key.unwrap_or_else(|| "".into()),
+            signer: Arc::new(HmacSigner::new(secret_key.as_deref().unwrap_or_default())),
             inner: builder.build().unwrap(),
             host,
+            rate_limiter: None,
+            retry_policy: None,
+            time_sync_interval: None,
         }
     }
 
-    pub async fn get_signed<T: DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let response = self.inner.get(&url).headers(self.build_headers(true)?).send().await?;
+    /// Limits requests with the limiter shared by all the clients of the host
+    pub(crate) fn with_rate_limiter(mut self, config: Option<&RateLimiterConfig>) -> Self {
+        self.rate_limiter = config.map(|config| rate_limit::register(&self.host, config));
+        self
+    }
 
-        self.handler(response).await
+    /// The rate limiter of this client, if one was configured
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> { self.rate_limiter.as_deref() }
+
+    /// Signs requests with `signer` instead of the HMAC secret key
+    pub(crate) fn with_signer(mut self, signer: Arc<dyn Signer>) -> Self {
+        self.signer = signer;
+        self
+    }
+
+    pub(crate) fn with_retry_policy(mut self, retry_policy: Option<&RetryPolicy>) -> Self {
+        self.retry_policy = retry_policy.cloned();
+        self
+    }
+
+    /// The retry policy of this client, if one was configured
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> { self.retry_policy.as_ref() }
+
+    /// Delay before retrying a request that failed with `error`, `None` if it should not be retried
+    fn retry_delay(&self, attempt: u32, error: &Error) -> Option<Duration> {
+        self.retry_policy.as_ref()?.retry_delay(attempt, error)
+    }
+
+    /// Retried according to the retry policy, with a new timestamp for each attempt
+    pub async fn get_signed<T: DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
+        self.signed(Method::GET, endpoint, request).await
     }
 
     pub async fn get_signed_d<T: de::DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
@@ -61,10 +107,7 @@ impl Client {
     }
 
     pub async fn post_signed<T: DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let response = self.inner.post(&url).headers(self.build_headers(true)?).send().await?;
-
-        self.handler(response).await
+        self.signed(Method::POST, endpoint, request).await
     }
 
     pub async fn post_signed_d<T: de::DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
@@ -92,25 +135,28 @@ impl Client {
     }
 
     pub async fn delete_signed<T: DeserializeOwned>(&self, endpoint: &str, request: &str) -> Result<T> {
-        let url = self.sign_request(endpoint, request);
-        let response = self
-            .inner
-            .delete(&url)
-            .headers(self.build_headers(true)?)
-            .send()
-            .await?;
-
-        self.handler(response).await
+        self.signed(Method::DELETE, endpoint, request).await
     }
 
+    /// Retried according to the retry policy
     pub async fn get<T: DeserializeOwned>(&self, endpoint: &str, request: Option<&str>) -> Result<T> {
         let url = request
             .map(|r| format!("{}{}?{}", self.host, endpoint, r))
             .unwrap_or_else(|| format!("{}{}", self.host, endpoint));
 
-        let response = self.inner.get(&url).send().await?;
-
-        self.handler(response).await
+        let mut attempt = 1;
+        loop {
+            match self.request(Method::GET, endpoint, request, url.clone(), None).await {
+                Err(e) => match self.retry_delay(attempt, &e) {
+                    Some(delay) => {
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                    None => return Err(e),
+                },
+                result => return result,
+            }
+        }
     }
 
     pub async fn get_p<T: DeserializeOwned>(&self, endpoint: &str, request: Option<&str>) -> Result<T> {
@@ -135,9 +181,8 @@ impl Client {
             .map(|s| format!("{}{}?symbol={}", self.host, endpoint, s))
             .unwrap_or_else(|| format!("{}{}", self.host, endpoint));
 
-        let response = self.inner.post(url).headers(self.build_headers(false)?).send().await?;
-
-        self.handler(response).await
+        self.request(Method::POST, endpoint, None, url, Some(self.build_headers(false)?))
+            .await
     }
 
     pub async fn put<T: DeserializeOwned>(&self, endpoint: &str, listen_key: &str, symbol: Option<&str>) -> Result<T> {
@@ -146,9 +191,8 @@ impl Client {
             .unwrap_or_else(|| format!("listenKey={listen_key}"));
         let headers = self.build_headers(false)?;
         let url = format!("{}{}?{}", self.host, endpoint, data);
-        let response = self.inner.put(&url).headers(headers).send().await?;
-
-        self.handler(response).await
+        self.request(Method::PUT, endpoint, Some(&data), url, Some(headers))
+            .await
     }
 
     pub async fn delete<T: DeserializeOwned>(
@@ -161,23 +205,109 @@ impl Client {
             .map(|s| format!("listenKey={listen_key}&symbol={s}"))
             .unwrap_or_else(|| format!("listenKey={listen_key}"));
         let url = format!("{}{}?{}", self.host, endpoint, data);
-        let response = self
-            .inner
-            .delete(url)
-            .headers(self.build_headers(false)?)
-            .send()
-            .await?;
+        self.request(
+            Method::DELETE,
+            endpoint,
+            Some(&data),
+            url,
+            Some(self.build_headers(false)?),
+        )
+        .await
+    }
+
+    /// Sends a signed request, GET requests are retried according to the retry policy.
+    /// When time sync is enabled, the clock offset is refreshed once stale and the request is
+    /// sent again once after a resync if its timestamp was rejected.
+    async fn signed<T: DeserializeOwned>(&self, method: Method, endpoint: &str, request: &str) -> Result<T> {
+        if self.time_sync_is_stale() {
+            self.sync_time(server_time_endpoint(endpoint)).await?;
+        }
+        let mut request = refresh_timestamp(request, &self.host)?;
+        let mut attempt = 1;
+        let mut resynced = false;
+        loop {
+            let url = self.sign_request(endpoint, &request)?;
+            let headers = self.build_headers(true)?;
+            let can_resync = self.time_sync_interval.is_some() && !resynced;
+            match self
+                .request(method.clone(), endpoint, Some(&request), url, Some(headers))
+                .await
+            {
+                Err(Error::BinanceError { response }) if can_resync && is_timestamp_error(&response) => {
+                    self.sync_time(server_time_endpoint(endpoint)).await?;
+                    resynced = true;
+                }
+                Err(e) if method == Method::GET => match self.retry_delay(attempt, &e) {
+                    Some(delay) => {
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                    }
+                    None => return Err(e),
+                },
+                result => return result,
+            }
+            request = refresh_timestamp(&request, &self.host)?;
+        }
+    }
+
+    pub(crate) fn with_time_sync(mut self, interval: Option<Duration>) -> Self {
+        self.time_sync_interval = interval;
+        self
+    }
+
+    fn time_sync_is_stale(&self) -> bool {
+        match (self.time_sync_interval, LAST_TIME_SYNC.lock().unwrap().get(&self.host)) {
+            (Some(interval), Some(synced_at)) => synced_at.elapsed() >= interval,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Measures the offset between the local clock and the server clock and applies it to the
+    /// timestamps of signed requests, returns the offset in milliseconds
+    pub(crate) async fn sync_time(&self, time_endpoint: &str) -> Result<i64> {
+        let url = format!("{}{}", self.host, time_endpoint);
+        let sent_at = Utc::now().timestamp_millis();
+        let server_time: ServerTime = self.request(Method::GET, time_endpoint, None, url, None).await?;
+        let received_at = Utc::now().timestamp_millis();
+        // Assume the server time was read halfway through the round trip
+        let offset = server_time.server_time as i64 - (sent_at + received_at) / 2;
+        set_time_offset(&self.host, offset);
+        LAST_TIME_SYNC.lock().unwrap().insert(self.host.clone(), Instant::now());
+        Ok(offset)
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        query: Option<&str>,
+        url: String,
+        headers: Option<HeaderMap>,
+    ) -> Result<T> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire(&method, endpoint, query).await?;
+        }
+        let mut request = self.inner.request(method, url);
+        if let Some(headers) = headers {
+            request = request.headers(headers);
+        }
+        let response = request.send().await?;
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.update(response.headers());
+        }
 
         self.handler(response).await
     }
 
     // Request must be signed
-    fn sign_request(&self, endpoint: &str, request: &str) -> String {
-        let signed_key = hmac::Key::new(hmac::HMAC_SHA256, self.secret_key.as_bytes());
-        let signature = hex_encode(hmac::sign(&signed_key, request.as_bytes()).as_ref());
+    fn sign_request(&self, endpoint: &str, request: &str) -> Result<String> {
+        let signature = self.signer.sign(request)?;
+        // Base64 signatures of Ed25519 and RSA keys contain reserved characters
+        let signature: String = form_urlencoded::byte_serialize(signature.as_bytes()).collect();
         let url = format!("{}{}?{}&signature={}", self.host, endpoint, request, signature);
 
-        url
+        Ok(url)
     }
 
     fn build_headers(&self, content_type: bool) -> Result<HeaderMap> {
@@ -213,15 +343,91 @@ impl Client {
                 let error: BinanceContentError = response.json().await?;
                 Err(handle_content_error(error))
             }
+            StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited {
+                retry_after: retry_after(response.headers()),
+            }),
+            StatusCode::IM_A_TEAPOT => {
+                let retry_after = retry_after(response.headers());
+                let body = response.text().await.unwrap_or_default();
+                let until = serde_json::from_str::<BinanceContentError>(&body)
+                    .ok()
+                    .and_then(|error| banned_until(&error.msg))
+                    .or_else(|| retry_after.map(|after| (Utc::now() + after).timestamp_millis() as u64));
+                Err(Error::IpBanned { until })
+            }
+            s if s.is_client_error() => {
+                let body = response.text().await?;
+                match serde_json::from_str::<BinanceContentError>(&body) {
+                    Ok(error) => Err(handle_content_error(error)),
+                    Err(_) => Err(Error::Msg(format!("Received response: {s:?} {body}"))),
+                }
+            }
             s => Err(Error::Msg(format!("Received response: {s:?}"))),
         }
     }
 }
 
-fn handle_content_error(error: BinanceContentError) -> crate::errors::Error {
+fn is_timestamp_error(error: &BinanceContentError) -> bool { error.code == TIMESTAMP_OUTSIDE_RECV_WINDOW }
+
+/// The server time endpoint of the API `endpoint` belongs to
+fn server_time_endpoint(endpoint: &str) -> &'static str {
+    if endpoint.starts_with("/fapi") {
+        "/fapi/v1/time"
+    } else {
+        "/api/v3/time"
+    }
+}
+
+/// `Retry-After` is given in seconds
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs)
+}
+
+/// Ban messages look like "Way too much request weight used; IP banned until 1589437529000. ..."
+fn banned_until(msg: &str) -> Option<u64> {
+    let (_, rest) = msg.split_once("banned until ")?;
+    rest.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()
+}
+
+pub(crate) fn handle_content_error(error: BinanceContentError) -> crate::errors::Error {
     match (error.code, error.msg.as_ref()) {
         (-1013, error_messages::INVALID_PRICE) => Error::InvalidPrice,
         (-1125, msg) => Error::InvalidListenKey(msg.to_string()),
         _ => Error::BinanceError { response: error },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ip_ban_and_retry_after() {
+        let msg = "Way too much request weight used; IP banned until 1589437529000. Please use the websocket.";
+        assert_eq!(banned_until(msg), Some(1589437529000));
+        assert_eq!(banned_until("Too many requests"), None);
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("42"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(42)));
+    }
+
+    #[test]
+    fn time_offset_by_host() {
+        set_time_offset("https://offset.test", 3_600_000);
+        let timestamp = |request: &str| -> u64 {
+            request
+                .strip_prefix("symbol=BTCUSDT&timestamp=")
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        let local = timestamp(&refresh_timestamp("symbol=BTCUSDT&timestamp=0", "https://other.test").unwrap());
+        let shifted = timestamp(&refresh_timestamp("symbol=BTCUSDT&timestamp=0", "https://offset.test").unwrap());
+        assert!(shifted >= local + 3_600_000);
+        assert!(shifted < local + 3_660_000);
+    }
+}
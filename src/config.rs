@@ -1,3 +1,10 @@
+use crate::rate_limit::RateLimiterConfig;
+use crate::retry::RetryPolicy;
+use crate::signer::{SharedSigner, SignatureType, Signer};
+use crate::websockets::ReconnectPolicy;
+use std::sync::Arc;
+use std::time::Duration;
+
 pub static DATA_REST_ENDPOINT: &str = "https://data.binance.com";
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -8,11 +15,25 @@ pub struct Config {
     pub futures_rest_api_endpoint: String,
     pub futures_ws_endpoint: String,
 
+    pub ws_api_endpoint: String,
+
     pub recv_window: u64,
 
     pub binance_us_api: bool,
 
     pub timeout: Option<u64>,
+
+    pub ws_reconnect_policy: Option<ReconnectPolicy>,
+
+    pub rate_limiter: Option<RateLimiterConfig>,
+    pub futures_rate_limiter: Option<RateLimiterConfig>,
+
+    pub retry_policy: Option<RetryPolicy>,
+
+    pub time_sync_interval: Option<Duration>,
+
+    pub signature_type: SignatureType,
+    pub signer: Option<SharedSigner>,
 }
 
 impl Config {
@@ -28,6 +49,7 @@ impl Config {
             .set_ws_endpoint("wss://testnet.binance.vision")
             .set_futures_rest_api_endpoint("https://testnet.binancefuture.com")
             .set_futures_ws_endpoint("wss://testnet.binancefuture.com")
+            .set_ws_api_endpoint("wss://ws-api.testnet.binance.vision/ws-api/v3")
     }
 
     /// Sets the rest api endpoint. Defaults to <https://api.binance.com>.
@@ -110,6 +132,26 @@ impl Config {
         self
     }
 
+    /// Sets the WebSocket API endpoint. Defaults to "wss://ws-api.binance.com:443/ws-api/v3".
+    ///
+    /// # Arguments
+    ///
+    /// * `ws_api_endpoint`:
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// let config = Config::default();
+    /// config.set_ws_api_endpoint("ws://myendpoint:8080/ws-api/v3");
+    /// ```
+    pub fn set_ws_api_endpoint<T: Into<String>>(mut self, ws_api_endpoint: T) -> Self {
+        self.ws_api_endpoint = ws_api_endpoint.into();
+        self
+    }
+
     /// Sets the 'receive window'. The receive window is the number of milliseconds after timestamp
     /// the request is valid for.
     ///
@@ -150,6 +192,157 @@ impl Config {
         self.timeout = Some(timeout);
         self
     }
+
+    /// Sets the websocket reconnect policy, websockets do not reconnect unless a policy is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy`: The backoff, jitter and maximum attempts used to re-establish a lost connection
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::websockets::ReconnectPolicy;
+    /// let config = Config::default();
+    /// config.set_ws_reconnect_policy(ReconnectPolicy::default());
+    /// ```
+    pub fn set_ws_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.ws_reconnect_policy = Some(policy);
+        self
+    }
+
+    /// Sets the rate limiter of the spot, margin, savings and wallet APIs, requests are not limited unless it is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate_limiter`: The limits to enforce and whether to delay or reject requests that would exceed them
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::rate_limit::{RateLimitMode, RateLimiterConfig};
+    /// let config = Config::default();
+    /// config.set_rate_limiter(RateLimiterConfig::spot(RateLimitMode::Delay));
+    /// ```
+    pub fn set_rate_limiter(mut self, rate_limiter: RateLimiterConfig) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Sets the rate limiter of the futures API, requests are not limited unless it is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate_limiter`: The limits to enforce and whether to delay or reject requests that would exceed them
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::rate_limit::{RateLimitMode, RateLimiterConfig};
+    /// let config = Config::default();
+    /// config.set_futures_rate_limiter(RateLimiterConfig::futures(RateLimitMode::Reject));
+    /// ```
+    pub fn set_futures_rate_limiter(mut self, rate_limiter: RateLimiterConfig) -> Self {
+        self.futures_rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Sets the retry policy of REST requests, requests are attempted once unless it is set.
+    /// GET requests are retried, orders only when `retry_orders` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy`: The number of attempts, the backoff and the errors that qualify for a retry
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::retry::RetryPolicy;
+    /// let config = Config::default();
+    /// config.set_retry_policy(RetryPolicy::default());
+    /// ```
+    pub fn set_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Synchronizes the timestamps of signed requests with the server clock, using the local clock unless it is set.
+    /// The offset is measured before the first signed request, refreshed once older than `interval`,
+    /// and requests rejected with a -1021 timestamp error are sent again once after a resync.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval`: How long a measured offset is used before being measured again
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use std::time::Duration;
+    /// let config = Config::default();
+    /// config.set_time_sync_interval(Duration::from_secs(600));
+    /// ```
+    pub fn set_time_sync_interval(mut self, interval: Duration) -> Self {
+        self.time_sync_interval = Some(interval);
+        self
+    }
+
+    /// Sets how the `secret_key` given to the `Binance` constructors is read. Defaults to an HMAC secret key.
+    ///
+    /// # Arguments
+    ///
+    /// * `signature_type`: `Ed25519` and `Rsa` expect the PEM private key matching the registered public key
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::signer::SignatureType;
+    /// let config = Config::default();
+    /// config.set_signature_type(SignatureType::Ed25519);
+    /// ```
+    pub fn set_signature_type(mut self, signature_type: SignatureType) -> Self {
+        self.signature_type = signature_type;
+        self
+    }
+
+    /// Sets a custom signer, used instead of the `secret_key` given to the `Binance` constructors.
+    ///
+    /// # Arguments
+    ///
+    /// * `signer`: Signs the payload of authenticated requests, e.g. with a key held by an HSM
+    ///
+    /// returns: Config
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binance::config::Config;
+    /// use binance::signer::HmacSigner;
+    /// use std::sync::Arc;
+    /// let config = Config::default();
+    /// config.set_signer(Arc::new(HmacSigner::new("secret")));
+    /// ```
+    pub fn set_signer(mut self, signer: Arc<dyn Signer>) -> Self {
+        self.signer = Some(SharedSigner(signer));
+        self
+    }
 }
 
 impl Default for Config {
@@ -167,10 +360,24 @@ impl Default for Config {
             futures_rest_api_endpoint: "https://fapi.binance.com".into(),
             futures_ws_endpoint: "wss://fstream.binance.com".into(),
 
+            ws_api_endpoint: "wss://ws-api.binance.com:443/ws-api/v3".into(),
+
             recv_window: 5000,
             binance_us_api: false,
 
             timeout: None,
+
+            ws_reconnect_policy: None,
+
+            rate_limiter: None,
+            futures_rate_limiter: None,
+
+            retry_policy: None,
+
+            time_sync_interval: None,
+
+            signature_type: SignatureType::Hmac,
+            signer: None,
         }
     }
 }
@@ -1,8 +1,11 @@
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::Duration;
 use thiserror::Error;
 
-#[derive(Debug, Deserialize, Error)]
+use crate::rest_model::RateLimitType;
+
+#[derive(Debug, Serialize, Deserialize, Error)]
 #[error("code: {code}, msg: {msg}")]
 pub struct BinanceContentError {
     pub code: i32,
@@ -12,6 +15,16 @@ pub struct BinanceContentError {
     extra: HashMap<String, Value>,
 }
 
+impl BinanceContentError {
+    pub(crate) fn new(code: i32, msg: &str) -> Self {
+        BinanceContentError {
+            code,
+            msg: msg.to_string(),
+            extra: HashMap::new(),
+        }
+    }
+}
+
 /// First errors are technical errors
 /// All unhandled binance content errors are BinanceError
 /// The rest are binance content errors that are properly handled
@@ -33,7 +46,7 @@ pub enum Error {
     #[error(transparent)]
     Qs(#[from] serde_qs::Error),
     #[error(transparent)]
-    Tungstenite(#[from] tokio_tungstenite::tungstenite::Error),
+    Tungstenite(Box<tokio_tungstenite::tungstenite::Error>),
     #[error(transparent)]
     TimestampError(#[from] std::time::SystemTimeError),
     #[error(transparent)]
@@ -49,6 +62,8 @@ pub enum Error {
     UnknownSymbol(String),
     #[error("{msg}")]
     InvalidOrderError { msg: String },
+    #[error("filter failure {filter}: {msg}")]
+    FilterFailure { filter: &'static str, msg: String },
     #[error("invalid price")]
     InvalidPrice,
     #[error("invalid period {0}")]
@@ -59,10 +74,29 @@ pub enum Error {
     ServiceUnavailable,
     #[error("Unauthorized")]
     Unauthorized,
+    #[error("{rate_limit_type:?} limit reached, retry after {retry_after:?}")]
+    RateLimitReached {
+        rate_limit_type: RateLimitType,
+        retry_after: Duration,
+    },
+    #[error("too many requests, retry after {retry_after:?}")]
+    RateLimited { retry_after: Option<Duration> },
+    #[error("IP banned until {until:?}")]
+    IpBanned {
+        /// Timestamp in milliseconds when the ban is lifted
+        until: Option<u64>,
+    },
+    #[error("invalid signing key: {0}")]
+    InvalidSigningKey(String),
     #[error("{0}")]
     Msg(String),
 }
 
+/// Boxed to keep `Result` small
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(error: tokio_tungstenite::tungstenite::Error) -> Self { Error::Tungstenite(Box::new(error)) }
+}
+
 /// Custom error messages
 pub mod error_messages {
     pub const INVALID_PRICE: &str = "Invalid price.";
@@ -0,0 +1,424 @@
+//! Local checks of orders against the filters of their symbol.
+//!
+//! Binance rejects an order failing a filter with `-1013 Filter failure`, after it has been counted in the
+//! request weight. Checking orders against the filters of `General::exchange_info` avoids the round trip.
+
+use crate::account::OrderRequest;
+use crate::errors::*;
+use crate::rest_model::{Filters, Number, OrderSide, OrderType, Symbol};
+
+/// Market state some of the filters depend on, checks that need a missing value are skipped
+#[derive(Clone, Debug, Default)]
+pub struct FilterContext {
+    /// Average price of the symbol over the `avg_price_mins` of the filters, see `Market::get_average_price`.
+    /// Used by PERCENT_PRICE(_BY_SIDE) and for the notional of market orders.
+    pub average_price: Option<Number>,
+    /// Number of open orders of the account on the symbol, checked against MAX_NUM_ORDERS
+    pub open_orders: Option<usize>,
+}
+
+impl Symbol {
+    /// Checks `order` against PRICE_FILTER, PERCENT_PRICE(_BY_SIDE), LOT_SIZE, MARKET_LOT_SIZE, MIN_NOTIONAL,
+    /// NOTIONAL, ICEBERG_PARTS and MAX_NUM_ORDERS, failing with `Error::FilterFailure`
+    pub fn validate_order(&self, order: &OrderRequest, context: &FilterContext) -> Result<()> {
+        self.filters
+            .iter()
+            .try_for_each(|filter| check_filter(filter, order, context))
+    }
+
+    /// Rounds `price` to the nearest multiple of the tick size of the PRICE_FILTER
+    pub fn round_price(&self, price: Number) -> Number {
+        match self.price_filter() {
+            Some(Filters::PriceFilter {
+                min_price, tick_size, ..
+            }) if !is_zero(tick_size) => round_to_step(price, min_price, tick_size),
+            _ => price,
+        }
+    }
+
+    /// Rounds `quantity` down to a multiple of the step size of the LOT_SIZE, and of the MARKET_LOT_SIZE
+    /// for market orders
+    pub fn round_quantity(&self, quantity: Number, order_type: &OrderType) -> Number {
+        self.filters.iter().fold(quantity, |quantity, filter| match filter {
+            Filters::LotSize { min_qty, step_size, .. } if !is_zero(*step_size) => {
+                floor_to_step(quantity, *min_qty, *step_size)
+            }
+            Filters::MarketLotSize { min_qty, step_size, .. } if is_market(order_type) && !is_zero(*step_size) => {
+                floor_to_step(quantity, *min_qty, *step_size)
+            }
+            _ => quantity,
+        })
+    }
+
+    /// Rounds the prices of `order` to the nearest tick and its quantities down to the lot size step
+    pub fn round_order(&self, order: &mut OrderRequest) {
+        order.price = order.price.map(|price| self.round_price(price));
+        order.stop_price = order.stop_price.map(|price| self.round_price(price));
+        order.quantity = order.quantity.map(|qty| self.round_quantity(qty, &order.order_type));
+        order.iceberg_qty = order.iceberg_qty.map(|qty| self.round_quantity(qty, &OrderType::Limit));
+    }
+}
+
+/// Orders executed at market price, immediately or once triggered
+fn is_market(order_type: &OrderType) -> bool {
+    matches!(
+        order_type,
+        OrderType::Market | OrderType::StopLoss | OrderType::TakeProfit
+    )
+}
+
+fn failure(filter: &'static str, msg: String) -> Error { Error::FilterFailure { filter, msg } }
+
+fn check_filter(filter: &Filters, order: &OrderRequest, context: &FilterContext) -> Result<()> {
+    match filter {
+        Filters::PriceFilter {
+            min_price,
+            max_price,
+            tick_size,
+        } => [order.price, order.stop_price]
+            .into_iter()
+            .flatten()
+            .try_for_each(|price| check_price(price, *min_price, *max_price, *tick_size)),
+        Filters::PercentPrice {
+            multiplier_up,
+            multiplier_down,
+            ..
+        } => check_percent_price("PERCENT_PRICE", order, context, *multiplier_up, *multiplier_down),
+        Filters::PercentPriceBySide {
+            bid_multiplier_up,
+            bid_multiplier_down,
+            ask_multiplier_up,
+            ask_multiplier_down,
+            ..
+        } => match order.side {
+            OrderSide::Buy => check_percent_price(
+                "PERCENT_PRICE_BY_SIDE",
+                order,
+                context,
+                *bid_multiplier_up,
+                *bid_multiplier_down,
+            ),
+            OrderSide::Sell => check_percent_price(
+                "PERCENT_PRICE_BY_SIDE",
+                order,
+                context,
+                *ask_multiplier_up,
+                *ask_multiplier_down,
+            ),
+        },
+        Filters::LotSize {
+            min_qty,
+            max_qty,
+            step_size,
+        } => [order.quantity, order.iceberg_qty]
+            .into_iter()
+            .flatten()
+            .try_for_each(|qty| check_quantity("LOT_SIZE", qty, *min_qty, *max_qty, *step_size)),
+        Filters::MarketLotSize {
+            min_qty,
+            max_qty,
+            step_size,
+        } => match order.quantity {
+            Some(qty) if is_market(&order.order_type) => {
+                check_quantity("MARKET_LOT_SIZE", qty, *min_qty, *max_qty, *step_size)
+            }
+            _ => Ok(()),
+        },
+        Filters::MinNotional {
+            min_notional,
+            apply_to_market,
+            ..
+        } => match notional(order, context, *apply_to_market) {
+            Some(notional) if notional < *min_notional => Err(failure(
+                "MIN_NOTIONAL",
+                format!("notional {notional} is below {min_notional}"),
+            )),
+            _ => Ok(()),
+        },
+        Filters::Notional {
+            min_notional,
+            apply_min_to_market,
+            max_notional,
+            apply_max_to_market,
+            ..
+        } => {
+            if let Some(notional) = notional(order, context, *apply_min_to_market) {
+                if notional < *min_notional {
+                    return Err(failure(
+                        "NOTIONAL",
+                        format!("notional {notional} is below {min_notional}"),
+                    ));
+                }
+            }
+            match notional(order, context, *apply_max_to_market) {
+                Some(notional) if !is_zero(*max_notional) && notional > *max_notional => Err(failure(
+                    "NOTIONAL",
+                    format!("notional {notional} is above {max_notional}"),
+                )),
+                _ => Ok(()),
+            }
+        }
+        Filters::IcebergParts { limit } => match (order.quantity, order.iceberg_qty) {
+            (Some(qty), Some(iceberg_qty)) if !is_zero(iceberg_qty) => {
+                let parts = (qty / iceberg_qty).ceil();
+                if parts > Number::from(*limit) {
+                    Err(failure("ICEBERG_PARTS", format!("{parts} parts is more than {limit}")))
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Ok(()),
+        },
+        Filters::MaxNumOrders { max_num_orders } => match context.open_orders {
+            Some(open_orders) if open_orders >= usize::from(*max_num_orders) => Err(failure(
+                "MAX_NUM_ORDERS",
+                format!("{open_orders} orders are already open"),
+            )),
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+fn check_price(price: Number, min_price: Number, max_price: Number, tick_size: Number) -> Result<()> {
+    if !is_zero(min_price) && price < min_price {
+        return Err(failure("PRICE_FILTER", format!("price {price} is below {min_price}")));
+    }
+    if !is_zero(max_price) && price > max_price {
+        return Err(failure("PRICE_FILTER", format!("price {price} is above {max_price}")));
+    }
+    if !is_zero(tick_size) && !is_multiple(price, min_price, tick_size) {
+        return Err(failure(
+            "PRICE_FILTER",
+            format!("price {price} is not a multiple of the tick size {tick_size}"),
+        ));
+    }
+    Ok(())
+}
+
+fn check_quantity(
+    filter: &'static str,
+    qty: Number,
+    min_qty: Number,
+    max_qty: Number,
+    step_size: Number,
+) -> Result<()> {
+    if qty < min_qty {
+        return Err(failure(filter, format!("quantity {qty} is below {min_qty}")));
+    }
+    if !is_zero(max_qty) && qty > max_qty {
+        return Err(failure(filter, format!("quantity {qty} is above {max_qty}")));
+    }
+    if !is_zero(step_size) && !is_multiple(qty, min_qty, step_size) {
+        return Err(failure(
+            filter,
+            format!("quantity {qty} is not a multiple of the step size {step_size}"),
+        ));
+    }
+    Ok(())
+}
+
+fn check_percent_price(
+    filter: &'static str,
+    order: &OrderRequest,
+    context: &FilterContext,
+    multiplier_up: Number,
+    multiplier_down: Number,
+) -> Result<()> {
+    let (Some(price), Some(average_price)) = (order.price, context.average_price) else {
+        return Ok(());
+    };
+    if price > average_price * multiplier_up {
+        return Err(failure(
+            filter,
+            format!("price {price} is above {multiplier_up} times the average price {average_price}"),
+        ));
+    }
+    if price < average_price * multiplier_down {
+        return Err(failure(
+            filter,
+            format!("price {price} is below {multiplier_down} times the average price {average_price}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Notional of the order, market orders are valued at the average price when `apply_to_market` is set
+fn notional(order: &OrderRequest, context: &FilterContext, apply_to_market: bool) -> Option<Number> {
+    if !is_market(&order.order_type) {
+        return Some(order.quantity? * order.price?);
+    }
+    if !apply_to_market {
+        return None;
+    }
+    match order.quote_order_qty {
+        Some(quote_order_qty) => Some(quote_order_qty),
+        None => Some(order.quantity? * context.average_price?),
+    }
+}
+
+fn is_zero(value: Number) -> bool { value == Number::default() }
+
+fn is_multiple(value: Number, base: Number, step: Number) -> bool {
+    let steps = steps(value, base, step);
+    steps == steps.floor()
+}
+
+fn floor_to_step(value: Number, base: Number, step: Number) -> Number {
+    if value < base {
+        return value;
+    }
+    to_step_precision(base + steps(value, base, step).floor() * step, step)
+}
+
+fn round_to_step(value: Number, base: Number, step: Number) -> Number {
+    to_step_precision(base + steps(value, base, step).round() * step, step)
+}
+
+/// Tolerance of float comparisons to a whole number of steps
+#[cfg(not(feature = "decimal"))]
+const EPSILON: f64 = 1e-9;
+
+/// Number of steps from `base` to `value`, snapped to a whole number of steps when within float precision
+#[cfg(not(feature = "decimal"))]
+fn steps(value: Number, base: Number, step: Number) -> Number {
+    let steps = (value - base) / step;
+    if (steps - steps.round()).abs() < EPSILON {
+        steps.round()
+    } else {
+        steps
+    }
+}
+
+#[cfg(feature = "decimal")]
+fn steps(value: Number, base: Number, step: Number) -> Number { (value - base) / step }
+
+/// Drops the float noise of `value` beyond the decimals of `step`
+#[cfg(not(feature = "decimal"))]
+fn to_step_precision(value: Number, step: Number) -> Number {
+    let mut decimals = 0;
+    let mut scaled = step;
+    while (scaled - scaled.round()).abs() > EPSILON && decimals < 16 {
+        scaled *= 10.0;
+        decimals += 1;
+    }
+    let factor = 10_f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+#[cfg(feature = "decimal")]
+fn to_step_precision(value: Number, _step: Number) -> Number { value.normalize() }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::num;
+
+    fn symbol() -> Symbol {
+        let symbol = serde_json::json!({
+            "symbol": "BTCUSDT",
+            "status": "TRADING",
+            "baseAsset": "BTC",
+            "baseAssetPrecision": 8,
+            "quoteAsset": "USDT",
+            "quotePrecision": 8,
+            "quoteAssetPrecision": 8,
+            "baseCommissionPrecision": 8,
+            "quoteCommissionPrecision": 8,
+            "orderTypes": ["LIMIT", "MARKET"],
+            "icebergAllowed": true,
+            "ocoAllowed": true,
+            "quoteOrderQtyMarketAllowed": true,
+            "isSpotTradingAllowed": true,
+            "isMarginTradingAllowed": true,
+            "permissions": ["SPOT"],
+            "filters": [
+                {"filterType": "PRICE_FILTER", "minPrice": "0.01", "maxPrice": "1000000.00", "tickSize": "0.01"},
+                {"filterType": "LOT_SIZE", "minQty": "0.00001", "maxQty": "9000.0", "stepSize": "0.00001"},
+                {"filterType": "ICEBERG_PARTS", "limit": 10},
+                {"filterType": "MAX_NUM_ORDERS", "maxNumOrders": 200},
+                {
+                    "filterType": "PERCENT_PRICE_BY_SIDE",
+                    "bidMultiplierUp": "5",
+                    "bidMultiplierDown": "0.2",
+                    "askMultiplierUp": "5",
+                    "askMultiplierDown": "0.2",
+                    "avgPriceMins": 5
+                },
+                {
+                    "filterType": "NOTIONAL",
+                    "minNotional": "5.00",
+                    "applyMinToMarket": true,
+                    "maxNotional": "9000000.00",
+                    "applyMaxToMarket": false,
+                    "avgPriceMins": 5
+                }
+            ]
+        });
+        serde_json::from_value(symbol).unwrap()
+    }
+
+    fn limit_buy(price: f64, quantity: f64) -> OrderRequest {
+        OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            order_type: OrderType::Limit,
+            price: Some(num(price)),
+            quantity: Some(num(quantity)),
+            ..OrderRequest::default()
+        }
+    }
+
+    fn failed_filter(result: Result<()>) -> Option<&'static str> {
+        match result {
+            Err(Error::FilterFailure { filter, .. }) => Some(filter),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn validate_order() {
+        let symbol = symbol();
+        let context = FilterContext {
+            average_price: Some(num(30000.0)),
+            open_orders: Some(3),
+        };
+        assert!(symbol.validate_order(&limit_buy(30000.01, 0.001), &context).is_ok());
+        let invalid = [
+            (limit_buy(30000.005, 0.001), "PRICE_FILTER"),
+            (limit_buy(30000.0, 0.000015), "LOT_SIZE"),
+            (limit_buy(30000.0, 0.0001), "NOTIONAL"),
+            (limit_buy(200000.0, 0.001), "PERCENT_PRICE_BY_SIDE"),
+        ];
+        for (order, filter) in invalid {
+            assert_eq!(failed_filter(symbol.validate_order(&order, &context)), Some(filter));
+        }
+        let iceberg = OrderRequest {
+            iceberg_qty: Some(num(0.0001)),
+            ..limit_buy(30000.0, 0.002)
+        };
+        assert_eq!(
+            failed_filter(symbol.validate_order(&iceberg, &context)),
+            Some("ICEBERG_PARTS")
+        );
+        let context = FilterContext {
+            open_orders: Some(200),
+            ..context
+        };
+        assert_eq!(
+            failed_filter(symbol.validate_order(&limit_buy(30000.0, 0.001), &context)),
+            Some("MAX_NUM_ORDERS")
+        );
+    }
+
+    #[test]
+    fn round_order() {
+        let symbol = symbol();
+        let mut order = limit_buy(30000.006, 0.0012345);
+        symbol.round_order(&mut order);
+        assert_eq!(order.price, Some(num(30000.01)));
+        assert_eq!(order.quantity, Some(num(0.00123)));
+        assert!(symbol.validate_order(&order, &FilterContext::default()).is_ok());
+        assert_eq!(symbol.round_quantity(num(0.3), &OrderType::Market), num(0.3));
+    }
+}
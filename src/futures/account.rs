@@ -1,16 +1,15 @@
 use std::collections::BTreeMap;
 
-use super::rest_model::{
-    AccountBalance, AccountInformation, CanceledOrder, ChangeLeverageResponse, Order, OrderType, Position,
-    PositionSide, Transaction, WorkingType,
-};
+use super::rest_model::{AccountBalance, AccountInformation, CanceledOrder, ChangeLeverageResponse, Order, OrderType,
+                        Position, PositionSide, Transaction, WorkingType};
 use crate::account::OrderCancellation;
 use crate::client::Client;
 use crate::errors::*;
-use crate::rest_model::{OrderSide, TimeInForce};
+use crate::rest_model::{Number, OrderSide, TimeInForce};
 use crate::rest_model::{PairAndWindowQuery, PairQuery};
 use crate::util::*;
 use serde::Serializer;
+use serde_json::Value;
 use std::fmt;
 
 #[derive(Clone)]
@@ -50,13 +49,13 @@ pub struct OrderRequest {
     pub order_type: OrderType,
     pub time_in_force: Option<TimeInForce>,
     #[serde(rename = "quantity")]
-    pub quantity: Option<f64>,
+    pub quantity: Option<Number>,
     pub reduce_only: Option<bool>,
-    pub price: Option<f64>,
-    pub stop_price: Option<f64>,
+    pub price: Option<Number>,
+    pub stop_price: Option<Number>,
     pub close_position: Option<bool>,
-    pub activation_price: Option<f64>,
-    pub callback_rate: Option<f64>,
+    pub activation_price: Option<Number>,
+    pub callback_rate: Option<Number>,
     pub working_type: Option<WorkingType>,
     #[serde(serialize_with = "serialize_opt_as_uppercase")]
     pub price_protect: Option<bool>,
@@ -85,8 +84,8 @@ impl FuturesAccount {
     pub async fn limit_buy(
         &self,
         symbol: impl Into<String>,
-        qty: impl Into<f64>,
-        price: f64,
+        qty: impl Into<Number>,
+        price: Number,
         time_in_force: TimeInForce,
     ) -> Result<Transaction> {
         let order = OrderRequest {
@@ -112,8 +111,8 @@ impl FuturesAccount {
     pub async fn limit_sell(
         &self,
         symbol: impl Into<String>,
-        qty: impl Into<f64>,
-        price: f64,
+        qty: impl Into<Number>,
+        price: Number,
         time_in_force: TimeInForce,
     ) -> Result<Transaction> {
         let order = OrderRequest {
@@ -140,7 +139,7 @@ impl FuturesAccount {
     pub async fn market_buy<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let order = OrderRequest {
             symbol: symbol.into(),
@@ -166,7 +165,7 @@ impl FuturesAccount {
     pub async fn market_sell<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let order: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -235,7 +234,8 @@ impl FuturesAccount {
     }
 
     pub async fn change_position_mode(&self, dual_side_position: bool) -> Result<()> {
-        self.client
+        let _: Value = self
+            .client
             .post_signed_p(
                 "/fapi/v1/positionSide/dual",
                 ChangePositionModeRequest { dual_side_position },
@@ -249,7 +249,8 @@ impl FuturesAccount {
     where
         S: Into<String>,
     {
-        self.client
+        let _: Value = self
+            .client
             .delete_signed_p(
                 "/fapi/v1/allOpenOrders",
                 PairQuery { symbol: symbol.into() },
@@ -2,6 +2,7 @@ use crate::client::*;
 use crate::errors::*;
 use crate::futures::rest_model::*;
 use crate::rest_model::ServerTime;
+use serde_json::Value;
 
 #[derive(Clone)]
 pub struct FuturesGeneral {
@@ -11,14 +12,15 @@ pub struct FuturesGeneral {
 impl FuturesGeneral {
     // Test connectivity
     pub async fn ping(&self) -> Result<String> {
-        self.client.get("/fapi/v1/ping", None).await?;
+        let _: Value = self.client.get("/fapi/v1/ping", None).await?;
         Ok("pong".into())
     }
 
     // Check server time
-    pub async fn get_server_time(&self) -> Result<ServerTime> {
-        self.client.get_p("/fapi/v1/time", None).await
-    }
+    pub async fn get_server_time(&self) -> Result<ServerTime> { self.client.get_p("/fapi/v1/time", None).await }
+
+    // Measure the clock offset against the server and apply it to signed requests
+    pub async fn sync_time(&self) -> Result<i64> { self.client.sync_time("/fapi/v1/time").await }
 
     // Obtain exchange information
     // - Current exchange trading rules and symbol information
@@ -1,10 +1,10 @@
 use crate::client::*;
 use crate::errors::*;
 use crate::futures::rest_model::*;
-use crate::rest_model::{
-    BookTickers, KlineSummaries, KlineSummary, PairAndWindowQuery, PairQuery, SymbolPrice, Tickers,
-};
+use crate::pagination::*;
+use crate::rest_model::{BookTickers, KlineInterval, KlineSummary, PairAndWindowQuery, PairQuery, SymbolPrice, Tickers};
 use crate::util::*;
+use futures::stream::BoxStream;
 use serde_json::Value;
 
 //TODO : Validate intervals and start/end times in history queries
@@ -28,6 +28,15 @@ impl FuturesMarket {
             .await
     }
 
+    // Order book with a custom depth limit, supported limits are: 5, 10, 20, 50, 100, 500, 1000
+    pub async fn get_custom_depth<S>(&self, symbol: S, limit: u16) -> Result<OrderBook>
+    where
+        S: Into<String>,
+    {
+        let request = build_request([("symbol", symbol.into()), ("limit", limit.to_string())]);
+        self.client.get("/fapi/v1/depth", Some(&request)).await
+    }
+
     /// Get trades for a pair
     pub async fn get_trades<S>(&self, symbol: S) -> Result<Trades>
     where
@@ -285,80 +294,96 @@ impl FuturesMarket {
             .await
     }
 
-    /// Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
+    /// Every kline of `symbol` and `interval` opened between `start_time` and `end_time` (inclusive, in ms),
+    /// in time order
+    pub fn klines_stream<S>(
+        &self,
+        symbol: S,
+        interval: KlineInterval,
+        start_time: u64,
+        end_time: u64,
+    ) -> BoxStream<'_, Result<KlineSummary>>
+    where
+        S: Into<String>,
+    {
+        let paging = Paging {
+            max_window: None,
+            limit: 1500,
+            by_offset: false,
+        };
+        let symbol = symbol.into();
+        let fetch = move |page: Page| {
+            self.get_klines(
+                symbol.clone(),
+                interval,
+                page.limit as u16,
+                page.start_time,
+                page.end_time,
+            )
+        };
+        paginate(
+            paging,
+            start_time,
+            end_time,
+            fetch,
+            |k| k.open_time as u64,
+            |k| k.open_time,
+        )
+    }
+
+    /// Returns up to 'limit' klines for given symbol and interval
     /// https://github.com/binance-exchange/binance-official-api-docs/blob/master/rest-api.md#klinecandlestick-data
-    pub async fn get_klines<S1, S2, S3, S4, S5>(
+    pub async fn get_klines<S1, S2, S3, S4>(
         &self,
         symbol: S1,
-        interval: S2,
-        limit: S3,
-        start_time: S4,
-        end_time: S5,
-    ) -> Result<KlineSummaries>
+        interval: KlineInterval,
+        limit: S2,
+        start_time: S3,
+        end_time: S4,
+    ) -> Result<Vec<KlineSummary>>
     where
         S1: Into<String>,
-        S2: Into<String>,
-        S3: Into<u16>,
+        S2: Into<u16>,
+        S3: Into<Option<u64>>,
         S4: Into<Option<u64>>,
-        S5: Into<Option<u64>>,
     {
         let query = HistoryQuery {
             start_time: start_time.into(),
             end_time: end_time.into(),
             limit: limit.into(),
             symbol: symbol.into(),
-            interval: Some(interval.into()),
+            interval: Some(interval),
             from_id: None,
             period: None,
         };
-        let data: Vec<Vec<Value>> = self.client.get_d("/fapi/v1/klines", Some(query)).await?;
-
-        let klines = KlineSummaries::AllKlineSummaries(
-            data.iter()
-                .map(|row| KlineSummary {
-                    open_time: to_i64(&row[0]),
-                    open: to_f64(&row[1]),
-                    high: to_f64(&row[2]),
-                    low: to_f64(&row[3]),
-                    close: to_f64(&row[4]),
-                    volume: to_f64(&row[5]),
-                    close_time: to_i64(&row[6]),
-                    quote_asset_volume: to_f64(&row[7]),
-                    number_of_trades: to_i64(&row[8]),
-                    taker_buy_base_asset_volume: to_f64(&row[9]),
-                    taker_buy_quote_asset_volume: to_f64(&row[10]),
-                })
-                .collect(),
-        );
-        Ok(klines)
+        self.client.get_d("/fapi/v1/klines", Some(query)).await
     }
 
-    /// Returns up to 'limit' blvt klines for given symbol and interval ("1m", "5m", ...)
+    /// Returns up to 'limit' blvt klines for given symbol and interval
     /// Note that the symbol is not the traditional pair but rather {symbol}{UP|DOWN}
     /// https://binance-docs.github.io/apidocs/futures/en/#blvt-nav-kline-candlestick-streams
     /// As the vector fields are undocumented on binance futures you are un your own, follow
     /// KlineSummary for an example
-    pub async fn get_blvt_klines_v<S1, S2, S3, S4, S5>(
+    pub async fn get_blvt_klines_v<S1, S2, S3, S4>(
         &self,
         symbol: S1,
-        interval: S2,
-        limit: S3,
-        start_time: S4,
-        end_time: S5,
+        interval: KlineInterval,
+        limit: S2,
+        start_time: S3,
+        end_time: S4,
     ) -> Result<Vec<Vec<Value>>>
     where
         S1: Into<String>,
-        S2: Into<String>,
-        S3: Into<u16>,
+        S2: Into<u16>,
+        S3: Into<Option<u64>>,
         S4: Into<Option<u64>>,
-        S5: Into<Option<u64>>,
     {
         let query = HistoryQuery {
             start_time: start_time.into(),
             end_time: end_time.into(),
             limit: limit.into(),
             symbol: symbol.into(),
-            interval: Some(interval.into()),
+            interval: Some(interval),
             from_id: None,
             period: None,
         };
@@ -367,31 +392,30 @@ impl FuturesMarket {
         Ok(klines)
     }
 
-    /// Returns up to 'limit' mark price klines for given symbol and interval ("1m", "5m", ...)
+    /// Returns up to 'limit' mark price klines for given symbol and interval
     /// https://binance-docs.github.io/apidocs/futures/en/#mark-price-kline-candlestick-data
     /// As the vector fields are undocumented on binance futures you are un your own, follow
     /// KlineSummary for an example
-    pub async fn get_mark_price_klines_v<S1, S2, S3, S4, S5>(
+    pub async fn get_mark_price_klines_v<S1, S2, S3, S4>(
         &self,
         symbol: S1,
-        interval: S2,
-        limit: S3,
-        start_time: S4,
-        end_time: S5,
+        interval: KlineInterval,
+        limit: S2,
+        start_time: S3,
+        end_time: S4,
     ) -> Result<Vec<Vec<Value>>>
     where
         S1: Into<String>,
-        S2: Into<String>,
-        S3: Into<u16>,
+        S2: Into<u16>,
+        S3: Into<Option<u64>>,
         S4: Into<Option<u64>>,
-        S5: Into<Option<u64>>,
     {
         let query = HistoryQuery {
             start_time: start_time.into(),
             end_time: end_time.into(),
             limit: limit.into(),
             symbol: symbol.into(),
-            interval: Some(interval.into()),
+            interval: Some(interval),
             from_id: None,
             period: None,
         };
@@ -400,31 +424,30 @@ impl FuturesMarket {
         Ok(klines)
     }
 
-    /// Returns up to 'limit' index price klines for given symbol and interval ("1m", "5m", ...)
+    /// Returns up to 'limit' index price klines for given symbol and interval
     /// https://binance-docs.github.io/apidocs/futures/en/#index-price-kline-candlestick-data
     /// As the vector fields are undocumented on binance futures you are un your own, follow
     /// KlineSummary for an example
-    pub async fn get_index_price_klines_v<S1, S2, S3, S4, S5>(
+    pub async fn get_index_price_klines_v<S1, S2, S3, S4>(
         &self,
         symbol: S1,
-        interval: S2,
-        limit: S3,
-        start_time: S4,
-        end_time: S5,
+        interval: KlineInterval,
+        limit: S2,
+        start_time: S3,
+        end_time: S4,
     ) -> Result<Vec<Vec<Value>>>
     where
         S1: Into<String>,
-        S2: Into<String>,
-        S3: Into<u16>,
+        S2: Into<u16>,
+        S3: Into<Option<u64>>,
         S4: Into<Option<u64>>,
-        S5: Into<Option<u64>>,
     {
         let query = HistoryQuery {
             start_time: start_time.into(),
             end_time: end_time.into(),
             limit: limit.into(),
             symbol: symbol.into(),
-            interval: Some(interval.into()),
+            interval: Some(interval),
             from_id: None,
             period: None,
         };
@@ -434,31 +457,30 @@ impl FuturesMarket {
         Ok(klines)
     }
 
-    /// Returns up to 'limit' continuous contract klines for given symbol and interval ("1m", "5m", ...)
+    /// Returns up to 'limit' continuous contract klines for given symbol and interval
     /// https://binance-docs.github.io/apidocs/futures/en/#continuous-contract-kline-candlestick-data
     /// As the vector fields are undocumented on binance futures you are un your own, follow
     /// KlineSummary for an example
-    pub async fn get_continuous_contract_klines_v<S1, S2, S3, S4, S5>(
+    pub async fn get_continuous_contract_klines_v<S1, S2, S3, S4>(
         &self,
         symbol: S1,
-        interval: S2,
-        limit: S3,
-        start_time: S4,
-        end_time: S5,
+        interval: KlineInterval,
+        limit: S2,
+        start_time: S3,
+        end_time: S4,
     ) -> Result<Vec<Vec<Value>>>
     where
         S1: Into<String>,
-        S2: Into<String>,
-        S3: Into<u16>,
+        S2: Into<u16>,
+        S3: Into<Option<u64>>,
         S4: Into<Option<u64>>,
-        S5: Into<Option<u64>>,
     {
         let query = HistoryQuery {
             start_time: start_time.into(),
             end_time: end_time.into(),
             limit: limit.into(),
             symbol: symbol.into(),
-            interval: Some(interval.into()),
+            interval: Some(interval),
             from_id: None,
             period: None,
         };
@@ -2,3 +2,6 @@ pub mod account;
 pub mod general;
 pub mod market;
 pub mod rest_model;
+pub mod userstream;
+pub mod websockets;
+pub mod ws_model;
@@ -1,10 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use crate::rest_model::{string_or_bool, string_or_float, string_or_float_opt, string_or_u64};
-pub use crate::rest_model::{
-    Asks, Bids, BookTickers, KlineSummaries, KlineSummary, OrderSide, OrderStatus, RateLimit, ServerTime, SymbolPrice,
-    SymbolStatus, Tickers, TimeInForce,
-};
+use crate::rest_model::{string_or_bool, string_or_float, string_or_float_opt, string_or_u64, Number};
+pub use crate::rest_model::{Asks, Bids, BookTickers, KlineInterval, KlineSummary, OrderSide, OrderStatus, RateLimit,
+                            ServerTime, SymbolPrice, SymbolStatus, Tickers, TimeInForce};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -24,7 +22,7 @@ pub struct AssetDetail {
     pub asset: String,
     pub margin_available: bool,
     #[serde(with = "string_or_float")]
-    pub auto_asset_exchange: f64,
+    pub auto_asset_exchange: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -37,9 +35,9 @@ pub struct Symbol {
     pub onboard_date: u64,
     pub status: SymbolStatus,
     #[serde(with = "string_or_float")]
-    pub maint_margin_percent: f64,
+    pub maint_margin_percent: Number,
     #[serde(with = "string_or_float")]
-    pub required_margin_percent: f64,
+    pub required_margin_percent: Number,
     pub base_asset: String,
     pub quote_asset: String,
     pub price_precision: u16,
@@ -50,12 +48,48 @@ pub struct Symbol {
     pub underlying_sub_type: Vec<String>,
     pub settle_plan: u64,
     #[serde(with = "string_or_float")]
-    pub trigger_protect: f64,
+    pub trigger_protect: Number,
     pub filters: Vec<Filters>,
     pub order_types: Vec<OrderType>,
     pub time_in_force: Vec<TimeInForce>,
 }
 
+impl Symbol {
+    pub fn price_filter(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::PriceFilter { .. }))
+    }
+
+    pub fn lot_size(&self) -> Option<Filters> { self.filter(|filter| matches!(filter, Filters::LotSize { .. })) }
+
+    pub fn market_lot_size(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::MarketLotSize { .. }))
+    }
+
+    pub fn max_num_orders(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::MaxNumOrders { .. }))
+    }
+
+    pub fn max_num_algo_orders(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::MaxNumAlgoOrders { .. }))
+    }
+
+    pub fn min_notional(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::MinNotional { .. }))
+    }
+
+    pub fn percent_price(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::PercentPrice { .. }))
+    }
+
+    pub fn position_risk_control(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::PositionRiskControl { .. }))
+    }
+
+    fn filter(&self, is_filter: impl Fn(&Filters) -> bool) -> Option<Filters> {
+        self.filters.iter().find(|filter| is_filter(filter)).cloned()
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ContractType {
@@ -82,9 +116,7 @@ pub enum OrderType {
 
 /// By default, use market orders
 impl Default for OrderType {
-    fn default() -> Self {
-        Self::Market
-    }
+    fn default() -> Self { Self::Market }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -105,39 +137,44 @@ pub enum WorkingType {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum MarginType {
+    #[serde(alias = "ISOLATED")]
     Isolated,
+    #[serde(alias = "CROSSED", alias = "crossed")]
     Cross,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(tag = "filterType")]
 pub enum Filters {
     #[serde(rename = "PRICE_FILTER")]
     #[serde(rename_all = "camelCase")]
     PriceFilter {
         #[serde(with = "string_or_float")]
-        min_price: f64,
+        min_price: Number,
         #[serde(with = "string_or_float")]
-        max_price: f64,
+        max_price: Number,
         #[serde(with = "string_or_float")]
-        tick_size: f64,
+        tick_size: Number,
     },
     #[serde(rename = "LOT_SIZE")]
     #[serde(rename_all = "camelCase")]
     LotSize {
         #[serde(with = "string_or_float")]
-        min_qty: f64,
+        min_qty: Number,
         #[serde(with = "string_or_float")]
-        max_qty: f64,
+        max_qty: Number,
         #[serde(with = "string_or_float")]
-        step_size: f64,
+        step_size: Number,
     },
     #[serde(rename = "MARKET_LOT_SIZE")]
     #[serde(rename_all = "camelCase")]
     MarketLotSize {
-        min_qty: String,
-        max_qty: String,
-        step_size: String,
+        #[serde(with = "string_or_float")]
+        min_qty: Number,
+        #[serde(with = "string_or_float")]
+        max_qty: Number,
+        #[serde(with = "string_or_float")]
+        step_size: Number,
     },
     #[serde(rename = "MAX_NUM_ORDERS")]
     #[serde(rename_all = "camelCase")]
@@ -149,18 +186,21 @@ pub enum Filters {
     #[serde(rename_all = "camelCase")]
     MinNotional {
         #[serde(with = "string_or_float")]
-        notional: f64,
+        notional: Number,
     },
     #[serde(rename = "PERCENT_PRICE")]
     #[serde(rename_all = "camelCase")]
     PercentPrice {
         #[serde(with = "string_or_float")]
-        multiplier_up: f64,
+        multiplier_up: Number,
         #[serde(with = "string_or_float")]
-        multiplier_down: f64,
+        multiplier_down: Number,
         #[serde(with = "string_or_float")]
-        multiplier_decimal: f64,
+        multiplier_decimal: Number,
     },
+    #[serde(rename = "POSITION_RISK_CONTROL")]
+    #[serde(rename_all = "camelCase")]
+    PositionRiskControl { position_control_side: String },
     #[serde(other)]
     Others,
 }
@@ -187,19 +227,19 @@ pub struct PriceStats {
     pub price_change_percent: String,
     pub weighted_avg_price: String,
     #[serde(with = "string_or_float")]
-    pub last_price: f64,
+    pub last_price: Number,
     #[serde(with = "string_or_float")]
-    pub open_price: f64,
+    pub open_price: Number,
     #[serde(with = "string_or_float")]
-    pub high_price: f64,
+    pub high_price: Number,
     #[serde(with = "string_or_float")]
-    pub low_price: f64,
+    pub low_price: Number,
     #[serde(with = "string_or_float")]
-    pub volume: f64,
+    pub volume: Number,
     #[serde(with = "string_or_float")]
-    pub quote_volume: f64,
+    pub quote_volume: Number,
     #[serde(with = "string_or_float")]
-    pub last_qty: f64,
+    pub last_qty: Number,
     pub open_time: u64,
     pub close_time: u64,
     pub first_id: u64,
@@ -219,11 +259,11 @@ pub struct Trade {
     pub id: u64,
     pub is_buyer_maker: bool,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
     #[serde(with = "string_or_float")]
-    pub quote_qty: f64,
+    pub quote_qty: Number,
     pub time: u64,
 }
 
@@ -247,9 +287,9 @@ pub struct AggTrade {
     #[serde(rename = "m")]
     pub maker: bool,
     #[serde(rename = "p", with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(rename = "q", with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
 }
 
 // #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -263,16 +303,16 @@ pub struct AggTrade {
 pub struct MarkPrice {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub mark_price: f64,
+    pub mark_price: Number,
     #[serde(with = "string_or_float")]
-    pub index_price: f64,
+    pub index_price: Number,
     #[serde(with = "string_or_float")]
-    pub estimated_settle_price: f64,
+    pub estimated_settle_price: Number,
     #[serde(with = "string_or_float")]
-    pub last_funding_rate: f64,
+    pub last_funding_rate: Number,
     pub next_funding_time: u64,
     #[serde(with = "string_or_float")]
-    pub interest_rate: f64,
+    pub interest_rate: Number,
     pub time: u64,
 }
 
@@ -286,13 +326,13 @@ pub enum LiquidationOrders {
 #[serde(rename_all = "camelCase")]
 pub struct LiquidationOrder {
     #[serde(with = "string_or_float")]
-    pub average_price: f64,
+    pub average_price: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     pub side: String,
     pub status: String,
     pub symbol: String,
@@ -305,7 +345,7 @@ pub struct LiquidationOrder {
 #[serde(rename_all = "camelCase")]
 pub struct OpenInterest {
     #[serde(with = "string_or_float")]
-    pub open_interest: f64,
+    pub open_interest: Number,
     pub symbol: String,
 }
 
@@ -314,22 +354,22 @@ pub struct OpenInterest {
 pub struct Order {
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub cum_quote: f64,
+    pub cum_quote: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     pub order_id: u64,
     #[serde(with = "string_or_float")]
-    pub avg_price: f64,
+    pub avg_price: Number,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     pub side: OrderSide,
     pub reduce_only: bool,
     pub position_side: PositionSide,
     pub status: OrderStatus,
     #[serde(with = "string_or_float", default = "default_stop_price")]
-    pub stop_price: f64,
+    pub stop_price: Number,
     pub close_position: bool,
     pub symbol: String,
     pub time_in_force: TimeInForce,
@@ -337,9 +377,9 @@ pub struct Order {
     pub order_type: OrderType,
     pub orig_type: OrderType,
     #[serde(with = "string_or_float", default = "default_activation_price")]
-    pub activate_price: f64,
+    pub activate_price: Number,
     #[serde(with = "string_or_float", default = "default_price_rate")]
-    pub price_rate: f64,
+    pub price_rate: Number,
     pub update_time: u64,
     pub working_type: WorkingType,
     pub price_protect: bool,
@@ -350,22 +390,22 @@ pub struct Order {
 pub struct Transaction {
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub cum_qty: f64,
+    pub cum_qty: Number,
     #[serde(with = "string_or_float")]
-    pub cum_quote: f64,
+    pub cum_quote: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     pub order_id: u64,
     #[serde(with = "string_or_float")]
-    pub avg_price: f64,
+    pub avg_price: Number,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     pub reduce_only: bool,
     pub side: OrderSide,
     pub position_side: PositionSide,
     pub status: OrderStatus,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Number,
     pub close_position: bool,
     pub symbol: String,
     pub time_in_force: TimeInForce,
@@ -374,10 +414,10 @@ pub struct Transaction {
     pub orig_type: OrderType,
     #[serde(default)]
     #[serde(with = "string_or_float_opt")]
-    pub activate_price: Option<f64>,
+    pub activate_price: Option<Number>,
     #[serde(default)]
     #[serde(with = "string_or_float_opt")]
-    pub price_rate: Option<f64>,
+    pub price_rate: Option<Number>,
     pub update_time: u64,
     pub working_type: WorkingType,
     price_protect: bool,
@@ -388,23 +428,23 @@ pub struct Transaction {
 pub struct CanceledOrder {
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub cum_qty: f64,
+    pub cum_qty: Number,
     #[serde(with = "string_or_float")]
-    pub cum_quote: f64,
+    pub cum_quote: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     pub order_id: u64,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     pub orig_type: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     pub reduce_only: bool,
     pub side: String,
     pub position_side: String,
     pub status: String,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Number,
     pub close_position: bool,
     pub symbol: String,
     pub time_in_force: String,
@@ -412,10 +452,10 @@ pub struct CanceledOrder {
     pub type_name: String,
     #[serde(default)]
     #[serde(with = "string_or_float_opt")]
-    pub activate_price: Option<f64>,
+    pub activate_price: Option<Number>,
     #[serde(default)]
     #[serde(with = "string_or_float_opt")]
-    pub price_rate: Option<f64>,
+    pub price_rate: Option<Number>,
     pub update_time: u64,
     pub working_type: String,
     price_protect: bool,
@@ -425,30 +465,30 @@ pub struct CanceledOrder {
 #[serde(rename_all = "camelCase")]
 pub struct Position {
     #[serde(with = "string_or_float")]
-    pub entry_price: f64,
+    pub entry_price: Number,
     pub margin_type: MarginType,
     #[serde(with = "string_or_bool")]
     pub is_auto_add_margin: bool,
     #[serde(with = "string_or_float")]
-    pub isolated_margin: f64,
+    pub isolated_margin: Number,
     pub leverage: u64,
     #[serde(with = "string_or_float")]
-    pub liquidation_price: f64,
+    pub liquidation_price: Number,
     #[serde(with = "string_or_float")]
-    pub mark_price: f64,
+    pub mark_price: Number,
     #[serde(with = "string_or_float")]
-    pub max_notional_value: f64,
+    pub max_notional_value: Number,
     #[serde(with = "string_or_float", rename = "positionAmt")]
-    pub position_amount: f64,
+    pub position_amount: Number,
     pub symbol: String,
     #[serde(with = "string_or_float", rename = "unRealizedProfit")]
-    pub unrealized_profit: f64,
+    pub unrealized_profit: Number,
     pub position_side: PositionSide,
     pub update_time: u64,
     #[serde(with = "string_or_float")]
-    pub notional: f64,
+    pub notional: Number,
     #[serde(with = "string_or_float")]
-    pub isolated_wallet: f64,
+    pub isolated_wallet: Number,
 }
 
 // https://binance-docs.github.io/apidocs/futures/en/#account-information-v2-user_data
@@ -458,29 +498,29 @@ pub struct Position {
 pub struct AccountPosition {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub initial_margin: f64,
+    pub initial_margin: Number,
     #[serde(with = "string_or_float", rename = "maintMargin")]
-    pub maintenance_margin: f64,
+    pub maintenance_margin: Number,
     #[serde(with = "string_or_float")]
-    pub unrealized_profit: f64,
+    pub unrealized_profit: Number,
     #[serde(with = "string_or_float")]
-    pub position_initial_margin: f64,
+    pub position_initial_margin: Number,
     #[serde(with = "string_or_float")]
-    pub open_order_initial_margin: f64,
+    pub open_order_initial_margin: Number,
     #[serde(with = "string_or_u64")]
     pub leverage: u64,
     pub isolated: bool,
     #[serde(with = "string_or_float")]
-    pub entry_price: f64,
+    pub entry_price: Number,
     #[serde(with = "string_or_float")]
-    pub max_notional: f64,
+    pub max_notional: Number,
     #[serde(with = "string_or_float")]
-    pub bid_notional: f64,
+    pub bid_notional: Number,
     #[serde(with = "string_or_float")]
-    pub ask_notional: f64,
+    pub ask_notional: Number,
     pub position_side: PositionSide,
     #[serde(with = "string_or_float", rename = "positionAmt")]
-    pub position_amount: f64,
+    pub position_amount: Number,
     pub update_time: u64,
 }
 
@@ -489,27 +529,27 @@ pub struct AccountPosition {
 pub struct AccountAsset {
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub wallet_balance: f64,
+    pub wallet_balance: Number,
     #[serde(with = "string_or_float")]
-    pub unrealized_profit: f64,
+    pub unrealized_profit: Number,
     #[serde(with = "string_or_float")]
-    pub margin_balance: f64,
+    pub margin_balance: Number,
     #[serde(with = "string_or_float")]
-    pub maint_margin: f64,
+    pub maint_margin: Number,
     #[serde(with = "string_or_float")]
-    pub initial_margin: f64,
+    pub initial_margin: Number,
     #[serde(with = "string_or_float")]
-    pub position_initial_margin: f64,
+    pub position_initial_margin: Number,
     #[serde(with = "string_or_float")]
-    pub open_order_initial_margin: f64,
+    pub open_order_initial_margin: Number,
     #[serde(with = "string_or_float")]
-    pub cross_wallet_balance: f64,
+    pub cross_wallet_balance: Number,
     #[serde(with = "string_or_float", rename = "crossUnPnl")]
-    pub cross_unrealized_pnl: f64,
+    pub cross_unrealized_pnl: Number,
     #[serde(with = "string_or_float")]
-    pub available_balance: f64,
+    pub available_balance: Number,
     #[serde(with = "string_or_float")]
-    pub max_withdraw_amount: f64,
+    pub max_withdraw_amount: Number,
     pub margin_available: bool,
     pub update_time: u64,
 }
@@ -524,27 +564,27 @@ pub struct AccountInformation {
     pub update_time: u64,
     pub multi_assets_margin: bool,
     #[serde(with = "string_or_float")]
-    pub total_initial_margin: f64,
+    pub total_initial_margin: Number,
     #[serde(with = "string_or_float", rename = "totalMaintMargin")]
-    pub total_maintenance_margin: f64,
+    pub total_maintenance_margin: Number,
     #[serde(with = "string_or_float")]
-    pub total_wallet_balance: f64,
+    pub total_wallet_balance: Number,
     #[serde(with = "string_or_float")]
-    pub total_unrealized_profit: f64,
+    pub total_unrealized_profit: Number,
     #[serde(with = "string_or_float")]
-    pub total_margin_balance: f64,
+    pub total_margin_balance: Number,
     #[serde(with = "string_or_float")]
-    pub total_position_initial_margin: f64,
+    pub total_position_initial_margin: Number,
     #[serde(with = "string_or_float")]
-    pub total_open_order_initial_margin: f64,
+    pub total_open_order_initial_margin: Number,
     #[serde(with = "string_or_float")]
-    pub total_cross_wallet_balance: f64,
+    pub total_cross_wallet_balance: Number,
     #[serde(with = "string_or_float", rename = "totalCrossUnPnl")]
-    pub total_cross_unrealized_pnl: f64,
+    pub total_cross_unrealized_pnl: Number,
     #[serde(with = "string_or_float")]
-    pub available_balance: f64,
+    pub available_balance: Number,
     #[serde(with = "string_or_float")]
-    pub max_withdraw_amount: f64,
+    pub max_withdraw_amount: Number,
     pub assets: Vec<AccountAsset>,
     pub positions: Vec<AccountPosition>,
 }
@@ -555,15 +595,15 @@ pub struct AccountBalance {
     pub account_alias: String,
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub balance: f64,
+    pub balance: Number,
     #[serde(with = "string_or_float")]
-    pub cross_wallet_balance: f64,
+    pub cross_wallet_balance: Number,
     #[serde(with = "string_or_float", rename = "crossUnPnl")]
-    pub cross_unrealized_pnl: f64,
+    pub cross_unrealized_pnl: Number,
     #[serde(with = "string_or_float")]
-    pub available_balance: f64,
+    pub available_balance: Number,
     #[serde(with = "string_or_float")]
-    pub max_withdraw_amount: f64,
+    pub max_withdraw_amount: Number,
     pub margin_available: bool,
     pub update_time: u64,
 }
@@ -573,19 +613,13 @@ pub struct AccountBalance {
 pub struct ChangeLeverageResponse {
     pub leverage: u8,
     #[serde(with = "string_or_float")]
-    pub max_notional_value: f64,
+    pub max_notional_value: Number,
     pub symbol: String,
 }
 
-fn default_stop_price() -> f64 {
-    0.0
-}
-fn default_activation_price() -> f64 {
-    0.0
-}
-fn default_price_rate() -> f64 {
-    0.0
-}
+fn default_stop_price() -> Number { Number::default() }
+fn default_activation_price() -> Number { Number::default() }
+fn default_price_rate() -> Number { Number::default() }
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -595,7 +629,7 @@ pub(crate) struct HistoryQuery {
     pub from_id: Option<u64>,
     pub limit: u16,
     pub symbol: String,
-    pub interval: Option<String>,
+    pub interval: Option<KlineInterval>,
     pub period: Option<String>,
 }
 
@@ -616,7 +650,7 @@ pub struct FundingRate {
     pub symbol: String,
     pub funding_time: u64,
     #[serde(with = "string_or_float")]
-    pub funding_rate: f64,
+    pub funding_rate: Number,
 }
 
 pub static PERIODS: &[&str] = &["5m", "15m", "30m", "1h", "2h", "4h", "6h", "12h", "1d"];
@@ -626,9 +660,9 @@ pub static PERIODS: &[&str] = &["5m", "15m", "30m", "1h", "2h", "4h", "6h", "12h
 pub struct OpenInterestHistory {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub sum_open_interest: f64,
+    pub sum_open_interest: Number,
     #[serde(with = "string_or_float")]
-    pub sum_open_interest_value: f64,
+    pub sum_open_interest_value: Number,
     pub timestamp: u64,
 }
 
@@ -637,11 +671,11 @@ pub struct OpenInterestHistory {
 pub struct LongShortRatio {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub long_account: f64,
+    pub long_account: Number,
     #[serde(with = "string_or_float")]
-    pub long_short_ratio: f64,
+    pub long_short_ratio: Number,
     #[serde(with = "string_or_float")]
-    pub short_account: f64,
+    pub short_account: Number,
     pub timestamp: u64,
 }
 
@@ -652,7 +686,7 @@ pub struct LeverageBracket {
     pub initial_leverage: u8,
     pub notional_cap: u64,
     pub notional_floor: u64,
-    pub maint_margin_ratio: f64,
+    pub maint_margin_ratio: Number,
     pub cum: u64,
 }
 
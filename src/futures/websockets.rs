@@ -0,0 +1,69 @@
+//! Stream names for the USD-M futures websocket endpoint,
+//! use with [`WebSockets::new_futures`](crate::websockets::WebSockets::new_futures)
+
+use super::rest_model::ContractType;
+use crate::rest_model::KlineInterval;
+
+pub use crate::websockets::{agg_trade_stream, all_book_ticker_stream, all_mini_ticker_stream, all_ticker_stream,
+                            book_ticker_stream, kline_stream, mini_ticker_stream, ticker_stream};
+
+/// # Arguments
+///
+/// * `symbol`: the market symbol
+/// * `update_speed`: 3000 or 1000
+pub fn mark_price_stream(symbol: &str, update_speed: u16) -> String {
+    match update_speed {
+        1000 => format!("{symbol}@markPrice@1s"),
+        _ => format!("{symbol}@markPrice"),
+    }
+}
+
+/// # Arguments
+///
+/// * `update_speed`: 3000 or 1000
+pub fn all_mark_price_stream(update_speed: u16) -> &'static str {
+    match update_speed {
+        1000 => "!markPrice@arr@1s",
+        _ => "!markPrice@arr",
+    }
+}
+
+/// # Arguments
+///
+/// * `pair`: the pair, e.g. btcusdt
+/// * `contract_type`: perpetual, current quarter or next quarter
+/// * `interval`: the kline interval
+pub fn continuous_kline_stream(pair: &str, contract_type: ContractType, interval: KlineInterval) -> String {
+    let contract_type = match contract_type {
+        ContractType::Perpetual => "perpetual",
+        ContractType::CurrentMonth => "current_month",
+        ContractType::NextMonth => "next_month",
+        ContractType::CurrentQuarter => "current_quarter",
+        ContractType::NextQuarter => "next_quarter",
+        ContractType::Empty => "",
+    };
+    format!("{pair}_{contract_type}@continuousKline_{interval}")
+}
+
+pub fn liquidation_order_stream(symbol: &str) -> String { format!("{symbol}@forceOrder") }
+
+pub fn all_liquidation_order_stream() -> &'static str { "!forceOrder@arr" }
+
+/// # Arguments
+///
+/// * `symbol`: the market symbol
+/// * `levels`: 5, 10 or 20
+/// * `update_speed`: 500, 250 or 100
+pub fn partial_book_depth_stream(symbol: &str, levels: u16, update_speed: u16) -> String {
+    format!("{symbol}@depth{levels}@{update_speed}ms")
+}
+
+/// # Arguments
+///
+/// * `symbol`: the market symbol
+/// * `update_speed`: 500, 250 or 100
+pub fn diff_book_depth_stream(symbol: &str, update_speed: u16) -> String { format!("{symbol}@depth@{update_speed}ms") }
+
+pub fn composite_index_stream(symbol: &str) -> String { format!("{symbol}@compositeIndex") }
+
+pub fn contract_info_stream() -> &'static str { "!contractInfo" }
@@ -0,0 +1,824 @@
+use super::rest_model::{ContractType, MarginType, OrderType, PositionSide, WorkingType};
+use crate::rest_model::{string_or_float, string_or_float_opt, string_or_u64, Asks, Bids, KlineInterval, Number,
+                        OrderSide, OrderStatus, SymbolStatus, TimeInForce};
+use crate::ws_model::{Kline, MiniDayTickerEvent};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "e")]
+pub enum FuturesWebsocketEvent {
+    #[serde(alias = "aggTrade")]
+    AggTrade(Box<AggTradeEvent>),
+    #[serde(alias = "markPriceUpdate")]
+    MarkPriceUpdate(Box<MarkPriceEvent>),
+    #[serde(alias = "kline")]
+    Kline(Box<KlineEvent>),
+    #[serde(alias = "continuous_kline")]
+    ContinuousKline(Box<ContinuousKlineEvent>),
+    #[serde(alias = "24hrTicker")]
+    DayTicker(Box<DayTickerEvent>),
+    #[serde(alias = "24hrMiniTicker")]
+    DayMiniTicker(Box<MiniDayTickerEvent>),
+    #[serde(alias = "bookTicker")]
+    BookTicker(Box<BookTickerEvent>),
+    #[serde(alias = "forceOrder")]
+    LiquidationOrder(Box<LiquidationOrderEvent>),
+    #[serde(alias = "depthUpdate")]
+    DepthOrderBook(Box<DepthOrderBookEvent>),
+    #[serde(alias = "compositeIndex")]
+    CompositeIndex(Box<CompositeIndexEvent>),
+    #[serde(alias = "contractInfo")]
+    ContractInfo(Box<ContractInfoEvent>),
+    #[serde(alias = "listenKeyExpired")]
+    ListenKeyExpired(Box<ListenKeyExpiredEvent>),
+    #[serde(alias = "MARGIN_CALL")]
+    MarginCall(Box<MarginCallEvent>),
+    #[serde(alias = "ACCOUNT_UPDATE")]
+    AccountUpdate(Box<AccountUpdateEvent>),
+    #[serde(alias = "ORDER_TRADE_UPDATE")]
+    OrderTradeUpdate(Box<OrderTradeUpdateEvent>),
+    #[serde(alias = "ACCOUNT_CONFIG_UPDATE")]
+    AccountConfigUpdate(Box<AccountConfigUpdateEvent>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AggTradeEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "a")]
+    pub aggregated_trade_id: u64,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Number,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub qty: Number,
+    #[serde(rename = "f")]
+    pub first_trade_id: u64,
+    #[serde(rename = "l")]
+    pub last_trade_id: u64,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+    #[serde(rename = "m")]
+    pub is_buyer_maker: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkPriceEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub mark_price: Number,
+    #[serde(rename = "i", with = "string_or_float")]
+    pub index_price: Number,
+    /// Only useful in the last hour before the settlement starts
+    #[serde(rename = "P", with = "string_or_float")]
+    pub estimated_settle_price: Number,
+    /// `None` for delivery contracts
+    #[serde(rename = "r", default, with = "string_or_float_opt")]
+    pub funding_rate: Option<Number>,
+    #[serde(rename = "T")]
+    pub next_funding_time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KlineEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "k")]
+    pub kline: Kline,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinuousKlineEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "ps")]
+    pub pair: String,
+    #[serde(rename = "ct")]
+    pub contract_type: ContractType,
+    #[serde(rename = "k")]
+    pub kline: ContinuousKline,
+}
+
+/// Same as [`Kline`], without the symbol as continuous klines are computed for a pair
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinuousKline {
+    #[serde(rename = "t")]
+    pub start_time: i64,
+    #[serde(rename = "T")]
+    pub end_time: i64,
+    #[serde(rename = "i")]
+    pub interval: KlineInterval,
+    #[serde(rename = "f")]
+    pub first_update_id: i64,
+    #[serde(rename = "L")]
+    pub last_update_id: i64,
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open: Number,
+    #[serde(rename = "c", with = "string_or_float")]
+    pub close: Number,
+    #[serde(rename = "h", with = "string_or_float")]
+    pub high: Number,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub low: Number,
+    #[serde(rename = "v", with = "string_or_float")]
+    pub volume: Number,
+    #[serde(rename = "n")]
+    pub number_of_trades: i64,
+    #[serde(rename = "x")]
+    pub is_final_bar: bool,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub quote_volume: Number,
+    #[serde(rename = "V", with = "string_or_float")]
+    pub active_buy_volume: Number,
+    #[serde(rename = "Q", with = "string_or_float")]
+    pub active_volume_buy_quote: Number,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DayTickerEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price_change: Number,
+    #[serde(rename = "P", with = "string_or_float")]
+    pub price_change_percent: Number,
+    #[serde(rename = "w", with = "string_or_float")]
+    pub average_price: Number,
+    #[serde(rename = "c", with = "string_or_float")]
+    pub current_close: Number,
+    #[serde(rename = "Q", with = "string_or_float")]
+    pub current_close_qty: Number,
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open: Number,
+    #[serde(rename = "h", with = "string_or_float")]
+    pub high: Number,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub low: Number,
+    #[serde(rename = "v", with = "string_or_float")]
+    pub volume: Number,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub quote_volume: Number,
+    #[serde(rename = "O")]
+    pub open_time: u64,
+    #[serde(rename = "C")]
+    pub close_time: u64,
+    #[serde(rename = "F")]
+    pub first_trade_id: i64,
+    #[serde(rename = "L")]
+    pub last_trade_id: i64,
+    #[serde(rename = "n")]
+    pub num_trades: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BookTickerEvent {
+    #[serde(rename = "u")]
+    pub update_id: u64,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "b", with = "string_or_float")]
+    pub best_bid: Number,
+    #[serde(rename = "B", with = "string_or_float")]
+    pub best_bid_qty: Number,
+    #[serde(rename = "a", with = "string_or_float")]
+    pub best_ask: Number,
+    #[serde(rename = "A", with = "string_or_float")]
+    pub best_ask_qty: Number,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidationOrderEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "o")]
+    pub order: LiquidationOrder,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidationOrder {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "S")]
+    pub side: OrderSide,
+    #[serde(rename = "o")]
+    pub order_type: OrderType,
+    #[serde(rename = "f")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub original_qty: Number,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Number,
+    #[serde(rename = "ap", with = "string_or_float")]
+    pub average_price: Number,
+    #[serde(rename = "X")]
+    pub order_status: OrderStatus,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub last_filled_qty: Number,
+    #[serde(rename = "z", with = "string_or_float")]
+    pub accumulated_filled_qty: Number,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+}
+
+/// Diff and partial depth updates, partial depth streams push the same event
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DepthOrderBookEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "U")]
+    pub first_update_id: u64,
+    #[serde(rename = "u")]
+    pub final_update_id: u64,
+    /// Final update id of the previous event
+    #[serde(rename = "pu")]
+    pub previous_final_update_id: u64,
+    #[serde(rename = "b")]
+    pub bids: Vec<Bids>,
+    #[serde(rename = "a")]
+    pub asks: Vec<Asks>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CompositeIndexEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: Number,
+    #[serde(rename = "C")]
+    pub base_asset: String,
+    #[serde(rename = "c")]
+    pub composition: Vec<IndexComposition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexComposition {
+    #[serde(rename = "b")]
+    pub base_asset: String,
+    #[serde(rename = "q")]
+    pub quote_asset: String,
+    #[serde(rename = "w", with = "string_or_float")]
+    pub weight_in_quantity: Number,
+    #[serde(rename = "W", with = "string_or_float")]
+    pub weight_in_percentage: Number,
+    #[serde(rename = "i", with = "string_or_float")]
+    pub index_price: Number,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractInfoEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "ps")]
+    pub pair: String,
+    #[serde(rename = "ct")]
+    pub contract_type: ContractType,
+    #[serde(rename = "dt")]
+    pub delivery_date: u64,
+    #[serde(rename = "ot")]
+    pub onboard_date: u64,
+    #[serde(rename = "cs")]
+    pub contract_status: SymbolStatus,
+    /// Only sent when the brackets are updated
+    #[serde(rename = "bks", default)]
+    pub brackets: Vec<ContractBracket>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractBracket {
+    #[serde(rename = "bs")]
+    pub bracket: u64,
+    #[serde(rename = "bnf")]
+    pub notional_floor: Number,
+    #[serde(rename = "bnc")]
+    pub notional_cap: Number,
+    #[serde(rename = "mmr")]
+    pub maintenance_ratio: Number,
+    #[serde(rename = "cf")]
+    pub cum: Number,
+    #[serde(rename = "mi")]
+    pub min_leverage: u64,
+    #[serde(rename = "ma")]
+    pub max_leverage: u64,
+}
+
+// User Data Stream events
+
+/// The listen key expired, a new one has to be requested and the stream reconnected
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKeyExpiredEvent {
+    #[serde(rename = "E", with = "string_or_u64")]
+    pub event_time: u64,
+    pub listen_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginCallEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    /// Only pushed with crossed positions
+    #[serde(rename = "cw", default, with = "string_or_float_opt")]
+    pub cross_wallet_balance: Option<Number>,
+    #[serde(rename = "p")]
+    pub positions: Vec<MarginCallPosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginCallPosition {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "ps")]
+    pub position_side: PositionSide,
+    #[serde(rename = "pa", with = "string_or_float")]
+    pub position_amount: Number,
+    #[serde(rename = "mt")]
+    pub margin_type: MarginType,
+    /// Only for isolated positions
+    #[serde(rename = "iw", with = "string_or_float")]
+    pub isolated_wallet: Number,
+    #[serde(rename = "mp", with = "string_or_float")]
+    pub mark_price: Number,
+    #[serde(rename = "up", with = "string_or_float")]
+    pub unrealized_pnl: Number,
+    #[serde(rename = "mm", with = "string_or_float")]
+    pub maintenance_margin_required: Number,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUpdateEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "a")]
+    pub data: AccountUpdateData,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUpdateData {
+    #[serde(rename = "m")]
+    pub reason: AccountUpdateReason,
+    #[serde(rename = "B")]
+    pub balances: Vec<AccountUpdateBalance>,
+    /// Only the positions that changed are pushed
+    #[serde(rename = "P")]
+    pub positions: Vec<AccountUpdatePosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AccountUpdateReason {
+    Deposit,
+    Withdraw,
+    Order,
+    FundingFee,
+    WithdrawReject,
+    Adjustment,
+    InsuranceClear,
+    AdminDeposit,
+    AdminWithdraw,
+    MarginTransfer,
+    MarginTypeChange,
+    AssetTransfer,
+    OptionsPremiumFee,
+    OptionsSettleProfit,
+    AutoExchange,
+    CoinSwapDeposit,
+    CoinSwapWithdraw,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUpdateBalance {
+    #[serde(rename = "a")]
+    pub asset: String,
+    #[serde(rename = "wb", with = "string_or_float")]
+    pub wallet_balance: Number,
+    #[serde(rename = "cw", with = "string_or_float")]
+    pub cross_wallet_balance: Number,
+    /// Balance change except PnL and commission
+    #[serde(rename = "bc", with = "string_or_float")]
+    pub balance_change: Number,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUpdatePosition {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "pa", with = "string_or_float")]
+    pub position_amount: Number,
+    #[serde(rename = "ep", with = "string_or_float")]
+    pub entry_price: Number,
+    #[serde(rename = "bep", default, with = "string_or_float_opt")]
+    pub breakeven_price: Option<Number>,
+    /// Pre-fee accumulated realized PnL
+    #[serde(rename = "cr", with = "string_or_float")]
+    pub accumulated_realized: Number,
+    #[serde(rename = "up", with = "string_or_float")]
+    pub unrealized_pnl: Number,
+    #[serde(rename = "mt")]
+    pub margin_type: MarginType,
+    /// Only for isolated positions
+    #[serde(rename = "iw", with = "string_or_float")]
+    pub isolated_wallet: Number,
+    #[serde(rename = "ps")]
+    pub position_side: PositionSide,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderTradeUpdateEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "o")]
+    pub order: OrderTradeUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExecutionType {
+    New,
+    Canceled,
+    /// Liquidation execution
+    Calculated,
+    Expired,
+    Trade,
+    Amendment,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderTradeUpdate {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+    #[serde(rename = "S")]
+    pub side: OrderSide,
+    #[serde(rename = "o")]
+    pub order_type: OrderType,
+    #[serde(rename = "f")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub original_qty: Number,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub original_price: Number,
+    #[serde(rename = "ap", with = "string_or_float")]
+    pub average_price: Number,
+    #[serde(rename = "sp", with = "string_or_float")]
+    pub stop_price: Number,
+    #[serde(rename = "x")]
+    pub execution_type: ExecutionType,
+    #[serde(rename = "X")]
+    pub order_status: OrderStatus,
+    #[serde(rename = "i")]
+    pub order_id: u64,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub last_filled_qty: Number,
+    #[serde(rename = "z", with = "string_or_float")]
+    pub accumulated_filled_qty: Number,
+    #[serde(rename = "L", with = "string_or_float")]
+    pub last_filled_price: Number,
+    /// Not pushed if no commission was charged
+    #[serde(rename = "N")]
+    pub commission_asset: Option<String>,
+    #[serde(rename = "n", default, with = "string_or_float_opt")]
+    pub commission: Option<Number>,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+    #[serde(rename = "t")]
+    pub trade_id: u64,
+    #[serde(rename = "b", with = "string_or_float")]
+    pub bids_notional: Number,
+    #[serde(rename = "a", with = "string_or_float")]
+    pub asks_notional: Number,
+    #[serde(rename = "m")]
+    pub is_maker: bool,
+    #[serde(rename = "R")]
+    pub is_reduce_only: bool,
+    #[serde(rename = "wt")]
+    pub stop_price_working_type: WorkingType,
+    #[serde(rename = "ot")]
+    pub original_order_type: OrderType,
+    #[serde(rename = "ps")]
+    pub position_side: PositionSide,
+    /// If close-all, pushed with conditional orders
+    #[serde(rename = "cp", default)]
+    pub close_all: bool,
+    /// Only pushed with trailing stop orders
+    #[serde(rename = "AP", default, with = "string_or_float_opt")]
+    pub activation_price: Option<Number>,
+    /// Only pushed with trailing stop orders
+    #[serde(rename = "cr", default, with = "string_or_float_opt")]
+    pub callback_rate: Option<Number>,
+    #[serde(rename = "rp", with = "string_or_float")]
+    pub realized_profit: Number,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountConfigUpdateEvent {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    /// Pushed when the leverage of a symbol changes
+    #[serde(rename = "ac")]
+    pub leverage: Option<LeverageUpdate>,
+    /// Pushed when the multi-assets mode changes
+    #[serde(rename = "ai")]
+    pub multi_assets_mode: Option<MultiAssetsModeUpdate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeverageUpdate {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "l")]
+    pub leverage: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiAssetsModeUpdate {
+    #[serde(rename = "j")]
+    pub multi_assets_mode: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(json: serde_json::Value) -> FuturesWebsocketEvent { serde_json::from_value(json).unwrap() }
+
+    fn num(value: &str) -> Number { value.parse().unwrap() }
+
+    #[test]
+    fn agg_trade() {
+        let FuturesWebsocketEvent::AggTrade(trade) = event(serde_json::json!({
+            "e": "aggTrade", "E": 123456789, "s": "BTCUSDT", "a": 5933014, "p": "0.001", "q": "100", "f": 100,
+            "l": 105, "T": 123456785, "m": true
+        })) else {
+            panic!("aggTrade")
+        };
+        assert_eq!(trade.price, num("0.001"));
+    }
+
+    #[test]
+    fn mark_price_update() {
+        let FuturesWebsocketEvent::MarkPriceUpdate(mark_price) = event(serde_json::json!({
+            "e": "markPriceUpdate", "E": 1562305380000u64, "s": "BTCUSDT", "p": "11794.15000000",
+            "i": "11784.62659091", "P": "11784.25641265", "r": "0.00038167", "T": 1562306400000u64
+        })) else {
+            panic!("markPriceUpdate")
+        };
+        assert_eq!(mark_price.funding_rate, Some(num("0.00038167")));
+        let FuturesWebsocketEvent::MarkPriceUpdate(mark_price) = event(serde_json::json!({
+            "e": "markPriceUpdate", "E": 1562305380000u64, "s": "BTCUSD_231229", "p": "11794.15000000",
+            "i": "11784.62659091", "P": "11784.25641265", "r": "", "T": 0
+        })) else {
+            panic!("markPriceUpdate")
+        };
+        assert_eq!(mark_price.funding_rate, None);
+    }
+
+    #[test]
+    fn kline() {
+        let FuturesWebsocketEvent::Kline(kline) = event(serde_json::json!({
+            "e": "kline", "E": 1638747660000u64, "s": "BTCUSDT",
+            "k": {
+                "t": 1638747660000u64, "T": 1638747719999u64, "s": "BTCUSDT", "i": "1m", "f": 100, "L": 200,
+                "o": "0.0010", "c": "0.0020", "h": "0.0025", "l": "0.0015", "v": "1000", "n": 100, "x": false,
+                "q": "1.0000", "V": "500", "Q": "0.500", "B": "123456"
+            }
+        })) else {
+            panic!("kline")
+        };
+        assert_eq!(kline.kline.interval, KlineInterval::OneMinute);
+    }
+
+    #[test]
+    fn continuous_kline() {
+        let FuturesWebsocketEvent::ContinuousKline(kline) = event(serde_json::json!({
+            "e": "continuous_kline", "E": 1607443058651u64, "ps": "BTCUSDT", "ct": "PERPETUAL",
+            "k": {
+                "t": 1607443020000u64, "T": 1607443079999u64, "i": "1m", "f": 116467658886u64,
+                "L": 116468012423u64, "o": "18787.00", "c": "18804.04", "h": "18804.04", "l": "18786.54",
+                "v": "197.664", "n": 543, "x": false, "q": "3715253.19494", "V": "184.769", "Q": "3472925.84746",
+                "B": "0"
+            }
+        })) else {
+            panic!("continuous_kline")
+        };
+        assert_eq!(kline.kline.close, num("18804.04"));
+    }
+
+    #[test]
+    fn day_ticker() {
+        let FuturesWebsocketEvent::DayTicker(ticker) = event(serde_json::json!({
+            "e": "24hrTicker", "E": 123456789, "s": "BTCUSDT", "p": "0.0015", "P": "250.00", "w": "0.0018",
+            "c": "0.0025", "Q": "10", "o": "0.0010", "h": "0.0025", "l": "0.0010", "v": "10000", "q": "18",
+            "O": 0, "C": 86400000, "F": 0, "L": 18150, "n": 18151
+        })) else {
+            panic!("24hrTicker")
+        };
+        assert_eq!(ticker.num_trades, 18151);
+    }
+
+    #[test]
+    fn day_mini_ticker() {
+        let FuturesWebsocketEvent::DayMiniTicker(ticker) = event(serde_json::json!({
+            "e": "24hrMiniTicker", "E": 123456789, "s": "BTCUSDT", "c": "0.0025", "o": "0.0010", "h": "0.0025",
+            "l": "0.0010", "v": "10000", "q": "18"
+        })) else {
+            panic!("24hrMiniTicker")
+        };
+        assert_eq!(ticker.symbol, "BTCUSDT");
+    }
+
+    #[test]
+    fn book_ticker() {
+        let FuturesWebsocketEvent::BookTicker(ticker) = event(serde_json::json!({
+            "e": "bookTicker", "u": 400900217, "E": 1568014460893u64, "T": 1568014460891u64, "s": "BNBUSDT",
+            "b": "25.35190000", "B": "31.21000000", "a": "25.36520000", "A": "40.66000000"
+        })) else {
+            panic!("bookTicker")
+        };
+        assert_eq!(ticker.best_ask, num("25.3652"));
+    }
+
+    #[test]
+    fn liquidation_order() {
+        let FuturesWebsocketEvent::LiquidationOrder(liquidation) = event(serde_json::json!({
+            "e": "forceOrder", "E": 1568014460893u64,
+            "o": {
+                "s": "BTCUSDT", "S": "SELL", "o": "LIMIT", "f": "IOC", "q": "0.014", "p": "9910", "ap": "9910",
+                "X": "FILLED", "l": "0.014", "z": "0.014", "T": 1568014460893u64
+            }
+        })) else {
+            panic!("forceOrder")
+        };
+        assert_eq!(liquidation.order.order_status, OrderStatus::Filled);
+    }
+
+    #[test]
+    fn depth_order_book() {
+        let FuturesWebsocketEvent::DepthOrderBook(depth) = event(serde_json::json!({
+            "e": "depthUpdate", "E": 123456789, "T": 123456788, "s": "BTCUSDT", "U": 157, "u": 160, "pu": 149,
+            "b": [["0.0024", "10"]], "a": [["0.0026", "100"]]
+        })) else {
+            panic!("depthUpdate")
+        };
+        assert_eq!((depth.previous_final_update_id, depth.bids.len()), (149, 1));
+    }
+
+    #[test]
+    fn composite_index() {
+        let FuturesWebsocketEvent::CompositeIndex(index) = event(serde_json::json!({
+            "e": "compositeIndex", "E": 1602310596000u64, "s": "DEFIUSDT", "p": "554.41604065", "C": "baseAsset",
+            "c": [{"b": "BAL", "q": "USDT", "w": "1.04884844", "W": "0.01457800", "i": "24.33521021"}]
+        })) else {
+            panic!("compositeIndex")
+        };
+        assert_eq!(index.composition[0].base_asset, "BAL");
+    }
+
+    #[test]
+    fn contract_info() {
+        let FuturesWebsocketEvent::ContractInfo(info) = event(serde_json::json!({
+            "e": "contractInfo", "E": 1669356423908u64, "s": "IOTAUSDT", "ps": "IOTAUSDT", "ct": "PERPETUAL",
+            "dt": 4133404800000u64, "ot": 1569398400000u64, "cs": "TRADING",
+            "bks": [{"bs": 1, "bnf": 0, "bnc": 5000, "mmr": 0.01, "cf": 0, "mi": 21, "ma": 50}]
+        })) else {
+            panic!("contractInfo")
+        };
+        assert_eq!(info.brackets[0].max_leverage, 50);
+    }
+
+    #[test]
+    fn listen_key_expired() {
+        for event_time in [serde_json::json!("1576653824250"), serde_json::json!(1576653824250u64)] {
+            let FuturesWebsocketEvent::ListenKeyExpired(expired) = event(serde_json::json!({
+                "e": "listenKeyExpired",
+                "E": event_time,
+                "listenKey": "WsCMN0a4KHUPTQuX6IUnqEZfB1inxmv1qR4kbf1LuEjur5VdbzqvyxqG9TSjVVxv"
+            })) else {
+                panic!("listenKeyExpired")
+            };
+            assert_eq!(expired.event_time, 1576653824250);
+        }
+    }
+
+    #[test]
+    fn margin_call() {
+        let FuturesWebsocketEvent::MarginCall(margin_call) = event(serde_json::json!({
+            "e": "MARGIN_CALL", "E": 1587727187525u64, "cw": "3.16812045",
+            "p": [{
+                "s": "ETHUSDT", "ps": "LONG", "pa": "1.327", "mt": "CROSSED", "iw": "0", "mp": "187.17127",
+                "up": "-1.166074", "mm": "1.614445"
+            }]
+        })) else {
+            panic!("MARGIN_CALL")
+        };
+        assert_eq!(margin_call.cross_wallet_balance, Some(num("3.16812045")));
+    }
+
+    #[test]
+    fn account_update() {
+        let FuturesWebsocketEvent::AccountUpdate(update) = event(serde_json::json!({
+            "e": "ACCOUNT_UPDATE", "E": 1564745798939u64, "T": 1564745798938u64,
+            "a": {
+                "m": "ORDER",
+                "B": [{"a": "USDT", "wb": "122624.12345678", "cw": "100.12345678", "bc": "50.12345678"}],
+                "P": [{
+                    "s": "BTCUSDT", "pa": "0", "ep": "0.00000", "bep": "0", "cr": "200", "up": "0", "mt": "isolated",
+                    "iw": "0.00000000", "ps": "BOTH"
+                }]
+            }
+        })) else {
+            panic!("ACCOUNT_UPDATE")
+        };
+        assert_eq!(update.data.reason, AccountUpdateReason::Order);
+        assert_eq!(update.data.positions[0].accumulated_realized, num("200"));
+    }
+
+    #[test]
+    fn order_trade_update() {
+        let FuturesWebsocketEvent::OrderTradeUpdate(update) = event(serde_json::json!({
+            "e": "ORDER_TRADE_UPDATE", "E": 1568879465651u64, "T": 1568879465650u64,
+            "o": {
+                "s": "BTCUSDT", "c": "TEST", "S": "SELL", "o": "TRAILING_STOP_MARKET", "f": "GTC", "q": "0.001",
+                "p": "0", "ap": "0", "sp": "7103.04", "x": "NEW", "X": "NEW", "i": 8886774, "l": "0", "z": "0",
+                "L": "0", "N": "USDT", "n": "0", "T": 1568879465650u64, "t": 0, "b": "0", "a": "9.91", "m": false,
+                "R": false, "wt": "CONTRACT_PRICE", "ot": "TRAILING_STOP_MARKET", "ps": "LONG", "cp": false,
+                "AP": "7476.89", "cr": "5.0", "rp": "0"
+            }
+        })) else {
+            panic!("ORDER_TRADE_UPDATE")
+        };
+        assert_eq!(update.order.execution_type, ExecutionType::New);
+        assert_eq!(update.order.activation_price, Some(num("7476.89")));
+    }
+
+    #[test]
+    fn account_config_update() {
+        let FuturesWebsocketEvent::AccountConfigUpdate(update) = event(serde_json::json!({
+            "e": "ACCOUNT_CONFIG_UPDATE", "E": 1611646737479u64, "T": 1611646737476u64,
+            "ac": {"s": "BTCUSDT", "l": 25}
+        })) else {
+            panic!("ACCOUNT_CONFIG_UPDATE")
+        };
+        assert_eq!(update.leverage.map(|leverage| leverage.leverage), Some(25));
+        let FuturesWebsocketEvent::AccountConfigUpdate(update) = event(serde_json::json!({
+            "e": "ACCOUNT_CONFIG_UPDATE", "E": 1611646737479u64, "T": 1611646737476u64, "ai": {"j": true}
+        })) else {
+            panic!("ACCOUNT_CONFIG_UPDATE")
+        };
+        assert!(update.multi_assets_mode.is_some_and(|mode| mode.multi_assets_mode));
+    }
+}
@@ -37,6 +37,17 @@ impl General {
     /// ```
     pub async fn get_server_time(&self) -> Result<ServerTime> { self.client.get("/api/v3/time", None).await }
 
+    /// Measure the offset between the local clock and the server clock, and apply it to signed requests
+    /// Returns the offset in milliseconds
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, general::*, config::*};
+    /// let general: General = Binance::new_with_env(&Config::default());
+    /// let offset = tokio_test::block_on(general.sync_time());
+    /// assert!(offset.is_ok(), "{:?}", offset);
+    /// ```
+    pub async fn sync_time(&self) -> Result<i64> { self.client.sync_time("/api/v3/time").await }
+
     /// Obtain exchange information (rate limits, symbol metadata etc)
     /// # Examples
     /// ```rust
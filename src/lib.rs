@@ -52,17 +52,17 @@
 //! # Details
 //!
 //! - Credentials are not enforced, you will get authentication errors if you don't provide
-//! credentials and they are required by an endpoint
+//!   credentials and they are required by an endpoint
 //!
 //! - Error codes are handled on a best effort basis as some are inconsistent and not even
-//! documented on Binance's side
+//!   documented on Binance's side
 //!
 //! - Errors are implemented using [![thiserror]](https://docs.rs/thiserror/1.0.25/thiserror/)
 //!
 
 #![deny(unstable_features, unused_must_use, unused_mut, unused_imports, unused_import_braces)]
 
-// #[macro_use]
+#[macro_use]
 extern crate lazy_static;
 #[macro_use]
 extern crate serde;
@@ -77,18 +77,32 @@ pub mod util;
 
 pub mod account;
 pub mod api;
+pub mod backfill;
 pub mod config;
+pub mod filters;
 #[cfg(feature = "futures_api")]
 pub mod futures;
 pub mod general;
 #[cfg(feature = "margin_api")]
 pub mod margin;
 pub mod market;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod orderbook;
+mod pagination;
+pub mod paper;
+pub mod rate_limit;
+#[cfg(feature = "recorder")]
+pub mod recorder;
 pub mod rest_model;
+pub mod retry;
 #[cfg(feature = "savings_api")]
 pub mod savings;
+pub mod signer;
+pub mod symbols;
 pub mod userstream;
 #[cfg(feature = "wallet_api")]
 pub mod wallet;
 pub mod websockets;
+pub mod ws_api;
 pub mod ws_model;
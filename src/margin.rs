@@ -1,7 +1,9 @@
 use crate::client::*;
 use crate::errors::*;
+use crate::pagination::*;
 use crate::rest_model::*;
 use crate::util::bool_to_string;
+use futures::stream::BoxStream;
 
 static SAPI_V1_MARGIN_TRANSFER: &str = "/sapi/v1/margin/transfer";
 static SAPI_V1_MARGIN_ISOLATED_TRANSFER: &str = "/sapi/v1/margin/isolated/transfer";
@@ -48,13 +50,14 @@ impl Margin {
     /// ```rust,no_run
     /// use binance::{api::*, margin::*, config::*, rest_model::*};
     /// let margin: Margin = Binance::new_with_env(&Config::testnet());
-    /// let transaction_id = tokio_test::block_on(margin.transfer("BTCUSDT", 0.001, MarginTransferType::FromMainToMargin));
+    /// let qty: Number = "0.001".parse().unwrap();
+    /// let transaction_id = tokio_test::block_on(margin.transfer("BTCUSDT", qty, MarginTransferType::FromMainToMargin));
     /// assert!(transaction_id.is_ok(), "{:?}", transaction_id);
     /// ```
     pub async fn transfer<S, F>(&self, symbol: S, qty: F, transfer_type: MarginTransferType) -> Result<TransactionId>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let transfer: Transfer = Transfer {
             asset: symbol.into(),
@@ -71,7 +74,8 @@ impl Margin {
     /// ```rust,no_run
     /// use binance::{api::*, margin::*, config::*, rest_model::*};
     /// let margin: Margin = Binance::new_with_env(&Config::testnet());
-    /// let transaction_id = tokio_test::block_on(margin.isolated_transfer("BTC", "BTC", 0.001, IsolatedMarginTransferType::Spot, IsolatedMarginTransferType::IsolatedMargin));
+    /// let qty: Number = "0.001".parse().unwrap();
+    /// let transaction_id = tokio_test::block_on(margin.isolated_transfer("BTC", "BTC", qty, IsolatedMarginTransferType::Spot, IsolatedMarginTransferType::IsolatedMargin));
     /// assert!(transaction_id.is_ok(), "{:?}", transaction_id);
     /// ```
     pub async fn isolated_transfer<S, F>(
@@ -84,7 +88,7 @@ impl Margin {
     ) -> Result<TransactionId>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let transfer = IsolatedTransfer {
             asset: asset_symbol.into(),
@@ -103,13 +107,14 @@ impl Margin {
     /// ```rust,no_run
     /// use binance::{api::*, margin::*, config::*, rest_model::*};
     /// let margin: Margin = Binance::new_with_env(&Config::testnet());
-    /// let transaction_id = tokio_test::block_on(margin.loan("BTCUSDT", 0.001));
+    /// let qty: Number = "0.001".parse().unwrap();
+    /// let transaction_id = tokio_test::block_on(margin.loan("BTCUSDT", qty));
     /// assert!(transaction_id.is_ok(), "{:?}", transaction_id);
     /// ```
     pub async fn loan<S, F>(&self, symbol: S, qty: F) -> Result<TransactionId>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         self.loan_with_isolation(symbol, qty, None, None).await
     }
@@ -119,7 +124,8 @@ impl Margin {
     /// ```rust,no_run
     /// use binance::{api::*, margin::*, config::*, rest_model::*};
     /// let margin: Margin = Binance::new_with_env(&Config::testnet());
-    /// let transaction_id = tokio_test::block_on(margin.loan_with_isolation("BTCUSDT", 0.001, Some(true), Some("BNB".to_string())));
+    /// let qty: Number = "0.001".parse().unwrap();
+    /// let transaction_id = tokio_test::block_on(margin.loan_with_isolation("BTCUSDT", qty, Some(true), Some("BNB".to_string())));
     /// assert!(transaction_id.is_ok(), "{:?}", transaction_id);
     /// ```
     pub async fn loan_with_isolation<S, F>(
@@ -131,7 +137,7 @@ impl Margin {
     ) -> Result<TransactionId>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let loan: Loan = Loan {
             asset: symbol.into(),
@@ -148,13 +154,14 @@ impl Margin {
     /// ```rust,no_run
     /// use binance::{api::*, margin::*, config::*, rest_model::*};
     /// let margin: Margin = Binance::new_with_env(&Config::testnet());
-    /// let transaction_id = tokio_test::block_on(margin.repay("BTCUSDT", 0.001));
+    /// let qty: Number = "0.001".parse().unwrap();
+    /// let transaction_id = tokio_test::block_on(margin.repay("BTCUSDT", qty));
     /// assert!(transaction_id.is_ok(), "{:?}", transaction_id);
     /// ```
     pub async fn repay<S, F>(&self, symbol: S, qty: F) -> Result<TransactionId>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         self.repay_with_isolation(symbol, qty, None, None).await
     }
@@ -164,7 +171,8 @@ impl Margin {
     /// ```rust,no_run
     /// use binance::{api::*, margin::*, config::*, rest_model::*};
     /// let margin: Margin = Binance::new_with_env(&Config::testnet());
-    /// let transaction_id = tokio_test::block_on(margin.repay_with_isolation("BTCUSDT", 0.001, Some(true), Some("BNB".to_string())));
+    /// let qty: Number = "0.001".parse().unwrap();
+    /// let transaction_id = tokio_test::block_on(margin.repay_with_isolation("BTCUSDT", qty, Some(true), Some("BNB".to_string())));
     /// assert!(transaction_id.is_ok(), "{:?}", transaction_id);
     /// ```
     pub async fn repay_with_isolation<S, F>(
@@ -176,7 +184,7 @@ impl Margin {
     ) -> Result<TransactionId>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let loan: Loan = Loan {
             asset: symbol.into(),
@@ -198,12 +206,12 @@ impl Margin {
     ///     symbol: "BTCUSDT".to_string(),
     ///     side: OrderSide::Sell,
     ///     order_type: OrderType::Limit,
-    ///     quantity: Some(0.001),
+    ///     quantity: Some("0.001".parse().unwrap()),
     ///     quote_order_qty: None,
-    ///     price: Some(10.0),
-    ///     stop_price: Some(10.0),
+    ///     price: Some("10.0".parse().unwrap()),
+    ///     stop_price: Some("10.0".parse().unwrap()),
     ///     new_client_order_id: Some("my_id".to_string()),
-    ///     iceberg_qty: Some(10.0),
+    ///     iceberg_qty: Some("10.0".parse().unwrap()),
     ///     new_order_resp_type: OrderResponse::Ack,
     ///     time_in_force: Some(TimeInForce::FOK),
     ///     side_effect_type: SideEffectType::NoSideEffect,
@@ -227,12 +235,12 @@ impl Margin {
     ///     symbol: "BTCUSDT".to_string(),
     ///     side: OrderSide::Sell,
     ///     order_type: OrderType::Limit,
-    ///     quantity: Some(0.001),
+    ///     quantity: Some("0.001".parse().unwrap()),
     ///     quote_order_qty: None,
-    ///     price: Some(10.0),
-    ///     stop_price: Some(10.0),
+    ///     price: Some("10.0".parse().unwrap()),
+    ///     stop_price: Some("10.0".parse().unwrap()),
     ///     new_client_order_id: Some("my_id".to_string()),
-    ///     iceberg_qty: Some(10.0),
+    ///     iceberg_qty: Some("10.0".parse().unwrap()),
     ///     new_order_resp_type: OrderResponse::Ack,
     ///     time_in_force: Some(TimeInForce::FOK),
     ///     side_effect_type: SideEffectType::NoSideEffect,
@@ -253,9 +261,9 @@ impl Margin {
     /// let margin_order = MarginOCOOrder {
     ///     symbol: "BTCUSDT".to_string(),
     ///     side: OrderSide::Sell,
-    ///     quantity: 10.0,
-    ///     price: 10.0,
-    ///     stop_price: 1.0,
+    ///     quantity: "10.0".parse().unwrap(),
+    ///     price: "10.0".parse().unwrap(),
+    ///     stop_price: "1.0".parse().unwrap(),
     ///     ..MarginOCOOrder::default()
     /// };
     /// let transaction_id = tokio_test::block_on(margin.new_oco_order(margin_order));
@@ -775,6 +783,42 @@ impl Margin {
             .await
     }
 
+    /// Every order of the query created between `start_time` and `end_time` (inclusive, in ms), in time order
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, margin::*, config::*, rest_model::*};
+    /// use futures::TryStreamExt;
+    /// let margin: Margin = Binance::new_with_env(&Config::testnet());
+    /// let records_query = MarginOrdersQuery {
+    ///    symbol: "BTCUSDT".to_string(),
+    ///    ..MarginOrdersQuery::default()
+    /// };
+    /// let records = margin.orders_stream(records_query, 1_672_531_200_000, 1_675_209_600_000);
+    /// let records: Vec<_> = tokio_test::block_on(records.try_collect()).unwrap();
+    /// ```
+    pub fn orders_stream(
+        &self,
+        all_orders_query: MarginOrdersQuery,
+        start_time: u64,
+        end_time: u64,
+    ) -> BoxStream<'_, Result<MarginOrderState>> {
+        let paging = Paging {
+            max_window: Some(DAY_MS),
+            limit: u64::from(u8::MAX),
+            by_offset: false,
+        };
+        let fetch = move |page: Page| {
+            let query = MarginOrdersQuery {
+                start_time: Some(page.start_time),
+                end_time: Some(page.end_time),
+                limit: Some(page.limit as u8),
+                ..all_orders_query.clone()
+            };
+            self.orders(query)
+        };
+        paginate(paging, start_time, end_time, fetch, |o| o.time, |o| o.order_id)
+    }
+
     /// Get all trades
     /// # Examples
     /// ```rust,no_run
@@ -1,8 +1,9 @@
 use crate::client::*;
 use crate::errors::*;
+use crate::pagination::*;
 use crate::rest_model::*;
 use crate::util::*;
-use serde_json::Value;
+use futures::stream::BoxStream;
 // use std::collections::BTreeMap;
 
 static API_V3_DEPTH: &str = "/api/v3/depth";
@@ -197,34 +198,62 @@ impl Market {
         self.client.get_p(API_V3_AGG_TRADES, Some(&request)).await
     }
 
-    /// Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
+    /// Every aggregated trade between `start_time` and `end_time` (inclusive, in ms), in time order
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, market::*, config::*};
+    /// use futures::TryStreamExt;
+    /// let market: Market = Binance::new_with_env(&Config::default());
+    /// let agg_trades = market.agg_trades_stream("BNBETH", 1_672_531_200_000, 1_672_617_600_000);
+    /// let agg_trades: Vec<_> = tokio_test::block_on(agg_trades.try_collect()).unwrap();
+    /// ```
+    pub fn agg_trades_stream<S>(&self, symbol: S, start_time: u64, end_time: u64) -> BoxStream<'_, Result<AggTrade>>
+    where
+        S: Into<String>,
+    {
+        let paging = Paging {
+            max_window: Some(HOUR_MS),
+            limit: 1000,
+            by_offset: false,
+        };
+        let symbol = symbol.into();
+        let fetch = move |page: Page| {
+            let symbol = symbol.clone();
+            async move {
+                self.get_agg_trades(symbol, None, page.start_time, page.end_time, page.limit as u16)
+                    .await
+            }
+        };
+        paginate(paging, start_time, end_time, fetch, |t| t.time, |t| t.agg_id)
+    }
+
+    /// Returns up to 'limit' klines for given symbol and interval
     /// <https://github.com/binance-exchange/binance-official-api-docs/blob/master/rest-api.md#klinecandlestick-data>
     /// # Examples
     /// ```rust
-    /// use binance::{api::*, market::*, config::*};
+    /// use binance::{api::*, market::*, config::*, rest_model::KlineInterval};
     /// let conf = Config::default().set_rest_api_endpoint(DATA_REST_ENDPOINT);
     /// let market: Market = Binance::new_with_env(&conf);
-    /// let klines = tokio_test::block_on(market.get_klines("BTCUSDT", "1m", None, None, None));
+    /// let klines = tokio_test::block_on(market.get_klines("BTCUSDT", KlineInterval::OneMinute, None, None, None));
     /// assert!(klines.is_ok(), "{:?}", klines);
     /// ```
-    pub async fn get_klines<S1, S2, S3, S4, S5>(
+    pub async fn get_klines<S1, S2, S3, S4>(
         &self,
         symbol: S1,
-        interval: S2,
-        limit: S3,
-        start_time: S4,
-        end_time: S5,
-    ) -> Result<KlineSummaries>
+        interval: KlineInterval,
+        limit: S2,
+        start_time: S3,
+        end_time: S4,
+    ) -> Result<Vec<KlineSummary>>
     where
         S1: Into<String>,
-        S2: Into<String>,
-        S3: Into<Option<u16>>,
+        S2: Into<Option<u16>>,
+        S3: Into<Option<u64>>,
         S4: Into<Option<u64>>,
-        S5: Into<Option<u64>>,
     {
         let parameters = IntoIterator::into_iter([
             Some(("symbol", symbol.into())),
-            Some(("interval", interval.into())),
+            Some(("interval", interval.to_string())),
             limit.into().map(|l| ("limit", l.to_string())),
             start_time.into().map(|s| ("startTime", s.to_string())),
             end_time.into().map(|e| ("endTime", e.to_string())),
@@ -233,26 +262,6 @@ impl Market {
 
         let request = build_request(parameters);
 
-        let parsed_data: Vec<Vec<Value>> = self.client.get(API_V3_KLINES, Some(&request)).await?;
-
-        let klines = KlineSummaries::AllKlineSummaries(
-            parsed_data
-                .iter()
-                .map(|row| KlineSummary {
-                    open_time: to_i64(&row[0]),
-                    open: to_f64(&row[1]),
-                    high: to_f64(&row[2]),
-                    low: to_f64(&row[3]),
-                    close: to_f64(&row[4]),
-                    volume: to_f64(&row[5]),
-                    close_time: to_i64(&row[6]),
-                    quote_asset_volume: to_f64(&row[7]),
-                    number_of_trades: to_i64(&row[8]),
-                    taker_buy_base_asset_volume: to_f64(&row[9]),
-                    taker_buy_quote_asset_volume: to_f64(&row[10]),
-                })
-                .collect(),
-        );
-        Ok(klines)
+        self.client.get(API_V3_KLINES, Some(&request)).await
     }
 }
@@ -0,0 +1,476 @@
+//! Local Binance server for tests.
+//!
+//! [`MockServer`] emulates the REST and websocket endpoints on localhost: canned responses are served for
+//! mocked routes, signed requests are checked for a valid HMAC signature and a timestamp within `recvWindow`,
+//! and errors can be injected to exercise retries and error handling. Point clients at it with
+//! [`MockServer::config`], or with [`Config::set_rest_api_endpoint`] and [`MockServer::rest_endpoint`].
+//!
+//! Ping, server time and the spot exchange information of `test_data/exchangeInfo.json` are served out of the
+//! box, other routes have to be mocked.
+//!
+//! # Examples
+//! ```rust
+//! use binance::{account::*, api::*, errors::Error, mock::*};
+//!
+//! tokio_test::block_on(async {
+//!     let server = MockServer::start().await?;
+//!     server.set_credentials("api_key", "secret_key");
+//!     server.mock_signed(Method::GET, "/api/v3/openOrders", "[]");
+//!     server.inject_error("/api/v3/openOrders", MockError::TooManyRequests { retry_after: 1 });
+//!
+//!     let (api_key, secret_key) = (Some("api_key".to_string()), Some("secret_key".to_string()));
+//!     let account = Account::new_with_config(api_key, secret_key, &server.config());
+//!     assert!(matches!(account.get_open_orders("BTCUSDT").await, Err(Error::RateLimited { .. })));
+//!     assert!(account.get_open_orders("BTCUSDT").await?.is_empty());
+//!     assert_eq!(server.requests().len(), 2);
+//!     Ok::<(), Error>(())
+//! });
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use futures::{SinkExt, StreamExt};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server, StatusCode};
+pub use reqwest::Method;
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::handshake::server::{Request as WsRequest, Response as WsResponse};
+use tokio_tungstenite::tungstenite::Message;
+use url::form_urlencoded;
+
+use crate::config::Config;
+use crate::errors::*;
+use crate::signer::{HmacSigner, Signer};
+use crate::websockets::{STREAM_ENDPOINT, WS_ENDPOINT};
+
+static EXCHANGE_INFO: &str = include_str!("../test_data/exchangeInfo.json");
+static TIME_ENDPOINTS: &[&str] = &["/api/v3/time", "/fapi/v1/time"];
+const DEFAULT_RECV_WINDOW: u64 = 5000;
+
+/// An error returned instead of the response of the next request of a route
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MockError {
+    /// 429 with a `Retry-After` header, in seconds
+    TooManyRequests { retry_after: u64 },
+    /// -1021, the timestamp of the request is outside of `recvWindow`
+    TimestampOutsideRecvWindow,
+    /// 503
+    ServiceUnavailable,
+    /// Any error code, with a 400 status
+    Binance { code: i32, msg: String },
+}
+
+/// A request received by the server
+#[derive(Clone, Debug)]
+pub struct MockRequest {
+    pub method: Method,
+    pub path: String,
+    pub query: String,
+    pub body: String,
+    /// The `X-MBX-APIKEY` header
+    pub api_key: Option<String>,
+}
+
+impl MockRequest {
+    /// The value of a query or body parameter
+    pub fn param(&self, name: &str) -> Option<String> {
+        form_urlencoded::parse(self.query.as_bytes())
+            .chain(form_urlencoded::parse(self.body.as_bytes()))
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    }
+}
+
+struct Route {
+    body: String,
+    signed: bool,
+}
+
+#[derive(Default)]
+struct MockState {
+    routes: HashMap<(Method, String), Route>,
+    credentials: Option<(String, String)>,
+    errors: HashMap<String, VecDeque<MockError>>,
+    requests: Vec<MockRequest>,
+    streams: HashMap<String, Vec<String>>,
+}
+
+/// A local server emulating the spot and futures endpoints, stopped when dropped
+pub struct MockServer {
+    http_addr: SocketAddr,
+    ws_addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts the REST and websocket servers on ports chosen by the OS
+    pub async fn start() -> Result<MockServer> {
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let http = TcpListener::bind("127.0.0.1:0")?;
+        http.set_nonblocking(true)?;
+        let http_addr = http.local_addr()?;
+        let ws = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let ws_addr = ws.local_addr()?;
+
+        let http_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = http_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |request| handle(state.clone(), request))) }
+        });
+        let server = Server::from_tcp(http)
+            .map_err(|e| Error::Msg(format!("mock server: {e}")))?
+            .serve(make_service);
+        let http_task = tokio::spawn(async move {
+            let _ = server.await;
+        });
+
+        let ws_state = state.clone();
+        let ws_task = tokio::spawn(async move {
+            while let Ok((stream, _)) = ws.accept().await {
+                tokio::spawn(serve_websocket(ws_state.clone(), stream));
+            }
+        });
+
+        let server = MockServer {
+            http_addr,
+            ws_addr,
+            state,
+            tasks: vec![http_task, ws_task],
+        };
+        server.mock(Method::GET, "/api/v3/ping", "{}");
+        server.mock(Method::GET, "/fapi/v1/ping", "{}");
+        server.mock(Method::GET, "/api/v3/exchangeInfo", EXCHANGE_INFO);
+        Ok(server)
+    }
+
+    /// Base url of the REST endpoints, e.g. `http://127.0.0.1:40000`
+    pub fn rest_endpoint(&self) -> String { format!("http://{}", self.http_addr) }
+
+    /// Base url of the websocket endpoints, e.g. `ws://127.0.0.1:40001`
+    pub fn ws_endpoint(&self) -> String { format!("ws://{}", self.ws_addr) }
+
+    /// The default configuration with the spot and futures endpoints of this server
+    pub fn config(&self) -> Config {
+        Config::default()
+            .set_rest_api_endpoint(self.rest_endpoint())
+            .set_futures_rest_api_endpoint(self.rest_endpoint())
+            .set_ws_endpoint(self.ws_endpoint())
+            .set_futures_ws_endpoint(self.ws_endpoint())
+    }
+
+    /// Requires signed requests to be signed with `secret_key` and sent with `api_key`
+    pub fn set_credentials(&self, api_key: &str, secret_key: &str) {
+        self.state().credentials = Some((api_key.to_string(), secret_key.to_string()));
+    }
+
+    /// Serves `body` with a 200 status to requests of `path`
+    pub fn mock<B: Into<String>>(&self, method: Method, path: &str, body: B) {
+        self.route(method, path, body.into(), false);
+    }
+
+    /// Same as [`MockServer::mock`] for an endpoint that requires a signature
+    pub fn mock_signed<B: Into<String>>(&self, method: Method, path: &str, body: B) {
+        self.route(method, path, body.into(), true);
+    }
+
+    /// Fails the next request of `path` with `error`, errors injected several times fail as many requests
+    pub fn inject_error(&self, path: &str, error: MockError) {
+        self.state()
+            .errors
+            .entry(path.to_string())
+            .or_default()
+            .push_back(error);
+    }
+
+    /// Messages sent to websocket connections subscribed to `stream`, e.g. `btcusdt@trade`
+    pub fn mock_stream(&self, stream: &str, messages: Vec<String>) {
+        self.state().streams.insert(stream.to_string(), messages);
+    }
+
+    /// The REST requests received so far
+    pub fn requests(&self) -> Vec<MockRequest> { self.state().requests.clone() }
+
+    fn route(&self, method: Method, path: &str, body: String, signed: bool) {
+        self.state()
+            .routes
+            .insert((method, path.to_string()), Route { body, signed });
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> { self.state.lock().unwrap() }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+async fn handle(
+    state: Arc<Mutex<MockState>>,
+    request: Request<Body>,
+) -> std::result::Result<Response<Body>, Infallible> {
+    let (parts, body) = request.into_parts();
+    let body = hyper::body::to_bytes(body).await.unwrap_or_default();
+    let request = MockRequest {
+        method: parts.method,
+        path: parts.uri.path().to_string(),
+        query: parts.uri.query().unwrap_or_default().to_string(),
+        body: String::from_utf8_lossy(&body).into_owned(),
+        api_key: parts
+            .headers
+            .get("x-mbx-apikey")
+            .and_then(|key| key.to_str().ok())
+            .map(String::from),
+    };
+    Ok(state.lock().unwrap().respond(request))
+}
+
+impl MockState {
+    fn respond(&mut self, request: MockRequest) -> Response<Body> {
+        self.requests.push(request.clone());
+        if let Some(error) = self.errors.get_mut(&request.path).and_then(VecDeque::pop_front) {
+            return error_response(error);
+        }
+        let Some(route) = self.routes.get(&(request.method.clone(), request.path.clone())) else {
+            if request.method == Method::GET && TIME_ENDPOINTS.contains(&request.path.as_str()) {
+                let body = json!({ "serverTime": Utc::now().timestamp_millis() });
+                return response(StatusCode::OK, body.to_string());
+            }
+            return response(StatusCode::NOT_FOUND, String::new());
+        };
+        if route.signed || request.param("signature").is_some() {
+            if let Err(error) = self.verify(&request) {
+                return *error;
+            }
+        }
+        response(StatusCode::OK, route.body.clone())
+    }
+
+    /// Checks the api key, signature and timestamp of a signed request like Binance does
+    fn verify(&self, request: &MockRequest) -> std::result::Result<(), Box<Response<Body>>> {
+        let Some((payload, signature)) = request.query.rsplit_once("&signature=") else {
+            return Err(Box::new(binance_error(
+                StatusCode::BAD_REQUEST,
+                -1102,
+                "Mandatory parameter 'signature' was not sent, was empty/null, or malformed.",
+            )));
+        };
+        if let Some((api_key, secret_key)) = &self.credentials {
+            if request.api_key.as_ref() != Some(api_key) {
+                return Err(Box::new(binance_error(
+                    StatusCode::UNAUTHORIZED,
+                    -2015,
+                    "Invalid API-key, IP, or permissions for action.",
+                )));
+            }
+            let expected = HmacSigner::new(secret_key).sign(&format!("{payload}{}", request.body));
+            if expected.ok().as_deref() != Some(signature) {
+                return Err(Box::new(binance_error(
+                    StatusCode::BAD_REQUEST,
+                    -1022,
+                    "Signature for this request is not valid.",
+                )));
+            }
+        }
+        let Some(timestamp) = request.param("timestamp").and_then(|t| t.parse::<u64>().ok()) else {
+            return Err(Box::new(binance_error(
+                StatusCode::BAD_REQUEST,
+                -1102,
+                "Mandatory parameter 'timestamp' was not sent, was empty/null, or malformed.",
+            )));
+        };
+        let recv_window = request
+            .param("recvWindow")
+            .and_then(|w| w.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_RECV_WINDOW);
+        let now = Utc::now().timestamp_millis() as u64;
+        if timestamp > now + 1000 || now.saturating_sub(timestamp) > recv_window {
+            return Err(Box::new(error_response(MockError::TimestampOutsideRecvWindow)));
+        }
+        Ok(())
+    }
+}
+
+fn response(status: StatusCode, body: String) -> Response<Body> {
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response
+}
+
+fn binance_error(status: StatusCode, code: i32, msg: &str) -> Response<Body> {
+    response(status, json!({ "code": code, "msg": msg }).to_string())
+}
+
+fn error_response(error: MockError) -> Response<Body> {
+    match error {
+        MockError::TooManyRequests { retry_after } => {
+            let mut response = binance_error(
+                StatusCode::TOO_MANY_REQUESTS,
+                -1003,
+                "Too much request weight used; current limit is 6000 request weight per 1 MINUTE.",
+            );
+            response
+                .headers_mut()
+                .insert(hyper::header::RETRY_AFTER, retry_after.into());
+            response
+        }
+        MockError::TimestampOutsideRecvWindow => binance_error(
+            StatusCode::BAD_REQUEST,
+            -1021,
+            "Timestamp for this request is outside of the recvWindow.",
+        ),
+        MockError::ServiceUnavailable => response(StatusCode::SERVICE_UNAVAILABLE, String::new()),
+        MockError::Binance { code, msg } => binance_error(StatusCode::BAD_REQUEST, code, &msg),
+    }
+}
+
+/// Sends the mocked messages of the streams of the connection, `/ws/<stream>` or `/stream?streams=<a>/<b>`,
+/// and answers subscription requests
+async fn serve_websocket(state: Arc<Mutex<MockState>>, stream: TcpStream) {
+    let mut uri = None;
+    // The error response type is set by tungstenite
+    #[allow(clippy::result_large_err)]
+    let callback = |request: &WsRequest, response: WsResponse| {
+        uri = Some(request.uri().clone());
+        Ok(response)
+    };
+    let Ok(mut socket) = tokio_tungstenite::accept_hdr_async(stream, callback).await else {
+        return;
+    };
+    let Some(uri) = uri else {
+        return;
+    };
+    let (mut streams, combined) = match uri.path().trim_start_matches('/').split_once('/') {
+        Some((endpoint, name)) if endpoint == WS_ENDPOINT => (vec![name.to_string()], false),
+        _ if uri.path().trim_matches('/') == STREAM_ENDPOINT => {
+            let names = form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes())
+                .find(|(key, _)| key == "streams")
+                .map(|(_, names)| names.split('/').map(String::from).collect())
+                .unwrap_or_default();
+            (names, true)
+        }
+        _ => return,
+    };
+    for name in streams.clone() {
+        if send_stream(&state, &mut socket, &name, combined).await.is_err() {
+            return;
+        }
+    }
+    while let Some(Ok(message)) = socket.next().await {
+        let Message::Text(text) = message else {
+            continue;
+        };
+        let Ok(request) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+        let names: Vec<String> = request["params"]
+            .as_array()
+            .map(|names| names.iter().filter_map(|n| n.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        let result = match request["method"].as_str() {
+            Some("SUBSCRIBE") => {
+                streams.extend(names.iter().cloned());
+                Value::Null
+            }
+            Some("UNSUBSCRIBE") => {
+                streams.retain(|name| !names.contains(name));
+                Value::Null
+            }
+            Some("LIST_SUBSCRIPTIONS") => json!(streams),
+            _ => Value::Null,
+        };
+        let reply = json!({ "result": result, "id": request["id"] }).to_string();
+        if socket.send(Message::Text(reply)).await.is_err() {
+            return;
+        }
+        if request["method"] == "SUBSCRIBE" {
+            for name in &names {
+                if send_stream(&state, &mut socket, name, true).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+async fn send_stream<S>(state: &Mutex<MockState>, socket: &mut S, name: &str, combined: bool) -> Result<()>
+where
+    S: SinkExt<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+{
+    let messages = state.lock().unwrap().streams.get(name).cloned().unwrap_or_default();
+    for message in messages {
+        let message = if combined {
+            format!(r#"{{"stream":"{name}","data":{message}}}"#)
+        } else {
+            message
+        };
+        socket.send(Message::Text(message)).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::Account;
+    use crate::api::Binance;
+    use crate::general::General;
+    use crate::websockets::{trade_stream, WebsocketStream};
+    use crate::ws_model::{CombinedStreamEvent, WebsocketEvent};
+
+    #[tokio::test]
+    async fn serves_and_verifies() {
+        let server = MockServer::start().await.unwrap();
+        let general: General = Binance::new_with_config(None, None, &server.config());
+        assert!(general.ping().await.is_ok());
+        assert!(!general.exchange_info().await.unwrap().symbols.is_empty());
+
+        server.set_credentials("key", "secret");
+        server.mock_signed(Method::GET, "/api/v3/openOrders", "[]");
+        let account = Account::new_with_config(Some("key".into()), Some("secret".into()), &server.config());
+        assert!(account.get_open_orders("BTCUSDT").await.unwrap().is_empty());
+        let request = server.requests().pop().unwrap();
+        assert_eq!(request.param("symbol").as_deref(), Some("BTCUSDT"));
+
+        let forged = Account::new_with_config(Some("key".into()), Some("other".into()), &server.config());
+        let error = forged.get_open_orders("BTCUSDT").await.unwrap_err();
+        assert!(
+            matches!(error, Error::BinanceError { ref response } if response.code == -1022),
+            "{error:?}"
+        );
+
+        server.inject_error("/api/v3/openOrders", MockError::TimestampOutsideRecvWindow);
+        server.inject_error("/api/v3/openOrders", MockError::ServiceUnavailable);
+        let error = account.get_open_orders("BTCUSDT").await.unwrap_err();
+        assert!(
+            matches!(error, Error::BinanceError { ref response } if response.code == -1021),
+            "{error:?}"
+        );
+        let error = account.get_open_orders("BTCUSDT").await.unwrap_err();
+        assert!(matches!(error, Error::ServiceUnavailable), "{error:?}");
+    }
+
+    #[tokio::test]
+    async fn streams_messages() {
+        let server = MockServer::start().await.unwrap();
+        let trade = json!({
+            "e": "trade", "E": 1, "s": "BTCUSDT", "t": 7, "p": "0.1", "q": "1.0", "b": 1, "a": 2, "T": 1,
+            "m": true, "M": true
+        });
+        server.mock_stream("btcusdt@trade", vec![trade.to_string()]);
+        let mut events: WebsocketStream<'_, CombinedStreamEvent<WebsocketEvent>> =
+            WebsocketStream::new(server.config());
+        events.connect_multiple(vec![trade_stream("btcusdt")]).await.unwrap();
+        let event = events.next().await.unwrap().unwrap();
+        assert!(matches!(event.data, WebsocketEvent::Trade(trade) if trade.trade_id == 7));
+    }
+}
@@ -0,0 +1,450 @@
+//! Local order book maintained from diff depth streams.
+//!
+//! Events are buffered until a REST snapshot is applied, then checked for continuity using the
+//! update ids of each event. When a gap is detected the book is cleared and resynchronized.
+//!
+//! # Examples
+//! ```rust,no_run
+//! use binance::{api::*, market::*, orderbook::*, websockets::*, ws_model::*};
+//! use std::sync::atomic::AtomicBool;
+//!
+//! tokio_test::block_on(async {
+//!     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+//!     let mut web_socket: WebSockets<'_, WebsocketEvent> = WebSockets::new(move |event: WebsocketEvent| {
+//!         if let WebsocketEvent::DepthOrderBook(depth) = event {
+//!             tx.send(*depth).ok();
+//!         }
+//!         Ok(())
+//!     });
+//!     web_socket.connect(&diff_book_depth_stream("btcusdt", 100)).await.unwrap();
+//!     tokio::spawn(async move {
+//!         let market: Market = Binance::new(None, None);
+//!         let mut book = LocalOrderBook::new("BTCUSDT", SnapshotSource::Spot(market));
+//!         while let Some(depth) = rx.recv().await {
+//!             book.handle_update(depth.into()).await.unwrap();
+//!             println!("best bid: {:?}, best ask: {:?}", book.best_bid(), book.best_ask());
+//!         }
+//!     });
+//!     web_socket.event_loop(&AtomicBool::new(true)).await.unwrap();
+//! });
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::errors::*;
+use crate::market::Market;
+use crate::rest_model::{Asks, Bids, Number};
+
+/// Default depth of the REST snapshot
+pub const DEFAULT_SNAPSHOT_LIMIT: u16 = 1000;
+
+/// Default delay before fetching a new snapshot when the previous one was older than the buffered events
+pub const DEFAULT_RESYNC_DELAY: Duration = Duration::from_millis(500);
+
+/// Where the snapshot is fetched from, this also selects the sequencing rules
+#[derive(Clone)]
+pub enum SnapshotSource {
+    Spot(Market),
+    #[cfg(feature = "futures_api")]
+    Futures(crate::futures::market::FuturesMarket),
+}
+
+/// A diff depth event, from either the spot or the futures streams
+#[derive(Debug, Clone)]
+pub struct DepthUpdate {
+    pub first_update_id: u64,
+    pub final_update_id: u64,
+    /// Final update id of the previous event, only sent by futures streams
+    pub previous_final_update_id: Option<u64>,
+    pub bids: Vec<Bids>,
+    pub asks: Vec<Asks>,
+}
+
+impl From<crate::ws_model::DepthOrderBookEvent> for DepthUpdate {
+    fn from(event: crate::ws_model::DepthOrderBookEvent) -> Self {
+        Self {
+            first_update_id: event.first_update_id,
+            final_update_id: event.final_update_id,
+            previous_final_update_id: None,
+            bids: event.bids,
+            asks: event.asks,
+        }
+    }
+}
+
+#[cfg(feature = "futures_api")]
+impl From<crate::futures::ws_model::DepthOrderBookEvent> for DepthUpdate {
+    fn from(event: crate::futures::ws_model::DepthOrderBookEvent) -> Self {
+        Self {
+            first_update_id: event.first_update_id,
+            final_update_id: event.final_update_id,
+            previous_final_update_id: Some(event.previous_final_update_id),
+            bids: event.bids,
+            asks: event.asks,
+        }
+    }
+}
+
+/// Price level key, floats are ordered with `Number::total_cmp`
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Price(Number);
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Price {
+    #[cfg(not(feature = "decimal"))]
+    fn cmp(&self, other: &Self) -> Ordering { self.0.total_cmp(&other.0) }
+
+    #[cfg(feature = "decimal")]
+    fn cmp(&self, other: &Self) -> Ordering { self.0.cmp(&other.0) }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncState {
+    /// Waiting for a snapshot, events are buffered
+    Unsynced,
+    /// The snapshot was applied, waiting for the first event that overlaps it
+    Snapshot { last_update_id: u64 },
+    /// Events are applied as they come
+    Synced { last_update_id: u64 },
+}
+
+pub struct LocalOrderBook {
+    symbol: String,
+    source: SnapshotSource,
+    snapshot_limit: u16,
+    state: SyncState,
+    resync_delay: Duration,
+    /// When the last snapshot was discarded for being older than the buffered events
+    stale_snapshot: Option<Instant>,
+    buffer: VecDeque<DepthUpdate>,
+    bids: BTreeMap<Price, Number>,
+    asks: BTreeMap<Price, Number>,
+}
+
+impl LocalOrderBook {
+    pub fn new<S: Into<String>>(symbol: S, source: SnapshotSource) -> Self {
+        Self {
+            symbol: symbol.into(),
+            source,
+            snapshot_limit: DEFAULT_SNAPSHOT_LIMIT,
+            state: SyncState::Unsynced,
+            resync_delay: DEFAULT_RESYNC_DELAY,
+            stale_snapshot: None,
+            buffer: VecDeque::new(),
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+        }
+    }
+
+    /// Set the depth of the REST snapshot, deeper snapshots cost more request weight
+    pub fn set_snapshot_limit(&mut self, limit: u16) { self.snapshot_limit = limit; }
+
+    /// Set how long to wait before replacing a snapshot that was older than the buffered events
+    pub fn set_resync_delay(&mut self, delay: Duration) { self.resync_delay = delay; }
+
+    pub fn symbol(&self) -> &str { &self.symbol }
+
+    /// Whether the book reflects the exchange state
+    pub fn is_synced(&self) -> bool { matches!(self.state, SyncState::Synced { .. }) }
+
+    /// Update id of the last applied event or snapshot
+    pub fn last_update_id(&self) -> Option<u64> {
+        match self.state {
+            SyncState::Unsynced => None,
+            SyncState::Snapshot { last_update_id } | SyncState::Synced { last_update_id } => Some(last_update_id),
+        }
+    }
+
+    fn is_futures(&self) -> bool {
+        match self.source {
+            SnapshotSource::Spot(_) => false,
+            #[cfg(feature = "futures_api")]
+            SnapshotSource::Futures(_) => true,
+        }
+    }
+
+    /// Apply or buffer an event, fetching a snapshot when the book is not synced
+    pub async fn handle_update(&mut self, update: DepthUpdate) -> Result<()> {
+        self.update(update);
+        if self.needs_snapshot() {
+            self.sync().await?;
+        }
+        Ok(())
+    }
+
+    /// Whether a snapshot should be fetched, a stale snapshot is only replaced after the resync delay
+    fn needs_snapshot(&self) -> bool {
+        self.state == SyncState::Unsynced
+            && self
+                .stale_snapshot
+                .is_none_or(|discarded_at| discarded_at.elapsed() >= self.resync_delay)
+    }
+
+    /// Apply or buffer an event, the book is reset if the event does not follow the previous one
+    pub fn update(&mut self, update: DepthUpdate) {
+        match self.state {
+            SyncState::Unsynced => self.buffer.push_back(update),
+            SyncState::Snapshot { last_update_id } => {
+                if self.is_stale(&update, last_update_id) {
+                    return;
+                }
+                if self.overlaps_snapshot(&update, last_update_id) {
+                    self.apply(update);
+                } else {
+                    self.reset(Some(update));
+                }
+            }
+            SyncState::Synced { last_update_id } => {
+                if self.follows(&update, last_update_id) {
+                    self.apply(update);
+                } else {
+                    self.reset(Some(update));
+                }
+            }
+        }
+    }
+
+    /// Fetch a snapshot and apply it along with the buffered events
+    pub async fn sync(&mut self) -> Result<()> {
+        let book = match self.source {
+            SnapshotSource::Spot(ref market) => market.get_custom_depth(&self.symbol, self.snapshot_limit).await?,
+            #[cfg(feature = "futures_api")]
+            SnapshotSource::Futures(ref market) => {
+                let book = market.get_custom_depth(&self.symbol, self.snapshot_limit).await?;
+                crate::rest_model::OrderBook {
+                    last_update_id: book.last_update_id,
+                    bids: book.bids,
+                    asks: book.asks,
+                }
+            }
+        };
+        self.apply_snapshot(book.last_update_id, book.bids, book.asks);
+        Ok(())
+    }
+
+    /// Replace the book with a snapshot and apply the buffered events that follow it.
+    /// The snapshot is discarded if it is older than the first buffered event, in which case
+    /// [`LocalOrderBook::handle_update`] waits for the resync delay before fetching another one.
+    pub fn apply_snapshot(&mut self, last_update_id: u64, bids: Vec<Bids>, asks: Vec<Asks>) {
+        let next = self.next_update_id(last_update_id);
+        if self.buffer.front().is_some_and(|first| first.first_update_id > next) {
+            self.stale_snapshot = Some(Instant::now());
+            return;
+        }
+        self.stale_snapshot = None;
+        self.bids = bids.into_iter().map(|b| (Price(b.price), b.qty)).collect();
+        self.asks = asks.into_iter().map(|a| (Price(a.price), a.qty)).collect();
+        self.state = SyncState::Snapshot { last_update_id };
+        let buffered: Vec<DepthUpdate> = self.buffer.drain(..).collect();
+        for update in buffered {
+            self.update(update);
+        }
+    }
+
+    /// Spot: drop events where `u` <= lastUpdateId, futures: drop events where `u` < lastUpdateId
+    fn is_stale(&self, update: &DepthUpdate, last_update_id: u64) -> bool {
+        if self.is_futures() {
+            update.final_update_id < last_update_id
+        } else {
+            update.final_update_id <= last_update_id
+        }
+    }
+
+    /// Update id the first event applied on top of a snapshot has to contain
+    fn next_update_id(&self, last_update_id: u64) -> u64 {
+        if self.is_futures() {
+            last_update_id
+        } else {
+            last_update_id + 1
+        }
+    }
+
+    /// Spot: `U` <= lastUpdateId + 1 <= `u`, futures: `U` <= lastUpdateId <= `u`
+    fn overlaps_snapshot(&self, update: &DepthUpdate, last_update_id: u64) -> bool {
+        let next = self.next_update_id(last_update_id);
+        update.first_update_id <= next && update.final_update_id >= next
+    }
+
+    /// Spot: `U` is the previous `u` + 1, futures: `pu` is the previous `u`
+    fn follows(&self, update: &DepthUpdate, last_update_id: u64) -> bool {
+        match update.previous_final_update_id {
+            Some(previous) if self.is_futures() => previous == last_update_id,
+            _ => update.first_update_id == last_update_id + 1,
+        }
+    }
+
+    fn apply(&mut self, update: DepthUpdate) {
+        for bid in update.bids {
+            Self::set_level(&mut self.bids, bid.price, bid.qty);
+        }
+        for ask in update.asks {
+            Self::set_level(&mut self.asks, ask.price, ask.qty);
+        }
+        self.state = SyncState::Synced {
+            last_update_id: update.final_update_id,
+        };
+    }
+
+    fn set_level(levels: &mut BTreeMap<Price, Number>, price: Number, qty: Number) {
+        if qty == Number::default() {
+            levels.remove(&Price(price));
+        } else {
+            levels.insert(Price(price), qty);
+        }
+    }
+
+    /// Clear the book, the next call to [`LocalOrderBook::sync`] starts over from a new snapshot
+    pub fn reset(&mut self, pending: Option<DepthUpdate>) {
+        self.state = SyncState::Unsynced;
+        self.stale_snapshot = None;
+        self.bids.clear();
+        self.asks.clear();
+        self.buffer.clear();
+        self.buffer.extend(pending);
+    }
+
+    pub fn best_bid(&self) -> Option<Bids> {
+        self.bids.iter().next_back().map(|(price, qty)| Bids {
+            price: price.0,
+            qty: *qty,
+        })
+    }
+
+    pub fn best_ask(&self) -> Option<Asks> {
+        self.asks.iter().next().map(|(price, qty)| Asks {
+            price: price.0,
+            qty: *qty,
+        })
+    }
+
+    /// The `n` best bids, highest price first
+    pub fn bids(&self, n: usize) -> Vec<Bids> {
+        self.bids
+            .iter()
+            .rev()
+            .take(n)
+            .map(|(price, qty)| Bids {
+                price: price.0,
+                qty: *qty,
+            })
+            .collect()
+    }
+
+    /// The `n` best asks, lowest price first
+    pub fn asks(&self, n: usize) -> Vec<Asks> {
+        self.asks
+            .iter()
+            .take(n)
+            .map(|(price, qty)| Asks {
+                price: price.0,
+                qty: *qty,
+            })
+            .collect()
+    }
+
+    /// Quantity bid at `price`, 0 if there is no such level
+    pub fn bid_qty(&self, price: Number) -> Number { self.bids.get(&Price(price)).copied().unwrap_or_default() }
+
+    /// Quantity asked at `price`, 0 if there is no such level
+    pub fn ask_qty(&self, price: Number) -> Number { self.asks.get(&Price(price)).copied().unwrap_or_default() }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::Binance;
+    use crate::util::num;
+
+    fn update(first: u64, last: u64, previous: Option<u64>, bids: &[(f64, f64)], asks: &[(f64, f64)]) -> DepthUpdate {
+        DepthUpdate {
+            first_update_id: first,
+            final_update_id: last,
+            previous_final_update_id: previous,
+            bids: bids
+                .iter()
+                .map(|&(price, qty)| Bids {
+                    price: num(price),
+                    qty: num(qty),
+                })
+                .collect(),
+            asks: asks
+                .iter()
+                .map(|&(price, qty)| Asks {
+                    price: num(price),
+                    qty: num(qty),
+                })
+                .collect(),
+        }
+    }
+
+    fn spot_book() -> LocalOrderBook { LocalOrderBook::new("BTCUSDT", SnapshotSource::Spot(Market::new(None, None))) }
+
+    #[test]
+    fn spot_sequencing() {
+        let mut book = spot_book();
+        book.update(update(90, 95, None, &[(1.0, 1.0)], &[]));
+        book.update(update(96, 102, None, &[(1.0, 2.0), (2.0, 1.0)], &[(3.0, 1.0)]));
+        book.update(update(103, 105, None, &[], &[(3.0, 0.0), (4.0, 1.0)]));
+        let bids = vec![Bids {
+            price: num(1.0),
+            qty: num(5.0),
+        }];
+        let asks = vec![Asks {
+            price: num(3.0),
+            qty: num(5.0),
+        }];
+        book.apply_snapshot(100, bids, asks);
+
+        assert!(book.is_synced());
+        assert_eq!(book.last_update_id(), Some(105));
+        assert_eq!(book.best_bid().map(|b| b.price), Some(num(2.0)));
+        assert_eq!(book.best_ask().map(|a| (a.price, a.qty)), Some((num(4.0), num(1.0))));
+        assert_eq!(book.bid_qty(num(1.0)), num(2.0));
+        assert_eq!(book.bids(5).len(), 2);
+
+        // gap between 105 and 107
+        book.update(update(107, 110, None, &[], &[]));
+        assert!(!book.is_synced());
+        assert!(book.best_bid().is_none());
+    }
+
+    #[test]
+    fn spot_stale_snapshot() {
+        let mut book = spot_book();
+        book.update(update(120, 125, None, &[], &[]));
+        assert!(book.needs_snapshot());
+        book.apply_snapshot(100, vec![], vec![]);
+        assert_eq!(book.last_update_id(), None);
+        // the next snapshot waits for the resync delay
+        book.update(update(126, 130, None, &[], &[]));
+        assert!(!book.needs_snapshot());
+        book.set_resync_delay(Duration::ZERO);
+        assert!(book.needs_snapshot());
+        book.apply_snapshot(121, vec![], vec![]);
+        assert_eq!(book.last_update_id(), Some(130));
+        assert!(!book.needs_snapshot());
+    }
+
+    #[cfg(feature = "futures_api")]
+    #[test]
+    fn futures_sequencing() {
+        let market = crate::futures::market::FuturesMarket::new(None, None);
+        let mut book = LocalOrderBook::new("BTCUSDT", SnapshotSource::Futures(market));
+        book.update(update(90, 99, Some(89), &[], &[]));
+        book.update(update(100, 104, Some(99), &[(1.0, 1.0)], &[]));
+        book.update(update(105, 108, Some(104), &[(1.0, 3.0)], &[]));
+        book.apply_snapshot(100, vec![], vec![]);
+        assert!(book.is_synced());
+        assert_eq!(book.bid_qty(num(1.0)), num(3.0));
+
+        book.update(update(112, 115, Some(110), &[], &[]));
+        assert!(!book.is_synced());
+    }
+}
@@ -0,0 +1,214 @@
+//! Streams walking history endpoints over a time range.
+//!
+//! History endpoints return a bounded number of records per request and often restrict the time range of a
+//! request. The range is split in windows of the maximum span of the endpoint, and each window is walked page
+//! by page, either moving the start time to the last record or with an offset for endpoints that support it.
+//! Records of the millisecond a page ends on are requested again and de-duplicated. Endpoints walked by offset
+//! are not in time order, so their windows are collected, sorted and de-duplicated before being yielded.
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::hash::Hash;
+
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+
+use crate::errors::*;
+
+pub(crate) const HOUR_MS: u64 = 3_600_000;
+pub(crate) const DAY_MS: u64 = 24 * HOUR_MS;
+
+/// A page requested from an endpoint, start and end times are inclusive
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Page {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub limit: u64,
+    /// Only used by `Paging::by_offset` endpoints
+    pub offset: u64,
+}
+
+/// Pagination rules of an endpoint
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Paging {
+    /// Maximum span of the time range of a request
+    pub max_window: Option<u64>,
+    /// Maximum number of records of a page
+    pub limit: u64,
+    /// Walk the pages of a window with an offset rather than by time
+    pub by_offset: bool,
+}
+
+struct State<F, K, T> {
+    fetch: F,
+    cursor: u64,
+    end_time: u64,
+    offset: u64,
+    /// Keys of the records of the current cursor millisecond already yielded
+    seen: HashSet<K>,
+    /// Records of the pages of the current window, when walking by offset
+    window: Vec<T>,
+}
+
+/// Every record between `start_time` and `end_time` (inclusive) in time order
+pub(crate) fn paginate<'a, T, K, F, Fut>(
+    paging: Paging,
+    start_time: u64,
+    end_time: u64,
+    fetch: F,
+    time_of: fn(&T) -> u64,
+    key_of: fn(&T) -> K,
+) -> BoxStream<'a, Result<T>>
+where
+    T: Send + 'a,
+    K: Eq + Hash + Send + 'a,
+    F: Fn(Page) -> Fut + Send + 'a,
+    Fut: Future<Output = Result<Vec<T>>> + Send + 'a,
+{
+    let state = State {
+        fetch,
+        cursor: start_time,
+        end_time,
+        offset: 0,
+        seen: HashSet::new(),
+        window: Vec::new(),
+    };
+    let pages = stream::unfold(Some(state), move |state| async move {
+        let mut state = state?;
+        loop {
+            if state.cursor > state.end_time {
+                return None;
+            }
+            let window_end = match paging.max_window {
+                Some(window) => state.cursor.saturating_add(window - 1).min(state.end_time),
+                None => state.end_time,
+            };
+            let page = Page {
+                start_time: state.cursor,
+                end_time: window_end,
+                limit: paging.limit,
+                offset: state.offset,
+            };
+            let mut records = match (state.fetch)(page).await {
+                Ok(records) => records,
+                // The stream ends after an error
+                Err(e) => return Some((Err(e), None)),
+            };
+            let full = records.len() as u64 >= paging.limit;
+            if paging.by_offset {
+                if full {
+                    state.offset += records.len() as u64;
+                    state.window.append(&mut records);
+                    continue;
+                }
+                state.offset = 0;
+                state.cursor = window_end.saturating_add(1);
+                // Records shift between pages when new ones come in, so a record can be served twice
+                let mut seen = HashSet::new();
+                records = std::mem::take(&mut state.window).into_iter().chain(records).collect();
+                records.retain(|record| seen.insert(key_of(record)));
+                records.sort_by_key(time_of);
+            } else {
+                records.sort_by_key(time_of);
+                let cursor = state.cursor;
+                let seen = std::mem::take(&mut state.seen);
+                records.retain(|record| time_of(record) != cursor || !seen.contains(&key_of(record)));
+                match records.last().map(time_of) {
+                    // More records in the window, continue from the millisecond of the last record
+                    Some(last_time) if full => {
+                        if last_time == cursor {
+                            state.seen = seen;
+                        }
+                        state
+                            .seen
+                            .extend(records.iter().filter(|r| time_of(r) == last_time).map(key_of));
+                        state.cursor = last_time;
+                    }
+                    // A page of already yielded records of a single millisecond, that cannot be walked by time
+                    None if full => state.cursor = cursor.saturating_add(1),
+                    _ => state.cursor = window_end.saturating_add(1),
+                }
+            }
+            if !records.is_empty() {
+                return Some((Ok(records), Some(state)));
+            }
+        }
+    });
+    pages
+        .map_ok(|records| stream::iter(records.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Records as (time, id), served in time order like the time based endpoints
+    fn serve(records: &[(u64, u64)], page: Page) -> Vec<(u64, u64)> {
+        records
+            .iter()
+            .filter(|(time, _)| (page.start_time..=page.end_time).contains(time))
+            .take(page.limit as usize)
+            .copied()
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn walks_windows_and_pages() {
+        let records: Vec<(u64, u64)> = vec![(1, 1), (2, 2), (2, 3), (2, 4), (3, 5), (12, 6), (25, 7), (26, 8)];
+        let pages = Mutex::new(vec![]);
+        let paging = Paging {
+            max_window: Some(10),
+            limit: 3,
+            by_offset: false,
+        };
+        let fetch = |page: Page| {
+            pages.lock().unwrap().push(page);
+            let records = serve(&records, page);
+            async move { Ok(records) }
+        };
+        let walked: Vec<(u64, u64)> = paginate(paging, 0, 25, fetch, |r| r.0, |r| r.1)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(walked, vec![(1, 1), (2, 2), (2, 3), (2, 4), (3, 5), (12, 6), (25, 7)]);
+        let starts: Vec<u64> = pages.lock().unwrap().iter().map(|p| p.start_time).collect();
+        assert_eq!(starts, vec![0, 2, 2, 3, 13, 23]);
+    }
+
+    #[tokio::test]
+    async fn walks_offsets() {
+        // Records as (time, id), served newest first, a record comes in after the first page
+        let records: Mutex<Vec<(u64, u64)>> = Mutex::new((0..7).map(|i| (i * 2, i)).collect());
+        let paging = Paging {
+            max_window: Some(10),
+            limit: 3,
+            by_offset: true,
+        };
+        let fetch = |page: Page| {
+            let mut records = records.lock().unwrap();
+            let served: Vec<(u64, u64)> = records
+                .iter()
+                .rev()
+                .filter(|(time, _)| (page.start_time..=page.end_time).contains(time))
+                .skip(page.offset as usize)
+                .take(page.limit as usize)
+                .copied()
+                .collect();
+            if page.start_time == 0 && page.offset == 0 {
+                records.push((9, 7));
+                records.sort();
+            }
+            async move { Ok(served) }
+        };
+        let walked: Vec<(u64, u64)> = paginate(paging, 0, 100, fetch, |r| r.0, |r| r.1)
+            .try_collect()
+            .await
+            .unwrap();
+        // (4, 2) is served twice, the new record is ahead of the offset
+        let expected = vec![(0, 0), (2, 1), (4, 2), (6, 3), (8, 4), (10, 5), (12, 6)];
+        assert_eq!(walked, expected);
+    }
+}
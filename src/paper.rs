@@ -0,0 +1,1034 @@
+//! Paper trading against live market data.
+//!
+//! A [`PaperAccount`] simulates the order operations of [`Account`] on local balances, without sending orders.
+//! Orders are matched against the market data it is fed, usually the trade and book ticker streams:
+//! * market orders, and limit orders crossing the book when placed, fill entirely as taker at the best bid or
+//!   ask, or at the last trade price without a book ticker,
+//! * resting limit orders fill as maker at their price when the market trades at or through it, for up to the
+//!   quantity of the trade or of the top of the book, ignoring their position in the queue,
+//! * stop loss and take profit orders are triggered by the last trade price.
+//!
+//! Funds are locked when orders are placed, stop loss and take profit market orders lock the quote asset of
+//! buys at the stop price. Commissions are charged in the asset received. The changes of orders and balances
+//! are broadcast as the `OrderUpdate` and `AccountPositionUpdate` events of the user stream, so that a strategy
+//! written against [`Trading`] and these events runs unchanged on the exchange.
+//!
+//! # Examples
+//! ```rust,no_run
+//! use binance::{account::*, api::*, config::*, general::*, paper::*, rest_model::*, websockets::*, ws_model::*};
+//! use futures::StreamExt;
+//! use std::time::Duration;
+//!
+//! tokio_test::block_on(async {
+//!     let general: General = Binance::new(None, None);
+//!     let symbols = general.exchange_info().await?.symbols;
+//!     let account = PaperAccount::new(symbols, [("USDT", "1000".parse().unwrap())])
+//!         .with_fees("0.001".parse().unwrap(), "0.001".parse().unwrap())
+//!         .with_latency(Duration::from_millis(20));
+//!     let mut user_events = account.subscribe();
+//!
+//!     let mut market_data: WebsocketStream<'_, CombinedStreamEvent<WebsocketEventUntag>> =
+//!         WebsocketStream::new(Config::default());
+//!     market_data
+//!         .connect_multiple(vec![trade_stream("btcusdt"), book_ticker_stream("btcusdt")])
+//!         .await?;
+//!     let order = OrderRequest {
+//!         symbol: "BTCUSDT".to_string(),
+//!         side: OrderSide::Buy,
+//!         order_type: OrderType::Limit,
+//!         time_in_force: Some(TimeInForce::GTC),
+//!         quantity: Some("0.001".parse().unwrap()),
+//!         price: Some("20000".parse().unwrap()),
+//!         ..OrderRequest::default()
+//!     };
+//!     account.place_order(order).await?;
+//!     while let Some(event) = market_data.next().await {
+//!         account.on_event(&event?.data);
+//!         while let Ok(event) = user_events.try_recv() {
+//!             println!("{event:?}");
+//!         }
+//!     }
+//!     Ok::<(), binance::errors::Error>(())
+//! });
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::Utc;
+use futures::future::BoxFuture;
+use tokio::sync::broadcast;
+
+use crate::account::{Account, CancelReplaceRequest, OrderCancellation, OrderRequest};
+use crate::errors::*;
+use crate::filters::FilterContext;
+use crate::rest_model::{AccountInformation, AccountType, Balance, CancelReplaceMode, Fill, Number, Order,
+                        OrderCanceled, OrderCanceledReplaced, OrderSide, OrderStatus, OrderType, Symbol, TimeInForce,
+                        Transaction};
+use crate::ws_model::{AccountPositionUpdate, BookTickerEvent, EventBalance, OrderUpdate, WebsocketEvent,
+                      WebsocketEventUntag, WsNumber};
+
+const EVENT_CAPACITY: usize = 1024;
+
+/// The order operations of a spot account, on the exchange or simulated
+pub trait Trading: Send + Sync {
+    fn place_order(&self, order: OrderRequest) -> BoxFuture<'_, Result<Transaction>>;
+
+    fn cancel_order(&self, cancellation: OrderCancellation) -> BoxFuture<'_, Result<OrderCanceled>>;
+
+    fn cancel_replace_order(&self, order: CancelReplaceRequest) -> BoxFuture<'_, Result<OrderCanceledReplaced>>;
+
+    fn get_open_orders<'a>(&'a self, symbol: &'a str) -> BoxFuture<'a, Result<Vec<Order>>>;
+
+    fn get_account(&self) -> BoxFuture<'_, Result<AccountInformation>>;
+}
+
+impl Trading for Account {
+    fn place_order(&self, order: OrderRequest) -> BoxFuture<'_, Result<Transaction>> {
+        Box::pin(Account::place_order(self, order))
+    }
+
+    fn cancel_order(&self, cancellation: OrderCancellation) -> BoxFuture<'_, Result<OrderCanceled>> {
+        Box::pin(Account::cancel_order(self, cancellation))
+    }
+
+    fn cancel_replace_order(&self, order: CancelReplaceRequest) -> BoxFuture<'_, Result<OrderCanceledReplaced>> {
+        Box::pin(Account::cancel_replace_order(self, order))
+    }
+
+    fn get_open_orders<'a>(&'a self, symbol: &'a str) -> BoxFuture<'a, Result<Vec<Order>>> {
+        Box::pin(Account::get_open_orders(self, symbol))
+    }
+
+    fn get_account(&self) -> BoxFuture<'_, Result<AccountInformation>> { Box::pin(Account::get_account(self)) }
+}
+
+/// A simulated spot account filling orders against market data
+pub struct PaperAccount {
+    state: Mutex<PaperState>,
+    events: broadcast::Sender<WebsocketEvent>,
+    latency: Duration,
+}
+
+impl PaperAccount {
+    /// An account trading `symbols`, e.g. from `General::exchange_info`, with free `balances` and no fees
+    pub fn new<S: Into<String>>(symbols: Vec<Symbol>, balances: impl IntoIterator<Item = (S, Number)>) -> Self {
+        let balances = balances
+            .into_iter()
+            .map(|(asset, free)| {
+                let asset = asset.into();
+                let balance = Balance {
+                    asset: asset.clone(),
+                    free,
+                    locked: Number::default(),
+                };
+                (asset, balance)
+            })
+            .collect();
+        let state = PaperState {
+            symbols: symbols
+                .into_iter()
+                .map(|symbol| (symbol.symbol.clone(), symbol))
+                .collect(),
+            balances,
+            ..PaperState::default()
+        };
+        PaperAccount {
+            state: Mutex::new(state),
+            events: broadcast::channel(EVENT_CAPACITY).0,
+            latency: Duration::ZERO,
+        }
+    }
+
+    /// Commission rates of maker and taker fills, e.g. 0.001 for 0.1%
+    pub fn with_fees(mut self, maker: Number, taker: Number) -> Self {
+        self.state.get_mut().unwrap().fees = Fees { maker, taker };
+        self
+    }
+
+    /// Delay of the order operations, market data received meanwhile is matched before the operation
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// The `OrderUpdate` and `AccountPositionUpdate` events of the account, from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<WebsocketEvent> { self.events.subscribe() }
+
+    /// Matches orders against trades and book tickers, other events are ignored
+    pub fn on_event(&self, event: &WebsocketEventUntag) {
+        match event {
+            WebsocketEventUntag::WebsocketEvent(WebsocketEvent::Trade(trade)) => {
+                self.on_trade_event(&trade.symbol, &trade.price, &trade.qty)
+            }
+            WebsocketEventUntag::WebsocketEvent(WebsocketEvent::AggTrade(trade)) => {
+                self.on_trade_event(&trade.symbol, &trade.price, &trade.qty)
+            }
+            WebsocketEventUntag::BookTicker(ticker) => self.on_book_ticker(ticker),
+            _ => {}
+        }
+    }
+
+    /// Triggers stop orders and fills resting limit orders of `symbol` traded through by a trade
+    pub fn on_trade(&self, symbol: &str, price: Number, qty: Number) {
+        self.update(|state, events| state.on_trade(symbol, price, qty, events))
+    }
+
+    /// Trades of websocket events, ignored when their price or quantity is not a number
+    fn on_trade_event(&self, symbol: &str, price: &WsNumber, qty: &WsNumber) {
+        if let (Some(price), Some(qty)) = (ws_number(price), ws_number(qty)) {
+            self.on_trade(symbol, price, qty)
+        }
+    }
+
+    /// Fills resting limit orders crossed by the best bid or ask
+    pub fn on_book_ticker(&self, ticker: &BookTickerEvent) {
+        self.update(|state, events| state.on_book_ticker(ticker, events))
+    }
+
+    pub async fn place_order(&self, order: OrderRequest) -> Result<Transaction> {
+        order.valid()?;
+        self.delay().await;
+        self.update(|state, events| state.place(order, events))
+    }
+
+    pub async fn cancel_order(&self, cancellation: OrderCancellation) -> Result<OrderCanceled> {
+        self.delay().await;
+        self.update(|state, events| {
+            state.cancel(
+                &cancellation.symbol,
+                cancellation.order_id,
+                cancellation.orig_client_order_id,
+                cancellation.new_client_order_id,
+                events,
+            )
+        })
+    }
+
+    /// Cancels an order and places a new one, failing with -2022 when both fail and with -2021 when one does
+    pub async fn cancel_replace_order(&self, order: CancelReplaceRequest) -> Result<OrderCanceledReplaced> {
+        order.valid()?;
+        self.delay().await;
+        self.update(|state, events| {
+            let canceled = state.cancel(
+                &order.symbol,
+                order.cancel_order_id,
+                order.cancel_orig_client_order_id,
+                order.cancel_new_client_order_id,
+                events,
+            );
+            if canceled.is_err() && matches!(order.cancel_replace_mode, CancelReplaceMode::StopOnFailure) {
+                return Err(binance_error(-2022, "Order cancel-replace failed."));
+            }
+            let placed = state.place(
+                OrderRequest {
+                    symbol: order.symbol,
+                    side: order.side,
+                    order_type: order.order_type,
+                    time_in_force: order.time_in_force,
+                    quantity: order.quantity,
+                    quote_order_qty: order.quote_order_qty,
+                    price: order.price,
+                    new_client_order_id: order.new_client_order_id,
+                    stop_price: order.stop_price,
+                    iceberg_qty: order.iceberg_qty,
+                    new_order_resp_type: order.new_order_resp_type,
+                    recv_window: order.recv_window,
+                },
+                events,
+            );
+            match (canceled, placed) {
+                (Ok(cancel_response), Ok(new_order_response)) => Ok(OrderCanceledReplaced {
+                    cancel_result: "SUCCESS".to_string(),
+                    new_order_result: "SUCCESS".to_string(),
+                    cancel_response,
+                    new_order_response,
+                }),
+                (Err(_), Err(_)) => Err(binance_error(-2022, "Order cancel-replace failed.")),
+                _ => Err(binance_error(-2021, "Order cancel-replace partially failed.")),
+            }
+        })
+    }
+
+    pub async fn get_open_orders<S>(&self, symbol: S) -> Result<Vec<Order>>
+    where
+        S: AsRef<str>,
+    {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .orders
+            .iter()
+            .filter(|open| open.order.symbol == symbol.as_ref())
+            .map(|open| open.order.clone())
+            .collect())
+    }
+
+    pub async fn get_account(&self) -> Result<AccountInformation> {
+        let state = self.state.lock().unwrap();
+        // Rates are in bips
+        let bips = |rate: Number| {
+            (rate * Number::from(10_000_u32))
+                .to_string()
+                .parse()
+                .unwrap_or_default()
+        };
+        Ok(AccountInformation {
+            maker_commission: bips(state.fees.maker),
+            taker_commission: bips(state.fees.taker),
+            buyer_commission: 0.0,
+            seller_commission: 0.0,
+            can_trade: true,
+            can_withdraw: false,
+            can_deposit: false,
+            account_type: AccountType::Spot,
+            balances: state.balances.values().cloned().collect(),
+            permissions: vec![AccountType::Spot],
+            update_time: Utc::now().timestamp_millis(),
+        })
+    }
+
+    async fn delay(&self) {
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+    }
+
+    /// Updates the state, then broadcasts the events of the update
+    fn update<T>(&self, f: impl FnOnce(&mut PaperState, &mut Vec<WebsocketEvent>) -> T) -> T {
+        let mut events = vec![];
+        let result = f(&mut self.state.lock().unwrap(), &mut events);
+        for event in events {
+            // Nobody listening
+            let _ = self.events.send(event);
+        }
+        result
+    }
+}
+
+impl Trading for PaperAccount {
+    fn place_order(&self, order: OrderRequest) -> BoxFuture<'_, Result<Transaction>> {
+        Box::pin(PaperAccount::place_order(self, order))
+    }
+
+    fn cancel_order(&self, cancellation: OrderCancellation) -> BoxFuture<'_, Result<OrderCanceled>> {
+        Box::pin(PaperAccount::cancel_order(self, cancellation))
+    }
+
+    fn cancel_replace_order(&self, order: CancelReplaceRequest) -> BoxFuture<'_, Result<OrderCanceledReplaced>> {
+        Box::pin(PaperAccount::cancel_replace_order(self, order))
+    }
+
+    fn get_open_orders<'a>(&'a self, symbol: &'a str) -> BoxFuture<'a, Result<Vec<Order>>> {
+        Box::pin(PaperAccount::get_open_orders(self, symbol))
+    }
+
+    fn get_account(&self) -> BoxFuture<'_, Result<AccountInformation>> { Box::pin(PaperAccount::get_account(self)) }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Fees {
+    maker: Number,
+    taker: Number,
+}
+
+/// Last trade price and top of the book of a symbol
+#[derive(Clone, Default)]
+struct Quotes {
+    last_price: Option<Number>,
+    bid: Option<Number>,
+    ask: Option<Number>,
+}
+
+impl Quotes {
+    /// The price taker orders of `side` execute at
+    fn taker_price(&self, side: &OrderSide) -> Option<Number> {
+        match side {
+            OrderSide::Buy => self.ask,
+            OrderSide::Sell => self.bid,
+        }
+        .or(self.last_price)
+    }
+}
+
+struct OpenOrder {
+    order: Order,
+    base_asset: String,
+    quote_asset: String,
+    /// Price the quote asset of buy orders is locked at
+    lock_price: Number,
+}
+
+#[derive(Default)]
+struct PaperState {
+    symbols: HashMap<String, Symbol>,
+    quotes: HashMap<String, Quotes>,
+    balances: BTreeMap<String, Balance>,
+    /// Open orders, in placement order
+    orders: Vec<OpenOrder>,
+    fees: Fees,
+    last_order_id: u64,
+    last_trade_id: i64,
+}
+
+impl PaperState {
+    fn place(&mut self, request: OrderRequest, events: &mut Vec<WebsocketEvent>) -> Result<Transaction> {
+        let symbol = self
+            .symbols
+            .get(&request.symbol)
+            .ok_or_else(|| Error::UnknownSymbol(request.symbol.clone()))?;
+        let quotes = self.quotes.get(&request.symbol).cloned().unwrap_or_default();
+        let open_orders = self
+            .orders
+            .iter()
+            .filter(|open| open.order.symbol == request.symbol)
+            .count();
+        let context = FilterContext {
+            average_price: quotes.last_price,
+            open_orders: Some(open_orders),
+        };
+        symbol.validate_order(&request, &context)?;
+        let (base_asset, quote_asset) = (symbol.base_asset.clone(), symbol.quote_asset.clone());
+
+        let no_market_data = || Error::InvalidOrderError {
+            msg: format!("no market data for {}", request.symbol),
+        };
+        let taker_price = quotes.taker_price(&request.side);
+        let quantity = match (request.quantity, request.quote_order_qty) {
+            (Some(quantity), _) => quantity,
+            (None, Some(quote_order_qty)) if request.order_type == OrderType::Market => {
+                match taker_price.filter(|price| *price > Number::default()) {
+                    Some(price) => quote_order_qty / price,
+                    None => return Err(no_market_data()),
+                }
+            }
+            _ => {
+                return Err(Error::InvalidOrderError {
+                    msg: "quantity is required".to_string(),
+                })
+            }
+        };
+        let stop_price = request.stop_price.unwrap_or_default();
+        let lock_price = match request.order_type {
+            OrderType::Market => taker_price.ok_or_else(no_market_data)?,
+            OrderType::StopLoss | OrderType::TakeProfit => stop_price,
+            _ => request.price.unwrap_or_default(),
+        };
+        let now = now();
+        let is_working = !is_conditional(&request.order_type);
+        let order = Order {
+            symbol: request.symbol,
+            order_id: self.last_order_id + 1,
+            order_list_id: -1,
+            client_order_id: request
+                .new_client_order_id
+                .unwrap_or_else(|| format!("paper-{}", self.last_order_id + 1)),
+            price: request.price.unwrap_or_default(),
+            orig_qty: quantity,
+            executed_qty: Number::default(),
+            cummulative_quote_qty: Number::default(),
+            status: OrderStatus::New,
+            time_in_force: request.time_in_force.unwrap_or(TimeInForce::GTC),
+            order_type: request.order_type,
+            side: request.side,
+            stop_price,
+            iceberg_qty: request.iceberg_qty.unwrap_or_default(),
+            time: now,
+            update_time: now,
+            is_working,
+            orig_quote_order_qty: request.quote_order_qty.unwrap_or_default(),
+        };
+        if order.order_type == OrderType::LimitMaker && taker_fill_price(&order, &quotes).is_some() {
+            return Err(binance_error(-2010, "Order would immediately match and take."));
+        }
+        if !order.is_working && quotes.last_price.is_some_and(|price| is_triggered(&order, price)) {
+            return Err(binance_error(-2010, "Stop price would trigger immediately."));
+        }
+        let (asset, amount) = match order.side {
+            OrderSide::Buy => (&quote_asset, quantity * lock_price),
+            OrderSide::Sell => (&base_asset, quantity),
+        };
+        let balance = self.balance(asset);
+        if balance.free < amount {
+            return Err(binance_error(
+                -2010,
+                "Account has insufficient balance for requested action.",
+            ));
+        }
+        balance.free -= amount;
+        balance.locked += amount;
+        self.last_order_id += 1;
+        events.push(order_update(&order, OrderStatus::New, None));
+        events.push(self.position_update(&[asset]));
+
+        let mut open = OpenOrder {
+            order,
+            base_asset,
+            quote_asset,
+            lock_price,
+        };
+        let fills = if open.order.is_working {
+            self.execute(&mut open, &quotes, events).into_iter().collect()
+        } else {
+            vec![]
+        };
+        let transaction = Transaction {
+            symbol: open.order.symbol.clone(),
+            order_id: open.order.order_id,
+            client_order_id: open.order.client_order_id.clone(),
+            transact_time: now,
+            price: open.order.price,
+            orig_qty: open.order.orig_qty,
+            executed_qty: open.order.executed_qty,
+            cummulative_quote_qty: open.order.cummulative_quote_qty,
+            status: open.order.status.clone(),
+            time_in_force: open.order.time_in_force.clone(),
+            order_type: open.order.order_type.clone(),
+            side: open.order.side.clone(),
+            fills,
+        };
+        if is_open(&open.order) {
+            self.orders.push(open);
+        }
+        Ok(transaction)
+    }
+
+    fn cancel(
+        &mut self,
+        symbol: &str,
+        order_id: Option<u64>,
+        orig_client_order_id: Option<String>,
+        new_client_order_id: Option<String>,
+        events: &mut Vec<WebsocketEvent>,
+    ) -> Result<OrderCanceled> {
+        if order_id.is_none() && orig_client_order_id.is_none() {
+            return Err(binance_error(
+                -1102,
+                "Param 'origClientOrderId' or 'orderId' must be sent, but both were empty/null!",
+            ));
+        }
+        let index = self
+            .orders
+            .iter()
+            .position(|open| {
+                open.order.symbol == symbol
+                    && match order_id {
+                        Some(order_id) => open.order.order_id == order_id,
+                        None => orig_client_order_id.as_ref() == Some(&open.order.client_order_id),
+                    }
+            })
+            .ok_or_else(|| binance_error(-2011, "Unknown order sent."))?;
+        let mut open = self.orders.remove(index);
+        self.close(&mut open, OrderStatus::Canceled, events);
+        Ok(OrderCanceled {
+            symbol: open.order.symbol,
+            orig_client_order_id: open.order.client_order_id,
+            order_id: open.order.order_id,
+            client_order_id: new_client_order_id.unwrap_or_else(|| format!("paper-cancel-{}", open.order.order_id)),
+        })
+    }
+
+    fn on_trade(&mut self, symbol: &str, price: Number, qty: Number, events: &mut Vec<WebsocketEvent>) {
+        let quotes = self.quotes.entry(symbol.to_string()).or_default();
+        quotes.last_price = Some(price);
+        let quotes = quotes.clone();
+        let mut available = qty;
+        for mut open in self.take_orders(symbol) {
+            if !open.order.is_working && is_triggered(&open.order, price) {
+                open.order.is_working = true;
+                if self.relock(&mut open, &quotes) {
+                    self.execute(&mut open, &quotes, events);
+                } else {
+                    self.close(&mut open, OrderStatus::Expired, events);
+                }
+            } else if open.order.is_working {
+                self.fill_resting(&mut open, price, &mut available, events);
+            }
+            if is_open(&open.order) {
+                self.orders.push(open);
+            }
+        }
+    }
+
+    fn on_book_ticker(&mut self, ticker: &BookTickerEvent, events: &mut Vec<WebsocketEvent>) {
+        let quotes = self.quotes.entry(ticker.symbol.clone()).or_default();
+        quotes.bid = Some(ticker.best_bid);
+        quotes.ask = Some(ticker.best_ask);
+        let (mut bid_qty, mut ask_qty) = (ticker.best_bid_qty, ticker.best_ask_qty);
+        for mut open in self.take_orders(&ticker.symbol) {
+            if open.order.is_working {
+                match open.order.side {
+                    OrderSide::Buy => self.fill_resting(&mut open, ticker.best_ask, &mut ask_qty, events),
+                    OrderSide::Sell => self.fill_resting(&mut open, ticker.best_bid, &mut bid_qty, events),
+                }
+            }
+            if is_open(&open.order) {
+                self.orders.push(open);
+            }
+        }
+    }
+
+    /// Removes the open orders of `symbol`, to put back those still open after matching
+    fn take_orders(&mut self, symbol: &str) -> Vec<OpenOrder> {
+        let (taken, others) = std::mem::take(&mut self.orders)
+            .into_iter()
+            .partition(|open| open.order.symbol == symbol);
+        self.orders = others;
+        taken
+    }
+
+    /// Fills a working order as taker if it crosses the book, expiring what remains of market, IOC and FOK orders
+    fn execute(&mut self, open: &mut OpenOrder, quotes: &Quotes, events: &mut Vec<WebsocketEvent>) -> Option<Fill> {
+        let fill = taker_fill_price(&open.order, quotes)
+            .map(|price| self.fill(open, remaining(&open.order), price, false, events));
+        let immediate = is_market(&open.order.order_type)
+            || matches!(open.order.time_in_force, TimeInForce::IOC | TimeInForce::FOK);
+        if immediate && is_open(&open.order) {
+            self.close(open, OrderStatus::Expired, events);
+        }
+        fill
+    }
+
+    /// Locks the quote asset of a triggered market buy at the price it fills at, which can be above the stop price
+    /// it was locked at. Returns false when the free balance cannot cover the difference.
+    fn relock(&mut self, open: &mut OpenOrder, quotes: &Quotes) -> bool {
+        if open.order.side != OrderSide::Buy || !is_market(&open.order.order_type) {
+            return true;
+        }
+        let price = match quotes.taker_price(&open.order.side) {
+            Some(price) if price > open.lock_price => price,
+            _ => return true,
+        };
+        let extra = remaining(&open.order) * (price - open.lock_price);
+        let quote = self.balance(&open.quote_asset);
+        if quote.free < extra {
+            return false;
+        }
+        quote.free -= extra;
+        quote.locked += extra;
+        open.lock_price = price;
+        true
+    }
+
+    /// Fills a resting limit order as maker at its price when the market `price` reached it, for up to `available`
+    fn fill_resting(
+        &mut self,
+        open: &mut OpenOrder,
+        price: Number,
+        available: &mut Number,
+        events: &mut Vec<WebsocketEvent>,
+    ) {
+        let crossed = match open.order.side {
+            OrderSide::Buy => price <= open.order.price,
+            OrderSide::Sell => price >= open.order.price,
+        };
+        if is_market(&open.order.order_type) || !crossed || *available <= Number::default() {
+            return;
+        }
+        let qty = remaining(&open.order).min(*available);
+        *available -= qty;
+        self.fill(open, qty, open.order.price, true, events);
+    }
+
+    fn fill(
+        &mut self,
+        open: &mut OpenOrder,
+        qty: Number,
+        price: Number,
+        maker: bool,
+        events: &mut Vec<WebsocketEvent>,
+    ) -> Fill {
+        let rate = if maker { self.fees.maker } else { self.fees.taker };
+        let quote_qty = qty * price;
+        let commission_asset = match open.order.side {
+            OrderSide::Buy => {
+                let released = qty * open.lock_price;
+                let quote = self.balance(&open.quote_asset);
+                quote.locked -= released;
+                quote.free += released - quote_qty;
+                open.base_asset.clone()
+            }
+            OrderSide::Sell => {
+                self.balance(&open.base_asset).locked -= qty;
+                open.quote_asset.clone()
+            }
+        };
+        let received = match open.order.side {
+            OrderSide::Buy => qty,
+            OrderSide::Sell => quote_qty,
+        };
+        let commission = received * rate;
+        self.balance(&commission_asset).free += received - commission;
+
+        let order = &mut open.order;
+        order.executed_qty += qty;
+        order.cummulative_quote_qty += quote_qty;
+        order.status = if remaining(order) > Number::default() {
+            OrderStatus::PartiallyFilled
+        } else {
+            OrderStatus::Filled
+        };
+        order.update_time = now();
+        order.is_working = is_open(order);
+        self.last_trade_id += 1;
+        let fill = Fill {
+            price,
+            qty,
+            commission,
+            commission_asset,
+        };
+        events.push(order_update(
+            order,
+            OrderStatus::Trade,
+            Some((&fill, self.last_trade_id, maker)),
+        ));
+        events.push(self.position_update(&[&open.base_asset, &open.quote_asset]));
+        fill
+    }
+
+    /// Closes an order with `status`, releasing the funds locked for its remaining quantity
+    fn close(&mut self, open: &mut OpenOrder, status: OrderStatus, events: &mut Vec<WebsocketEvent>) {
+        let remaining = remaining(&open.order);
+        let (asset, amount) = match open.order.side {
+            OrderSide::Buy => (&open.quote_asset, remaining * open.lock_price),
+            OrderSide::Sell => (&open.base_asset, remaining),
+        };
+        let balance = self.balance(asset);
+        balance.locked -= amount;
+        balance.free += amount;
+        open.order.status = status.clone();
+        open.order.update_time = now();
+        open.order.is_working = false;
+        events.push(order_update(&open.order, status, None));
+        events.push(self.position_update(&[asset]));
+    }
+
+    fn balance(&mut self, asset: &str) -> &mut Balance {
+        self.balances.entry(asset.to_string()).or_insert_with(|| Balance {
+            asset: asset.to_string(),
+            free: Number::default(),
+            locked: Number::default(),
+        })
+    }
+
+    fn position_update(&self, assets: &[&String]) -> WebsocketEvent {
+        let now = now();
+        let balances = assets
+            .iter()
+            .filter_map(|asset| self.balances.get(*asset))
+            .map(|balance| EventBalance {
+                asset: balance.asset.clone(),
+                free: balance.free,
+                locked: balance.locked,
+            })
+            .collect();
+        WebsocketEvent::AccountPositionUpdate(Box::new(AccountPositionUpdate {
+            event_time: now,
+            last_update_time: now,
+            balances,
+        }))
+    }
+}
+
+fn now() -> u64 { Utc::now().timestamp_millis() as u64 }
+
+#[cfg(not(feature = "decimal"))]
+fn ws_number(value: &WsNumber) -> Option<Number> { value.parse().ok() }
+
+#[cfg(feature = "decimal")]
+fn ws_number(value: &WsNumber) -> Option<Number> { Some(*value) }
+
+fn binance_error(code: i32, msg: &str) -> Error { Error::from(BinanceContentError::new(code, msg)) }
+
+fn remaining(order: &Order) -> Number { order.orig_qty - order.executed_qty }
+
+fn is_open(order: &Order) -> bool { matches!(order.status, OrderStatus::New | OrderStatus::PartiallyFilled) }
+
+/// Orders executed as market orders once working
+fn is_market(order_type: &OrderType) -> bool {
+    matches!(
+        order_type,
+        OrderType::Market | OrderType::StopLoss | OrderType::TakeProfit
+    )
+}
+
+/// Orders working once triggered by their stop price
+fn is_conditional(order_type: &OrderType) -> bool {
+    matches!(
+        order_type,
+        OrderType::StopLoss | OrderType::StopLossLimit | OrderType::TakeProfit | OrderType::TakeProfitLimit
+    )
+}
+
+/// Whether the stop price of a conditional order is reached by the `last_price`
+fn is_triggered(order: &Order, last_price: Number) -> bool {
+    match (&order.order_type, &order.side) {
+        (OrderType::StopLoss | OrderType::StopLossLimit, OrderSide::Buy)
+        | (OrderType::TakeProfit | OrderType::TakeProfitLimit, OrderSide::Sell) => last_price >= order.stop_price,
+        _ => last_price <= order.stop_price,
+    }
+}
+
+/// The price a working order fills at as taker, if it crosses the book
+fn taker_fill_price(order: &Order, quotes: &Quotes) -> Option<Number> {
+    let price = quotes.taker_price(&order.side)?;
+    let crosses = match order.side {
+        OrderSide::Buy => price <= order.price,
+        OrderSide::Sell => price >= order.price,
+    };
+    (is_market(&order.order_type) || crosses).then_some(price)
+}
+
+/// The execution report of `order`, with the last fill, its trade id and whether it was maker
+fn order_update(order: &Order, execution_type: OrderStatus, fill: Option<(&Fill, i64, bool)>) -> WebsocketEvent {
+    let zero = Number::default();
+    WebsocketEvent::OrderUpdate(Box::new(OrderUpdate {
+        event_time: now(),
+        symbol: order.symbol.clone(),
+        client_order_id: Some(order.client_order_id.clone()),
+        side: order.side.clone(),
+        order_type: order.order_type.clone(),
+        time_in_force: order.time_in_force.clone(),
+        qty: order.orig_qty,
+        price: order.price,
+        stop_price: order.stop_price,
+        iceberg_qty: order.iceberg_qty,
+        order_list_id: order.order_list_id as i64,
+        origin_client_id: None,
+        execution_type,
+        current_order_status: order.status.clone(),
+        order_reject_reason: "NONE".to_string(),
+        order_id: order.order_id,
+        qty_last_executed: fill.map_or(zero, |(fill, ..)| fill.qty),
+        cumulative_filled_qty: order.executed_qty,
+        last_executed_price: fill.map_or(zero, |(fill, ..)| fill.price),
+        commission: fill.map_or(zero, |(fill, ..)| fill.commission),
+        commission_asset: fill.map(|(fill, ..)| fill.commission_asset.clone()),
+        trade_order_time: order.update_time,
+        trade_id: fill.map_or(-1, |(_, trade_id, _)| trade_id),
+        i_ignore: 0,
+        is_order_on_the_book: order.is_working,
+        is_buyer_maker: fill.is_some_and(|(.., maker)| maker),
+        m_ignore: false,
+        order_creation_time: order.time,
+        cumulative_quote_asset_transacted_qty: order.cummulative_quote_qty,
+        last_quote_asset_transacted_qty: fill.map_or(zero, |(fill, ..)| fill.price * fill.qty),
+        quote_order_qty: order.orig_quote_order_qty,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::num;
+
+    fn account() -> PaperAccount {
+        let symbol: Symbol = serde_json::from_value(serde_json::json!({
+            "symbol": "BTCUSDT",
+            "status": "TRADING",
+            "baseAsset": "BTC",
+            "baseAssetPrecision": 8,
+            "quoteAsset": "USDT",
+            "quotePrecision": 8,
+            "quoteAssetPrecision": 8,
+            "baseCommissionPrecision": 8,
+            "quoteCommissionPrecision": 8,
+            "orderTypes": ["LIMIT", "LIMIT_MAKER", "MARKET", "STOP_LOSS"],
+            "icebergAllowed": true,
+            "ocoAllowed": true,
+            "quoteOrderQtyMarketAllowed": true,
+            "isSpotTradingAllowed": true,
+            "isMarginTradingAllowed": true,
+            "permissions": ["SPOT"],
+            "filters": [
+                {"filterType": "PRICE_FILTER", "minPrice": "0.01", "maxPrice": "1000000.00", "tickSize": "0.01"},
+                {"filterType": "LOT_SIZE", "minQty": "0.001", "maxQty": "9000.0", "stepSize": "0.001"}
+            ]
+        }))
+        .unwrap();
+        PaperAccount::new(vec![symbol], [("USDT", num(1000.0)), ("BTC", num(1.0))]).with_fees(num(0.0), num(0.01))
+    }
+
+    fn order(side: OrderSide, order_type: OrderType, quantity: f64, price: Option<f64>) -> OrderRequest {
+        OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side,
+            time_in_force: price.map(|_| TimeInForce::GTC),
+            order_type,
+            quantity: Some(num(quantity)),
+            price: price.map(num),
+            ..OrderRequest::default()
+        }
+    }
+
+    fn ticker(bid: f64, ask: f64) -> BookTickerEvent {
+        BookTickerEvent {
+            update_id: 1,
+            symbol: "BTCUSDT".to_string(),
+            best_bid: num(bid),
+            best_bid_qty: num(1.0),
+            best_ask: num(ask),
+            best_ask_qty: num(1.0),
+        }
+    }
+
+    async fn balance(account: &PaperAccount, asset: &str) -> (Number, Number) {
+        let information = account.get_account().await.unwrap();
+        let balance = information
+            .balances
+            .into_iter()
+            .find(|balance| balance.asset == asset)
+            .unwrap();
+        (balance.free, balance.locked)
+    }
+
+    fn error_code(error: Error) -> i32 {
+        match error {
+            Error::BinanceError { response } => response.code,
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn fills_market_orders_as_taker() {
+        let account = account();
+        let mut events = account.subscribe();
+        account.on_book_ticker(&ticker(99.0, 100.0));
+
+        let transaction = account
+            .place_order(order(OrderSide::Buy, OrderType::Market, 2.0, None))
+            .await
+            .unwrap();
+        assert_eq!(transaction.status, OrderStatus::Filled);
+        assert_eq!(transaction.fills.len(), 1);
+        assert_eq!(transaction.fills[0].price, num(100.0));
+        assert_eq!(transaction.fills[0].commission, num(0.02));
+        assert_eq!(balance(&account, "USDT").await, (num(800.0), num(0.0)));
+        assert_eq!(balance(&account, "BTC").await, (num(2.98), num(0.0)));
+
+        let mut execution_types = vec![];
+        while let Ok(event) = events.try_recv() {
+            if let WebsocketEvent::OrderUpdate(update) = event {
+                execution_types.push(update.execution_type);
+            }
+        }
+        assert_eq!(execution_types, vec![OrderStatus::New, OrderStatus::Trade]);
+
+        let error = account
+            .place_order(order(OrderSide::Buy, OrderType::Market, 20.0, None))
+            .await
+            .unwrap_err();
+        assert_eq!(error_code(error), -2010);
+    }
+
+    #[tokio::test]
+    async fn fills_resting_orders_as_maker() {
+        let account = account();
+        account.on_book_ticker(&ticker(99.0, 100.0));
+        let error = account
+            .place_order(order(OrderSide::Sell, OrderType::LimitMaker, 1.0, Some(99.0)))
+            .await
+            .unwrap_err();
+        assert_eq!(error_code(error), -2010);
+
+        let transaction = account
+            .place_order(order(OrderSide::Sell, OrderType::Limit, 1.0, Some(105.0)))
+            .await
+            .unwrap();
+        assert_eq!(transaction.status, OrderStatus::New);
+        assert_eq!(balance(&account, "BTC").await, (num(0.0), num(1.0)));
+
+        account.on_trade("BTCUSDT", num(104.0), num(5.0));
+        let trade = serde_json::json!({
+            "e": "trade", "E": 1, "s": "BTCUSDT", "t": 1, "p": "106.00000000", "q": "0.25000000", "b": 1, "a": 2,
+            "T": 1, "m": false, "M": true
+        });
+        account.on_event(&serde_json::from_value(trade).unwrap());
+        let open_orders = account.get_open_orders("BTCUSDT").await.unwrap();
+        assert_eq!(open_orders.len(), 1);
+        assert_eq!(open_orders[0].status, OrderStatus::PartiallyFilled);
+        assert_eq!(open_orders[0].executed_qty, num(0.25));
+        assert_eq!(balance(&account, "USDT").await, (num(1026.25), num(0.0)));
+
+        let canceled = account
+            .cancel_order(OrderCancellation {
+                symbol: "BTCUSDT".to_string(),
+                order_id: Some(transaction.order_id),
+                ..OrderCancellation::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(canceled.order_id, transaction.order_id);
+        assert_eq!(balance(&account, "BTC").await, (num(0.75), num(0.0)));
+        let error = account
+            .cancel_order(OrderCancellation {
+                symbol: "BTCUSDT".to_string(),
+                order_id: Some(transaction.order_id),
+                ..OrderCancellation::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error_code(error), -2011);
+    }
+
+    #[tokio::test]
+    async fn triggers_stop_orders_and_replaces_orders() {
+        let account = account();
+        account.on_trade("BTCUSDT", num(100.0), num(1.0));
+        let stop_loss = OrderRequest {
+            stop_price: Some(num(90.0)),
+            ..order(OrderSide::Sell, OrderType::StopLoss, 1.0, None)
+        };
+        let transaction = account.place_order(stop_loss).await.unwrap();
+        assert_eq!(transaction.status, OrderStatus::New);
+
+        let replaced = account
+            .cancel_replace_order(CancelReplaceRequest {
+                symbol: "BTCUSDT".to_string(),
+                side: OrderSide::Sell,
+                order_type: OrderType::StopLoss,
+                quantity: Some(num(0.5)),
+                stop_price: Some(num(95.0)),
+                cancel_order_id: Some(transaction.order_id),
+                ..CancelReplaceRequest::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(replaced.cancel_response.order_id, transaction.order_id);
+        assert_eq!(balance(&account, "BTC").await, (num(0.5), num(0.5)));
+
+        account.on_book_ticker(&ticker(94.0, 94.5));
+        account.on_trade("BTCUSDT", num(94.5), num(1.0));
+        assert!(account.get_open_orders("BTCUSDT").await.unwrap().is_empty());
+        assert_eq!(balance(&account, "BTC").await, (num(0.5), num(0.0)));
+        assert_eq!(balance(&account, "USDT").await, (num(1046.53), num(0.0)));
+    }
+
+    #[tokio::test]
+    async fn stop_buys_gapping_above_their_stop() {
+        let stop_buy = |quantity: f64| OrderRequest {
+            stop_price: Some(num(100.0)),
+            ..order(OrderSide::Buy, OrderType::StopLoss, quantity, None)
+        };
+
+        // The whole balance is locked at the stop price, the fill price cannot be paid
+        let locked = account();
+        locked.on_trade("BTCUSDT", num(90.0), num(1.0));
+        locked.place_order(stop_buy(10.0)).await.unwrap();
+        assert_eq!(balance(&locked, "USDT").await, (num(0.0), num(1000.0)));
+        locked.on_book_ticker(&ticker(104.0, 105.0));
+        locked.on_trade("BTCUSDT", num(104.0), num(1.0));
+        assert!(locked.get_open_orders("BTCUSDT").await.unwrap().is_empty());
+        assert_eq!(balance(&locked, "USDT").await, (num(1000.0), num(0.0)));
+        assert_eq!(balance(&locked, "BTC").await, (num(1.0), num(0.0)));
+
+        // The free balance covers the difference
+        let covered = account();
+        covered.on_trade("BTCUSDT", num(90.0), num(1.0));
+        covered.place_order(stop_buy(5.0)).await.unwrap();
+        covered.on_book_ticker(&ticker(104.0, 105.0));
+        covered.on_trade("BTCUSDT", num(104.0), num(1.0));
+        assert_eq!(balance(&covered, "USDT").await, (num(475.0), num(0.0)));
+        assert_eq!(balance(&covered, "BTC").await, (num(5.95), num(0.0)));
+    }
+}
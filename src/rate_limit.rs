@@ -0,0 +1,409 @@
+//! Client side rate limiting.
+//!
+//! Usage is estimated from the weight of each endpoint and corrected with the
+//! `X-MBX-USED-WEIGHT-*` and `X-MBX-ORDER-COUNT-*` headers returned by Binance.
+//! Limiters are shared by all the clients of a host, as Binance counts usage per IP and account.
+//! A client configured with different limits replaces the limiter of its host for the clients created after it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+
+use crate::errors::*;
+use crate::rest_model::{RateLimit, RateLimitInterval, RateLimitType};
+
+const USED_WEIGHT: &str = "x-mbx-used-weight-";
+const ORDER_COUNT: &str = "x-mbx-order-count-";
+
+lazy_static! {
+    static ref RATE_LIMITERS: Mutex<HashMap<String, Arc<RateLimiter>>> = Mutex::new(HashMap::new());
+}
+
+/// What to do with a request that would exceed a limit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Wait for the end of the current window
+    Delay,
+    /// Fail with [`Error::RateLimitReached`]
+    Reject,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimiterConfig {
+    pub mode: RateLimitMode,
+    /// Limits to enforce, as returned in `rate_limits` by `exchange_info`
+    pub limits: Vec<RateLimit>,
+}
+
+impl RateLimiterConfig {
+    /// Default limits of the spot, margin and wallet APIs
+    pub fn spot(mode: RateLimitMode) -> Self {
+        Self {
+            mode,
+            limits: vec![
+                rate_limit(RateLimitType::RequestWeight, RateLimitInterval::Minute, 1, 6000),
+                rate_limit(RateLimitType::Orders, RateLimitInterval::Second, 10, 100),
+                rate_limit(RateLimitType::Orders, RateLimitInterval::Day, 1, 200000),
+                rate_limit(RateLimitType::RawRequests, RateLimitInterval::Minute, 5, 61000),
+            ],
+        }
+    }
+
+    /// Default limits of the USD-M futures API
+    pub fn futures(mode: RateLimitMode) -> Self {
+        Self {
+            mode,
+            limits: vec![
+                rate_limit(RateLimitType::RequestWeight, RateLimitInterval::Minute, 1, 2400),
+                rate_limit(RateLimitType::Orders, RateLimitInterval::Minute, 1, 1200),
+                rate_limit(RateLimitType::Orders, RateLimitInterval::Second, 10, 300),
+            ],
+        }
+    }
+}
+
+fn rate_limit(rate_limit_type: RateLimitType, interval: RateLimitInterval, interval_num: i32, limit: i32) -> RateLimit {
+    RateLimit {
+        interval,
+        rate_limit_type,
+        interval_num,
+        limit,
+    }
+}
+
+fn interval_millis(interval: &RateLimitInterval, interval_num: i32) -> u64 {
+    let unit = match interval {
+        RateLimitInterval::Second => 1000,
+        RateLimitInterval::Minute => 60 * 1000,
+        RateLimitInterval::Day => 24 * 60 * 60 * 1000,
+    };
+    unit * interval_num.max(1) as u64
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Current usage of a limit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimitUsage {
+    pub rate_limit: RateLimit,
+    pub used: u32,
+    /// Time left until the usage is reset
+    pub reset_in: Duration,
+}
+
+#[derive(Debug)]
+struct Counter {
+    rate_limit: RateLimit,
+    interval_millis: u64,
+    window: u64,
+    used: u32,
+}
+
+impl Counter {
+    /// Binance windows are aligned on the epoch
+    fn refresh(&mut self, now: u64) {
+        let window = now / self.interval_millis;
+        if window != self.window {
+            self.window = window;
+            self.used = 0;
+        }
+    }
+
+    fn reset_in(&self, now: u64) -> Duration {
+        let window_end = (self.window + 1) * self.interval_millis;
+        Duration::from_millis(window_end.saturating_sub(now))
+    }
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimiterConfig,
+    counters: Mutex<Vec<Counter>>,
+}
+
+/// The limiter shared by the clients of `host`, replaced by a new one if it was created from another `config`
+pub(crate) fn register(host: &str, config: &RateLimiterConfig) -> Arc<RateLimiter> {
+    let mut limiters = RATE_LIMITERS.lock().unwrap();
+    match limiters.get(host) {
+        Some(limiter) if limiter.config == *config => limiter.clone(),
+        _ => {
+            let limiter = Arc::new(RateLimiter::new(config));
+            limiters.insert(host.to_string(), limiter.clone());
+            limiter
+        }
+    }
+}
+
+/// The limiter of `host`, if a client was configured with one
+pub fn rate_limiter(host: &str) -> Option<Arc<RateLimiter>> { RATE_LIMITERS.lock().unwrap().get(host).cloned() }
+
+impl RateLimiter {
+    pub fn new(config: &RateLimiterConfig) -> Self {
+        let counters = config
+            .limits
+            .iter()
+            .map(|rate_limit| Counter {
+                interval_millis: interval_millis(&rate_limit.interval, rate_limit.interval_num),
+                rate_limit: rate_limit.clone(),
+                window: 0,
+                used: 0,
+            })
+            .collect();
+        Self {
+            config: config.clone(),
+            counters: Mutex::new(counters),
+        }
+    }
+
+    /// Current usage of each limit
+    pub fn usage(&self) -> Vec<RateLimitUsage> {
+        let now = now_millis();
+        let mut counters = self.counters.lock().unwrap();
+        counters
+            .iter_mut()
+            .map(|counter| {
+                counter.refresh(now);
+                RateLimitUsage {
+                    rate_limit: counter.rate_limit.clone(),
+                    used: counter.used,
+                    reset_in: counter.reset_in(now),
+                }
+            })
+            .collect()
+    }
+
+    /// Reserve the weight of a request, waiting or failing according to the mode if a limit would be exceeded.
+    /// A request costing more than a limit allows fails in any mode, as it could never be sent.
+    pub(crate) async fn acquire(&self, method: &Method, endpoint: &str, query: Option<&str>) -> Result<()> {
+        let weight = endpoint_weight(method, endpoint, query.unwrap_or_default());
+        let is_order = is_order(method, endpoint);
+        loop {
+            let wait = self.try_acquire(weight, is_order)?;
+            match wait {
+                None => return Ok(()),
+                Some(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    fn try_acquire(&self, weight: u32, is_order: bool) -> Result<Option<Duration>> {
+        let now = now_millis();
+        let mut counters = self.counters.lock().unwrap();
+        for counter in counters.iter_mut() {
+            counter.refresh(now);
+            let cost = cost(&counter.rate_limit.rate_limit_type, weight, is_order);
+            let limit = counter.rate_limit.limit.max(0) as u32;
+            if cost > limit {
+                return Err(Error::RateLimitReached {
+                    rate_limit_type: counter.rate_limit.rate_limit_type.clone(),
+                    retry_after: Duration::MAX,
+                });
+            }
+            if cost > 0 && counter.used + cost > limit {
+                let retry_after = counter.reset_in(now);
+                return match self.config.mode {
+                    RateLimitMode::Delay => Ok(Some(retry_after)),
+                    RateLimitMode::Reject => Err(Error::RateLimitReached {
+                        rate_limit_type: counter.rate_limit.rate_limit_type.clone(),
+                        retry_after,
+                    }),
+                };
+            }
+        }
+        for counter in counters.iter_mut() {
+            counter.used += cost(&counter.rate_limit.rate_limit_type, weight, is_order);
+        }
+        Ok(None)
+    }
+
+    /// Correct the estimated usage with the usage reported by Binance
+    pub(crate) fn update(&self, headers: &HeaderMap) {
+        let now = now_millis();
+        let mut counters = self.counters.lock().unwrap();
+        for (name, value) in headers {
+            let name = name.as_str();
+            let (rate_limit_type, interval) = match (name.strip_prefix(USED_WEIGHT), name.strip_prefix(ORDER_COUNT)) {
+                (Some(interval), _) => (RateLimitType::RequestWeight, interval),
+                (_, Some(interval)) => (RateLimitType::Orders, interval),
+                _ => continue,
+            };
+            let (interval_millis, used) = match (parse_interval(interval), value.to_str().map(str::parse::<u32>)) {
+                (Some(interval_millis), Ok(Ok(used))) => (interval_millis, used),
+                _ => continue,
+            };
+            for counter in counters.iter_mut() {
+                if counter.rate_limit.rate_limit_type == rate_limit_type && counter.interval_millis == interval_millis {
+                    counter.refresh(now);
+                    counter.used = counter.used.max(used);
+                }
+            }
+        }
+    }
+}
+
+fn cost(rate_limit_type: &RateLimitType, weight: u32, is_order: bool) -> u32 {
+    match rate_limit_type {
+        RateLimitType::RequestWeight => weight,
+        RateLimitType::Orders if is_order => 1,
+        RateLimitType::RawRequests => 1,
+        _ => 0,
+    }
+}
+
+/// Header suffixes look like `1m`, `10s` or `1d`
+fn parse_interval(interval: &str) -> Option<u64> {
+    let split = interval.find(|c: char| !c.is_ascii_digit())?;
+    let num: u64 = interval[..split].parse().ok()?;
+    let unit = match &interval[split..] {
+        "s" => 1000,
+        "m" => 60 * 1000,
+        "h" => 60 * 60 * 1000,
+        "d" => 24 * 60 * 60 * 1000,
+        _ => return None,
+    };
+    Some(num * unit)
+}
+
+fn is_order(method: &Method, endpoint: &str) -> bool {
+    method == Method::POST
+        && !endpoint.ends_with("/test")
+        && (endpoint.contains("/order") || endpoint.ends_with("/batchOrders"))
+}
+
+fn param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix(name).and_then(|rest| rest.strip_prefix('=')))
+}
+
+/// Request weight of an endpoint, 1 for endpoints missing from the table
+pub fn endpoint_weight(method: &Method, endpoint: &str, query: &str) -> u32 {
+    let limit = param(query, "limit").and_then(|l| l.parse::<u32>().ok());
+    let with_symbol = param(query, "symbol").is_some() || param(query, "symbols").is_some();
+    match endpoint {
+        "/api/v3/exchangeInfo" => 20,
+        "/api/v3/depth" => match limit.unwrap_or(100) {
+            0..=100 => 5,
+            101..=500 => 25,
+            501..=1000 => 50,
+            _ => 250,
+        },
+        "/api/v3/trades" | "/api/v3/historicalTrades" => 25,
+        "/api/v3/aggTrades" | "/api/v3/klines" | "/api/v3/uiKlines" | "/api/v3/avgPrice" => 2,
+        "/api/v3/ticker/24hr" if with_symbol => 2,
+        "/api/v3/ticker/24hr" => 80,
+        "/api/v3/ticker/price" | "/api/v3/ticker/bookTicker" if with_symbol => 2,
+        "/api/v3/ticker/price" | "/api/v3/ticker/bookTicker" => 4,
+        "/api/v3/order" if method == Method::GET => 4,
+        "/api/v3/openOrders" if with_symbol => 6,
+        "/api/v3/openOrders" => 80,
+        "/api/v3/allOrders" | "/api/v3/account" | "/api/v3/myTrades" | "/api/v3/allOrderList" => 20,
+        "/api/v3/orderList" if method == Method::GET => 4,
+        "/api/v3/openOrderList" => 6,
+        "/api/v3/userDataStream" => 2,
+        "/fapi/v1/depth" => match limit.unwrap_or(500) {
+            0..=50 => 2,
+            51..=100 => 5,
+            101..=500 => 10,
+            _ => 20,
+        },
+        "/fapi/v1/klines" | "/fapi/v1/continuousKlines" | "/fapi/v1/indexPriceKlines" | "/fapi/v1/markPriceKlines" => {
+            match limit.unwrap_or(500) {
+                0..=99 => 1,
+                100..=499 => 2,
+                500..=1000 => 5,
+                _ => 10,
+            }
+        }
+        "/fapi/v1/trades" => 5,
+        "/fapi/v1/historicalTrades" | "/fapi/v1/aggTrades" => 20,
+        "/fapi/v1/premiumIndex" if !with_symbol => 10,
+        "/fapi/v1/ticker/24hr" if !with_symbol => 40,
+        "/fapi/v1/ticker/price" if !with_symbol => 2,
+        "/fapi/v1/ticker/bookTicker" if with_symbol => 2,
+        "/fapi/v1/ticker/bookTicker" => 5,
+        "/fapi/v1/openOrders" if !with_symbol => 40,
+        "/fapi/v1/allOrders" | "/fapi/v1/userTrades" => 5,
+        "/fapi/v2/account" | "/fapi/v2/balance" | "/fapi/v2/positionRisk" => 5,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn endpoint_weights() {
+        assert_eq!(
+            endpoint_weight(&Method::GET, "/api/v3/depth", "symbol=BTCUSDT&limit=1000"),
+            50
+        );
+        assert_eq!(endpoint_weight(&Method::GET, "/api/v3/ticker/24hr", ""), 80);
+        assert_eq!(
+            endpoint_weight(&Method::GET, "/api/v3/ticker/24hr", "symbol=BTCUSDT"),
+            2
+        );
+        assert_eq!(endpoint_weight(&Method::POST, "/api/v3/order", "symbol=BTCUSDT"), 1);
+        assert!(is_order(&Method::POST, "/api/v3/order"));
+        assert!(!is_order(&Method::POST, "/api/v3/order/test"));
+        assert_eq!(parse_interval("10s"), Some(10_000));
+        assert_eq!(parse_interval("1m"), Some(60_000));
+    }
+
+    #[test]
+    fn reject_and_update_from_headers() {
+        let limiter = RateLimiter::new(&RateLimiterConfig {
+            mode: RateLimitMode::Reject,
+            limits: vec![rate_limit(RateLimitType::RequestWeight, RateLimitInterval::Day, 1, 10)],
+        });
+        assert!(limiter.try_acquire(5, false).unwrap().is_none());
+        let mut headers = HeaderMap::new();
+        headers.insert("x-mbx-used-weight-1d", HeaderValue::from_static("8"));
+        limiter.update(&headers);
+        assert_eq!(limiter.usage()[0].used, 8);
+        assert!(matches!(
+            limiter.try_acquire(5, false),
+            Err(Error::RateLimitReached { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn reject_requests_over_the_limit_when_delaying() {
+        let limiter = RateLimiter::new(&RateLimiterConfig {
+            mode: RateLimitMode::Delay,
+            limits: vec![rate_limit(
+                RateLimitType::RequestWeight,
+                RateLimitInterval::Minute,
+                1,
+                10,
+            )],
+        });
+        let acquire = limiter.acquire(&Method::GET, "/api/v3/exchangeInfo", None);
+        let result = tokio::time::timeout(Duration::from_secs(1), acquire).await;
+        assert!(matches!(result, Ok(Err(Error::RateLimitReached { .. }))));
+        assert_eq!(limiter.usage()[0].used, 0);
+    }
+
+    #[test]
+    fn register_replaces_limiters_with_another_config() {
+        let host = "https://rate-limit.test";
+        let spot = register(host, &RateLimiterConfig::spot(RateLimitMode::Delay));
+        assert!(Arc::ptr_eq(
+            &spot,
+            &register(host, &RateLimiterConfig::spot(RateLimitMode::Delay))
+        ));
+        let reject = register(host, &RateLimiterConfig::spot(RateLimitMode::Reject));
+        assert!(!Arc::ptr_eq(&spot, &reject));
+        assert!(Arc::ptr_eq(&reject, &rate_limiter(host).unwrap()));
+    }
+}
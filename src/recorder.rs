@@ -0,0 +1,356 @@
+//! Recording of websocket streams to disk and replay.
+//!
+//! The [`Recorder`] writes raw messages with their receive time as JSON lines to gzip compressed files,
+//! rotated by age and size. Messages are received as [`RawValue`] to record them exactly as sent by Binance,
+//! from [`WebSockets`](crate::websockets::WebSockets) or [`WebsocketStream`](crate::websockets::WebsocketStream).
+//! The [`Replayer`] reads the files back and deserializes the messages into the same events, e.g.
+//! [`WebsocketEvent`](crate::ws_model::WebsocketEvent) or
+//! [`CombinedStreamEvent`](crate::ws_model::CombinedStreamEvent), delivered to a handler at the original pace,
+//! accelerated or as fast as possible.
+//!
+//! # Examples
+//! ```rust,no_run
+//! use binance::{recorder::*, websockets::*, ws_model::*};
+//! use serde_json::value::RawValue;
+//! use std::sync::atomic::AtomicBool;
+//!
+//! tokio_test::block_on(async {
+//!     let mut recorder = Recorder::new("recordings", "btcusdt")?;
+//!     let mut web_socket: WebSockets<'_, Box<RawValue>> =
+//!         WebSockets::new(|message: Box<RawValue>| recorder.record(&message));
+//!     web_socket.connect(&trade_stream("btcusdt")).await?;
+//!     web_socket.event_loop(&AtomicBool::new(true)).await?;
+//!     drop(web_socket);
+//!     recorder.finish()?;
+//!
+//!     let mut replayer: Replayer<'_, WebsocketEvent> = Replayer::new(
+//!         recordings("recordings", "btcusdt")?,
+//!         |event: WebsocketEvent| {
+//!             println!("{event:?}");
+//!             Ok(())
+//!         },
+//!     );
+//!     replayer.set_speed(ReplaySpeed::Accelerated(10.0));
+//!     replayer.event_loop(&AtomicBool::new(true)).await?;
+//!     Ok::<(), binance::errors::Error>(())
+//! });
+//! ```
+
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use chrono::Utc;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use tokio::sync::mpsc;
+use tokio::time::{sleep_until, Instant};
+
+use crate::errors::*;
+
+const EXTENSION: &str = "jsonl.gz";
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(3600);
+/// Lines read ahead of the replay
+const READ_AHEAD_LINES: usize = 1024;
+
+/// A line of a recording
+#[derive(Serialize, Deserialize)]
+struct Record<'a> {
+    /// Local receive time, in ms
+    received: u64,
+    #[serde(borrow)]
+    message: &'a RawValue,
+}
+
+struct RecordingFile {
+    encoder: GzEncoder<BufWriter<File>>,
+    opened: u64,
+    /// Uncompressed size
+    bytes: u64,
+}
+
+/// Writes websocket messages to rotating gzip files named `{prefix}-{first receive time in ms}.jsonl.gz`
+pub struct Recorder {
+    directory: PathBuf,
+    prefix: String,
+    max_age: Duration,
+    max_bytes: Option<u64>,
+    file: Option<RecordingFile>,
+}
+
+impl Recorder {
+    /// Records in `directory`, created if it does not exist, rotating files every hour
+    pub fn new<P: Into<PathBuf>>(directory: P, prefix: &str) -> Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        Ok(Recorder {
+            directory,
+            prefix: prefix.to_string(),
+            max_age: DEFAULT_MAX_AGE,
+            max_bytes: None,
+            file: None,
+        })
+    }
+
+    /// Rotate files once they are older than `max_age`
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Rotate files once they hold more than `max_bytes` of uncompressed messages
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Records a message received now
+    pub fn record(&mut self, message: &RawValue) -> Result<()> {
+        self.record_at(Utc::now().timestamp_millis() as u64, message)
+    }
+
+    /// Records a message received at `received` (in ms)
+    pub fn record_at(&mut self, received: u64, message: &RawValue) -> Result<()> {
+        if self.file.as_ref().is_some_and(|file| self.is_full(file, received)) {
+            self.finish()?;
+        }
+        let file = match self.file {
+            Some(ref mut file) => file,
+            None => self.file.insert(self.open(received)?),
+        };
+        let mut line = serde_json::to_vec(&Record { received, message })?;
+        line.push(b'\n');
+        file.encoder.write_all(&line)?;
+        file.bytes += line.len() as u64;
+        Ok(())
+    }
+
+    /// Flushes the current file, its content can be read while it is being written
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(ref mut file) = self.file {
+            file.encoder.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Completes the current file, the next message starts a new one
+    pub fn finish(&mut self) -> Result<()> {
+        if let Some(file) = self.file.take() {
+            file.encoder.finish()?.flush()?;
+        }
+        Ok(())
+    }
+
+    fn is_full(&self, file: &RecordingFile, received: u64) -> bool {
+        received.saturating_sub(file.opened) >= self.max_age.as_millis() as u64
+            || self.max_bytes.is_some_and(|max_bytes| file.bytes >= max_bytes)
+    }
+
+    fn open(&self, received: u64) -> Result<RecordingFile> {
+        let mut path = self.directory.join(format!("{}-{received}.{EXTENSION}", self.prefix));
+        // Files opened in the same millisecond
+        let mut n = 0;
+        while path.exists() {
+            n += 1;
+            path = self
+                .directory
+                .join(format!("{}-{received}-{n}.{EXTENSION}", self.prefix));
+        }
+        let writer = BufWriter::new(File::create(path)?);
+        Ok(RecordingFile {
+            encoder: GzEncoder::new(writer, Compression::default()),
+            opened: received,
+            bytes: 0,
+        })
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        // Errors can only be handled with an explicit `finish`
+        let _ = self.finish();
+    }
+}
+
+/// The recordings of `prefix` in `directory`, in time order
+pub fn recordings<P: AsRef<Path>>(directory: P, prefix: &str) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<((u64, u64), PathBuf)> = vec![];
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let order = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| recording_order(name, prefix));
+        if let Some(order) = order {
+            files.push((order, path));
+        }
+    }
+    files.sort();
+    Ok(files.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Opening time and counter of files opened in the same millisecond, from `{prefix}-{opened}[-{n}].jsonl.gz`
+fn recording_order(name: &str, prefix: &str) -> Option<(u64, u64)> {
+    let name = name
+        .strip_prefix(prefix)?
+        .strip_prefix('-')?
+        .strip_suffix(EXTENSION)?
+        .strip_suffix('.')?;
+    let (opened, n) = name.split_once('-').unwrap_or((name, "0"));
+    Some((opened.parse().ok()?, n.parse().ok()?))
+}
+
+/// Pace of a replay
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplaySpeed {
+    /// Messages are delivered with the delays they were received with
+    Original,
+    /// Delays are divided by the factor, which has to be positive
+    Accelerated(f64),
+    /// Messages are delivered without delay
+    Max,
+}
+
+/// Replays recordings to a handler, like [`WebSockets`](crate::websockets::WebSockets) does with live messages
+pub struct Replayer<'a, WE> {
+    files: Vec<PathBuf>,
+    handler: Box<dyn FnMut(WE) -> Result<()> + 'a + Send>,
+    speed: ReplaySpeed,
+}
+
+impl<'a, WE: DeserializeOwned> Replayer<'a, WE> {
+    /// Replays `files` in order
+    pub fn new<Callback>(files: Vec<PathBuf>, handler: Callback) -> Replayer<'a, WE>
+    where
+        Callback: FnMut(WE) -> Result<()> + 'a + Send,
+    {
+        Replayer {
+            files,
+            handler: Box::new(handler),
+            speed: ReplaySpeed::Original,
+        }
+    }
+
+    /// Sets the pace of the replay, accelerations by an infinite, zero, negative or NaN factor replay at
+    /// [`ReplaySpeed::Max`]
+    pub fn set_speed(&mut self, speed: ReplaySpeed) {
+        self.speed = match speed {
+            ReplaySpeed::Accelerated(factor) if !(factor.is_finite() && factor > 0.0) => ReplaySpeed::Max,
+            speed => speed,
+        };
+    }
+
+    /// Delivers the recorded messages until `running` is set to false or all the files were replayed.
+    /// Files are read and decompressed on a blocking thread.
+    pub async fn event_loop(&mut self, running: &AtomicBool) -> Result<()> {
+        let mut start: Option<(u64, Instant)> = None;
+        let mut lines = read_lines(self.files.clone());
+        while let Some(line) = lines.recv().await {
+            if !running.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let record: Record<'_> = serde_json::from_str(&line)?;
+            let (first_received, started) = *start.get_or_insert((record.received, Instant::now()));
+            let elapsed = Duration::from_millis(record.received.saturating_sub(first_received));
+            let delay = match self.speed {
+                ReplaySpeed::Original => Some(elapsed),
+                // Delays too long to be represented are not waited for
+                ReplaySpeed::Accelerated(factor) => Duration::try_from_secs_f64(elapsed.as_secs_f64() / factor).ok(),
+                ReplaySpeed::Max => None,
+            };
+            if let Some(deadline) = delay.and_then(|delay| started.checked_add(delay)) {
+                sleep_until(deadline).await;
+            }
+            let event: WE = serde_json::from_str(record.message.get())?;
+            (self.handler)(event)?;
+        }
+        Ok(())
+    }
+}
+
+/// The lines of `files` in order, read on a blocking thread until the receiver is dropped or a read fails
+fn read_lines(files: Vec<PathBuf>) -> mpsc::Receiver<std::io::Result<String>> {
+    let (lines, receiver) = mpsc::channel(READ_AHEAD_LINES);
+    tokio::task::spawn_blocking(move || {
+        for path in files {
+            let file = match File::open(path) {
+                Ok(file) => file,
+                Err(e) => {
+                    let _ = lines.blocking_send(Err(e));
+                    return;
+                }
+            };
+            for line in BufReader::new(MultiGzDecoder::new(BufReader::new(file))).lines() {
+                let failed = line.is_err();
+                if lines.blocking_send(line).is_err() || failed {
+                    return;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::ws_model::{CombinedStreamEvent, WebsocketEvent};
+
+    fn raw(message: &str) -> Box<RawValue> { RawValue::from_string(message.to_string()).unwrap() }
+
+    #[tokio::test]
+    async fn records_and_replays() {
+        let directory = std::env::temp_dir().join(format!("binance-recorder-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        let trade = |id: u64| {
+            let data = serde_json::json!({
+                "e": "trade", "E": 1, "s": "BTCUSDT", "t": id, "p": "0.1", "q": "1.0", "b": 1, "a": 2, "T": 1,
+                "m": true, "M": true
+            });
+            serde_json::json!({ "stream": "btcusdt@trade", "data": data }).to_string()
+        };
+        let mut recorder = Recorder::new(&directory, "btcusdt").unwrap().with_max_bytes(1);
+        for (received, id) in [(1_000, 1), (1_001, 2), (1_020, 3)] {
+            recorder.record_at(received, &raw(&trade(id))).unwrap();
+        }
+        recorder.finish().unwrap();
+        drop(recorder);
+
+        let files = recordings(&directory, "btcusdt").unwrap();
+        assert_eq!(files.len(), 3, "{files:?}");
+        let ids = Mutex::new(vec![]);
+        let started = Instant::now();
+        let mut replayer = Replayer::new(files, |event: CombinedStreamEvent<WebsocketEvent>| {
+            if let WebsocketEvent::Trade(trade) = event.data {
+                ids.lock().unwrap().push(trade.trade_id);
+            }
+            Ok(())
+        });
+        replayer.set_speed(ReplaySpeed::Original);
+        replayer.event_loop(&AtomicBool::new(true)).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert_eq!(*ids.lock().unwrap(), vec![1, 2, 3]);
+
+        for factor in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            replayer.set_speed(ReplaySpeed::Accelerated(factor));
+            assert_eq!(replayer.speed, ReplaySpeed::Max);
+        }
+        replayer.set_speed(ReplaySpeed::Accelerated(f64::MIN_POSITIVE));
+        replayer.event_loop(&AtomicBool::new(true)).await.unwrap();
+        assert_eq!(ids.lock().unwrap().len(), 6);
+        drop(replayer);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
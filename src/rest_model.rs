@@ -2,6 +2,13 @@ use chrono::{DateTime, Utc};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::collections::HashMap;
 
+/// Prices, quantities and amounts, `rust_decimal::Decimal` with the `decimal` feature and `f64` otherwise
+#[cfg(not(feature = "decimal"))]
+pub type Number = f64;
+/// Prices, quantities and amounts, `rust_decimal::Decimal` with the `decimal` feature and `f64` otherwise
+#[cfg(feature = "decimal")]
+pub type Number = rust_decimal::Decimal;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ServerTime {
@@ -41,18 +48,66 @@ pub struct Symbol {
 }
 
 impl Symbol {
-    pub fn lot_size(&self) -> Option<Filters> {
-        self.filters
-            .iter()
-            .find(|filter| matches!(filter, Filters::LotSize { .. }))
-            .cloned()
+    pub fn price_filter(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::PriceFilter { .. }))
+    }
+
+    pub fn percent_price(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::PercentPrice { .. }))
+    }
+
+    pub fn percent_price_by_side(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::PercentPriceBySide { .. }))
     }
 
+    pub fn lot_size(&self) -> Option<Filters> { self.filter(|filter| matches!(filter, Filters::LotSize { .. })) }
+
     pub fn market_lot_size(&self) -> Option<Filters> {
-        self.filters
-            .iter()
-            .find(|filter| matches!(filter, Filters::MarketLotSize { .. }))
-            .cloned()
+        self.filter(|filter| matches!(filter, Filters::MarketLotSize { .. }))
+    }
+
+    pub fn min_notional(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::MinNotional { .. }))
+    }
+
+    pub fn notional(&self) -> Option<Filters> { self.filter(|filter| matches!(filter, Filters::Notional { .. })) }
+
+    pub fn iceberg_parts(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::IcebergParts { .. }))
+    }
+
+    pub fn max_num_orders(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::MaxNumOrders { .. }))
+    }
+
+    pub fn max_num_algo_orders(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::MaxNumAlgoOrders { .. }))
+    }
+
+    pub fn max_num_iceberg_orders(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::MaxNumIcebergOrders { .. }))
+    }
+
+    pub fn max_position(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::MaxPosition { .. }))
+    }
+
+    pub fn trailing_delta(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::TrailingDelta { .. }))
+    }
+
+    pub fn max_num_order_amends(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::MaxNumOrderAmends { .. }))
+    }
+
+    pub fn max_num_order_lists(&self) -> Option<Filters> {
+        self.filter(|filter| matches!(filter, Filters::MaxNumOrderLists { .. }))
+    }
+
+    pub fn t_plus_sell(&self) -> Option<Filters> { self.filter(|filter| matches!(filter, Filters::TPlusSell { .. })) }
+
+    fn filter(&self, is_filter: impl Fn(&Filters) -> bool) -> Option<Filters> {
+        self.filters.iter().find(|filter| is_filter(filter)).cloned()
     }
 }
 
@@ -63,49 +118,73 @@ pub enum Filters {
     #[serde(rename_all = "camelCase")]
     PriceFilter {
         #[serde(with = "string_or_float")]
-        min_price: f64,
+        min_price: Number,
         #[serde(with = "string_or_float")]
-        max_price: f64,
+        max_price: Number,
         #[serde(with = "string_or_float")]
-        tick_size: f64,
+        tick_size: Number,
     },
     #[serde(rename = "PERCENT_PRICE")]
     #[serde(rename_all = "camelCase")]
     PercentPrice {
         #[serde(with = "string_or_float")]
-        multiplier_up: f64,
+        multiplier_up: Number,
         #[serde(with = "string_or_float")]
-        multiplier_down: f64,
+        multiplier_down: Number,
+        avg_price_mins: u64,
+    },
+    #[serde(rename = "PERCENT_PRICE_BY_SIDE")]
+    #[serde(rename_all = "camelCase")]
+    PercentPriceBySide {
+        #[serde(with = "string_or_float")]
+        bid_multiplier_up: Number,
+        #[serde(with = "string_or_float")]
+        bid_multiplier_down: Number,
+        #[serde(with = "string_or_float")]
+        ask_multiplier_up: Number,
+        #[serde(with = "string_or_float")]
+        ask_multiplier_down: Number,
         avg_price_mins: u64,
     },
     #[serde(rename = "LOT_SIZE")]
     #[serde(rename_all = "camelCase")]
     LotSize {
         #[serde(with = "string_or_float")]
-        min_qty: f64,
+        min_qty: Number,
         #[serde(with = "string_or_float")]
-        max_qty: f64,
+        max_qty: Number,
         #[serde(with = "string_or_float")]
-        step_size: f64,
+        step_size: Number,
     },
     #[serde(rename = "MARKET_LOT_SIZE")]
     #[serde(rename_all = "camelCase")]
     MarketLotSize {
         #[serde(with = "string_or_float")]
-        min_qty: f64,
+        min_qty: Number,
         #[serde(with = "string_or_float")]
-        max_qty: f64,
+        max_qty: Number,
         #[serde(with = "string_or_float")]
-        step_size: f64,
+        step_size: Number,
     },
     #[serde(rename = "MIN_NOTIONAL")]
     #[serde(rename_all = "camelCase")]
     MinNotional {
         #[serde(with = "string_or_float")]
-        min_notional: f64,
+        min_notional: Number,
         apply_to_market: bool,
         avg_price_mins: u64,
     },
+    #[serde(rename = "NOTIONAL")]
+    #[serde(rename_all = "camelCase")]
+    Notional {
+        #[serde(with = "string_or_float")]
+        min_notional: Number,
+        apply_min_to_market: bool,
+        #[serde(with = "string_or_float")]
+        max_notional: Number,
+        apply_max_to_market: bool,
+        avg_price_mins: u64,
+    },
     #[serde(rename = "ICEBERG_PARTS")]
     #[serde(rename_all = "camelCase")]
     IcebergParts { limit: u16 },
@@ -122,14 +201,37 @@ pub enum Filters {
     #[serde(rename_all = "camelCase")]
     MaxPosition {
         #[serde(with = "string_or_float")]
-        max_position: f64,
+        max_position: Number,
+    },
+    #[serde(rename = "TRAILING_DELTA")]
+    #[serde(rename_all = "camelCase")]
+    TrailingDelta {
+        min_trailing_above_delta: u64,
+        max_trailing_above_delta: u64,
+        min_trailing_below_delta: u64,
+        max_trailing_below_delta: u64,
     },
+    #[serde(rename = "MAX_NUM_ORDER_AMENDS")]
+    #[serde(rename_all = "camelCase")]
+    MaxNumOrderAmends { max_num_order_amends: u16 },
+    #[serde(rename = "MAX_NUM_ORDER_LISTS")]
+    #[serde(rename_all = "camelCase")]
+    MaxNumOrderLists { max_num_order_lists: u16 },
+    #[serde(rename = "T_PLUS_SELL")]
+    #[serde(rename_all = "camelCase")]
+    TPlusSell { end_time: Option<u64> },
     #[serde(rename = "EXCHANGE_MAX_NUM_ORDERS")]
     #[serde(rename_all = "camelCase")]
     ExchangeMaxNumOrders { max_num_orders: u16 },
-    #[serde(rename = "EXCHANGE_MAX_ALGO_ORDERS")]
+    #[serde(rename = "EXCHANGE_MAX_NUM_ALGO_ORDERS", alias = "EXCHANGE_MAX_ALGO_ORDERS")]
     #[serde(rename_all = "camelCase")]
     ExchangeMaxNumAlgoOrders { max_num_algo_orders: u16 },
+    #[serde(rename = "EXCHANGE_MAX_NUM_ICEBERG_ORDERS")]
+    #[serde(rename_all = "camelCase")]
+    ExchangeMaxNumIcebergOrders { max_num_iceberg_orders: u16 },
+    #[serde(rename = "EXCHANGE_MAX_NUM_ORDER_LISTS")]
+    #[serde(rename_all = "camelCase")]
+    ExchangeMaxNumOrderLists { max_num_order_lists: u16 },
     #[serde(other)]
     Others,
 }
@@ -175,9 +277,9 @@ pub enum AccountType {
 pub struct Balance {
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub free: f64,
+    pub free: Number,
     #[serde(with = "string_or_float")]
-    pub locked: f64,
+    pub locked: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -188,27 +290,27 @@ pub struct Order {
     pub order_list_id: i32,
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Number,
     pub status: OrderStatus,
     pub time_in_force: TimeInForce,
     #[serde(rename = "type")]
     pub order_type: OrderType,
     pub side: OrderSide,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Number,
     #[serde(with = "string_or_float")]
-    pub iceberg_qty: f64,
+    pub iceberg_qty: Number,
     pub time: u64,
     pub update_time: u64,
     pub is_working: bool,
     #[serde(with = "string_or_float")]
-    pub orig_quote_order_qty: f64,
+    pub orig_quote_order_qty: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -233,11 +335,11 @@ pub struct OrderCanceledReplaced {
 #[serde(rename_all = "camelCase")]
 pub struct Fill {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
     #[serde(with = "string_or_float")]
-    pub commission: f64,
+    pub commission: Number,
     pub commission_asset: String,
 }
 
@@ -249,13 +351,13 @@ pub struct Transaction {
     pub client_order_id: String,
     pub transact_time: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Number,
     pub status: OrderStatus,
     pub time_in_force: TimeInForce,
     #[serde(rename = "type")]
@@ -264,6 +366,27 @@ pub struct Transaction {
     pub fills: Vec<Fill>,
 }
 
+/// An order found after placing it, fills are not known
+impl From<Order> for Transaction {
+    fn from(order: Order) -> Self {
+        Self {
+            symbol: order.symbol,
+            order_id: order.order_id,
+            client_order_id: order.client_order_id,
+            transact_time: order.time,
+            price: order.price,
+            orig_qty: order.orig_qty,
+            executed_qty: order.executed_qty,
+            cummulative_quote_qty: order.cummulative_quote_qty,
+            status: order.status,
+            time_in_force: order.time_in_force,
+            order_type: order.order_type,
+            side: order.side,
+            fills: vec![],
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionId {
@@ -289,17 +412,17 @@ pub struct OrderBook {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Bids {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Asks {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -322,14 +445,14 @@ pub enum Prices {
 pub struct SymbolPrice {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AveragePrice {
     pub mins: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -339,23 +462,18 @@ pub enum BookTickers {
     AllBookTickers(Vec<Tickers>),
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub enum KlineSummaries {
-    AllKlineSummaries(Vec<KlineSummary>),
-}
-
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Tickers {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub bid_price: f64,
+    pub bid_price: Number,
     #[serde(with = "string_or_float")]
-    pub bid_qty: f64,
+    pub bid_qty: Number,
     #[serde(with = "string_or_float")]
-    pub ask_price: f64,
+    pub ask_price: Number,
     #[serde(with = "string_or_float")]
-    pub ask_qty: f64,
+    pub ask_qty: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -363,9 +481,9 @@ pub struct Tickers {
 pub struct TradeHistory {
     pub id: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
     pub commission: String,
     pub commission_asset: String,
     pub time: u64,
@@ -381,21 +499,21 @@ pub struct PriceStats {
     pub price_change_percent: String,
     pub weighted_avg_price: String,
     #[serde(with = "string_or_float")]
-    pub prev_close_price: f64,
+    pub prev_close_price: Number,
     #[serde(with = "string_or_float")]
-    pub last_price: f64,
+    pub last_price: Number,
     #[serde(with = "string_or_float")]
-    pub bid_price: f64,
+    pub bid_price: Number,
     #[serde(with = "string_or_float")]
-    pub ask_price: f64,
+    pub ask_price: Number,
     #[serde(with = "string_or_float")]
-    pub open_price: f64,
+    pub open_price: Number,
     #[serde(with = "string_or_float")]
-    pub high_price: f64,
+    pub high_price: Number,
     #[serde(with = "string_or_float")]
-    pub low_price: f64,
+    pub low_price: Number,
     #[serde(with = "string_or_float")]
-    pub volume: f64,
+    pub volume: Number,
     pub open_time: u64,
     pub close_time: u64,
     pub first_id: u64,
@@ -418,9 +536,9 @@ pub struct AggTrade {
     #[serde(rename = "M")]
     pub best_match: bool,
     #[serde(rename = "p", with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(rename = "q", with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
 }
 
 #[derive(Debug, Serialize_repr, Deserialize_repr, Clone, PartialEq, Eq)]
@@ -434,7 +552,7 @@ pub enum MarginTransferType {
 #[serde(rename_all = "camelCase")]
 pub struct Transfer {
     pub asset: String,
-    pub amount: f64,
+    pub amount: Number,
     #[serde(rename(serialize = "type", deserialize = "type"))]
     pub transfer_type: MarginTransferType,
 }
@@ -451,7 +569,7 @@ pub enum IsolatedMarginTransferType {
 pub struct IsolatedTransfer {
     pub asset: String,
     pub symbol: String,
-    pub amount: f64,
+    pub amount: Number,
     pub trans_from: IsolatedMarginTransferType,
     pub trans_to: IsolatedMarginTransferType,
 }
@@ -460,7 +578,7 @@ pub struct IsolatedTransfer {
 #[serde(rename_all = "camelCase")]
 pub struct Loan {
     pub asset: String,
-    pub amount: f64,
+    pub amount: Number,
     pub is_isolated: Option<String>,
     pub symbol: Option<String>,
 }
@@ -509,9 +627,7 @@ pub enum OrderSide {
 
 /// By default, buy
 impl Default for OrderSide {
-    fn default() -> Self {
-        Self::Buy
-    }
+    fn default() -> Self { Self::Buy }
 }
 
 /// The allowed values are:
@@ -526,9 +642,7 @@ pub enum CancelReplaceMode {
 
 /// By default, STOP_ON_FAILURE
 impl Default for CancelReplaceMode {
-    fn default() -> Self {
-        Self::StopOnFailure
-    }
+    fn default() -> Self { Self::StopOnFailure }
 }
 
 /// Order types, the following restrictions apply
@@ -555,9 +669,7 @@ pub enum OrderType {
 
 /// By default, use market orders
 impl Default for OrderType {
-    fn default() -> Self {
-        Self::Market
-    }
+    fn default() -> Self { Self::Market }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -567,14 +679,14 @@ pub struct MarginOrder {
     pub side: OrderSide,
     #[serde(rename(serialize = "type", deserialize = "type"))]
     pub order_type: OrderType,
-    pub quantity: Option<f64>,
-    pub quote_order_qty: Option<f64>,
-    pub price: Option<f64>,
+    pub quantity: Option<Number>,
+    pub quote_order_qty: Option<Number>,
+    pub price: Option<Number>,
     /// Used with `OrderType::StopLoss`, `OrderType::StopLossLimit`, `OrderType::TakeProfit` and `OrderType::TakeProfitLimit`
-    pub stop_price: Option<f64>,
+    pub stop_price: Option<Number>,
     pub new_client_order_id: Option<String>,
     /// Used with `OrderType::Limit`, `OrderType::StopLossLimit` and `OrderType::TakeProfitLimit` to create an iceberg order
-    pub iceberg_qty: Option<f64>,
+    pub iceberg_qty: Option<Number>,
     /// Default is `OrderResponse::ACK`
     pub new_order_resp_type: OrderResponse,
     /// N.B. : do not set with `OrderType::Market`
@@ -603,13 +715,13 @@ pub struct MarginOrderCancellationResult {
     pub orig_client_order_id: Option<String>,
     pub client_order_id: Option<String>,
     #[serde(with = "string_or_float_opt")]
-    pub price: Option<f64>,
+    pub price: Option<Number>,
     #[serde(with = "string_or_float_opt")]
-    pub orig_qty: Option<f64>,
+    pub orig_qty: Option<Number>,
     #[serde(with = "string_or_float_opt")]
-    pub executed_qty: Option<f64>,
+    pub executed_qty: Option<Number>,
     #[serde(with = "string_or_float_opt")]
-    pub cummulative_quote_qty: Option<f64>,
+    pub cummulative_quote_qty: Option<Number>,
     pub status: Option<OrderStatus>,
     pub time_in_force: Option<TimeInForce>,
     #[serde(rename(serialize = "type", deserialize = "type"))]
@@ -641,16 +753,16 @@ pub struct MarginOCOOrder {
     /// A unique identifier that will be applied to all orders
     pub list_client_order_id: Option<String>,
     pub side: OrderSide,
-    pub quantity: f64,
+    pub quantity: Number,
     /// A unique identifier that will be applied to the limit order
     pub limit_client_order_id: Option<String>,
-    pub price: f64,
-    pub limit_iceberg_qty: Option<f64>,
+    pub price: Number,
+    pub limit_iceberg_qty: Option<Number>,
     /// A unique identifier that will be applied to the stop order
     pub stop_client_order_id: Option<String>,
-    pub stop_price: f64,
-    pub stop_limit_price: Option<f64>,
-    pub stop_iceberg_qty: Option<f64>,
+    pub stop_price: Number,
+    pub stop_limit_price: Option<Number>,
+    pub stop_iceberg_qty: Option<Number>,
     pub stop_limit_time_in_force: Option<TimeInForce>,
     /// Default is `OrderResponse::ACK`
     pub new_order_resp_type: Option<OrderResponse>,
@@ -669,7 +781,7 @@ pub struct MarginOCOOrderResult {
     pub transaction_time: u128,
     pub symbol: String,
     #[serde(default, with = "string_or_float_opt")]
-    pub margin_buy_borrow_amount: Option<f64>,
+    pub margin_buy_borrow_amount: Option<Number>,
     pub margin_buy_borrow_asset: Option<String>,
     pub is_isolated: Option<bool>,
     pub orders: Vec<OCOOrderDetail>,
@@ -692,22 +804,39 @@ pub struct OCOOrderReport {
     pub client_order_id: Option<String>,
     pub transact_time: u128,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Number,
     pub status: OrderStatus,
     pub time_in_force: TimeInForce,
     #[serde(rename = "type")]
     pub order_type: OrderType,
     pub side: OrderSide,
     #[serde(default, with = "string_or_float_opt")]
-    pub stop_price: Option<f64>,
+    pub stop_price: Option<Number>,
     #[serde(default, with = "string_or_float_opt")]
-    pub iceberg_qty: Option<f64>,
+    pub iceberg_qty: Option<Number>,
+}
+
+/// A spot order list, such as an OCO
+/// `order_reports` are only returned when placing or canceling the list
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderList {
+    pub order_list_id: u64,
+    pub contingency_type: ContingencyType,
+    pub list_status_type: OCOStatus,
+    pub list_order_status: OCOOrderStatus,
+    pub list_client_order_id: String,
+    pub transaction_time: u64,
+    pub symbol: String,
+    pub orders: Vec<OCOOrderDetail>,
+    #[serde(default)]
+    pub order_reports: Vec<OCOOrderReport>,
 }
 
 /// archived and is_isolated are only applicable to certain endpoints
@@ -782,12 +911,12 @@ pub struct IsolatedTransfersQuery {
 #[serde(rename_all = "camelCase")]
 pub struct RepayState {
     #[serde(with = "string_or_float")]
-    pub amount: f64,
+    pub amount: Number,
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub interest: f64,
+    pub interest: Number,
     #[serde(with = "string_or_float")]
-    pub principal: f64,
+    pub principal: Number,
     pub status: TransactionStatus,
     pub timestamp: u64,
     pub tx_id: u64,
@@ -809,7 +938,7 @@ pub enum TransactionStatus {
 pub struct LoanState {
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub principal: f64,
+    pub principal: Number,
     pub timestamp: u64,
     pub status: TransactionStatus,
     pub isolated_symbol: Option<String>,
@@ -830,7 +959,7 @@ pub enum TransferType {
 #[serde(rename_all = "camelCase")]
 pub struct OrderState {
     #[serde(with = "string_or_float")]
-    pub amount: f64,
+    pub amount: Number,
     pub asset: String,
     pub status: TransactionStatus,
     pub timestamp: u64,
@@ -859,12 +988,12 @@ pub enum InterestType {
 pub struct InterestState {
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub interest: f64,
+    pub interest: Number,
     pub interest_accured_time: u64,
     #[serde(with = "string_or_float")]
-    pub interest_rate: f64,
+    pub interest_rate: Number,
     #[serde(with = "string_or_float")]
-    pub principal: f64,
+    pub principal: Number,
     #[serde(rename = "type")]
     pub interest_type: InterestType,
     pub isolated_symbol: Option<String>,
@@ -874,14 +1003,14 @@ pub struct InterestState {
 #[serde(rename_all = "camelCase")]
 pub struct ForcedLiquidationState {
     #[serde(with = "string_or_float")]
-    pub avg_price: f64,
+    pub avg_price: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     pub order_id: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
     pub side: OrderSide,
     pub symbol: String,
     pub time_in_force: TimeInForce,
@@ -901,15 +1030,15 @@ pub struct RecordsQueryResult<R> {
 pub struct UserAsset {
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub borrowed: f64,
+    pub borrowed: Number,
     #[serde(with = "string_or_float")]
-    pub free: f64,
+    pub free: Number,
     #[serde(with = "string_or_float")]
-    pub interest: f64,
+    pub interest: Number,
     #[serde(with = "string_or_float")]
-    pub locked: f64,
+    pub locked: Number,
     #[serde(with = "string_or_float")]
-    pub net_asset: f64,
+    pub net_asset: Number,
 }
 
 pub type UserAssets = Vec<UserAsset>;
@@ -919,13 +1048,13 @@ pub type UserAssets = Vec<UserAsset>;
 pub struct MarginAccountDetails {
     pub borrow_enabled: bool,
     #[serde(with = "string_or_float")]
-    pub margin_level: f64,
+    pub margin_level: Number,
     #[serde(with = "string_or_float")]
-    pub total_asset_of_btc: f64,
+    pub total_asset_of_btc: Number,
     #[serde(with = "string_or_float")]
-    pub total_liability_of_btc: f64,
+    pub total_liability_of_btc: Number,
     #[serde(with = "string_or_float")]
-    pub total_net_asset_of_btc: f64,
+    pub total_net_asset_of_btc: Number,
     pub trade_enabled: bool,
     pub transfer_enabled: bool,
     pub user_assets: UserAssets,
@@ -937,20 +1066,20 @@ pub struct IsolatedMarginAccountAsset {
     pub asset: String,
     pub borrow_enabled: bool,
     #[serde(with = "string_or_float")]
-    pub borrowed: f64,
+    pub borrowed: Number,
     #[serde(with = "string_or_float")]
-    pub free: f64,
+    pub free: Number,
     #[serde(with = "string_or_float")]
-    pub interest: f64,
+    pub interest: Number,
     #[serde(with = "string_or_float")]
-    pub locked: f64,
+    pub locked: Number,
     #[serde(with = "string_or_float")]
-    pub net_asset: f64,
+    pub net_asset: Number,
     #[serde(with = "string_or_float")]
-    pub net_asset_of_btc: f64,
+    pub net_asset_of_btc: Number,
     pub repay_enabled: bool,
     #[serde(with = "string_or_float")]
-    pub total_asset: f64,
+    pub total_asset: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -962,16 +1091,16 @@ pub struct IsolatedMarginAccountAssetDetails {
     pub isolated_created: bool,
     pub enabled: bool,
     #[serde(with = "string_or_float")]
-    pub margin_level: f64,
+    pub margin_level: Number,
     #[serde(with = "string_or_float")]
-    pub margin_ratio: f64,
+    pub margin_ratio: Number,
     pub margin_level_status: MarginLevelStatus,
     #[serde(with = "string_or_float")]
-    pub index_price: f64,
+    pub index_price: Number,
     #[serde(with = "string_or_float")]
-    pub liquidate_price: f64,
+    pub liquidate_price: Number,
     #[serde(with = "string_or_float")]
-    pub liquidate_rate: f64,
+    pub liquidate_rate: Number,
     pub trade_enabled: bool,
 }
 
@@ -992,11 +1121,11 @@ pub enum MarginLevelStatus {
 pub struct IsolatedMarginAccountDetails {
     pub assets: Vec<IsolatedMarginAccountAssetDetails>,
     #[serde(default, with = "string_or_float_opt")]
-    pub total_asset_of_btc: Option<f64>,
+    pub total_asset_of_btc: Option<Number>,
     #[serde(default, with = "string_or_float_opt")]
-    pub total_liability_of_btc: Option<f64>,
+    pub total_liability_of_btc: Option<Number>,
     #[serde(default, with = "string_or_float_opt")]
-    pub total_net_asset_of_btc: Option<f64>,
+    pub total_net_asset_of_btc: Option<Number>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1020,9 +1149,9 @@ pub struct AssetDetails {
     pub is_borrowable: bool,
     pub is_mortgageable: bool,
     #[serde(with = "string_or_float")]
-    pub user_min_borrow: f64,
+    pub user_min_borrow: Number,
     #[serde(with = "string_or_float")]
-    pub user_min_repay: f64,
+    pub user_min_repay: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1085,7 +1214,7 @@ pub type AllIsolatedPairs = Vec<IsolatedPairDetails>;
 pub struct PriceIndex {
     pub calc_time: u128,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     pub symbol: String,
 }
 
@@ -1106,20 +1235,20 @@ pub struct MarginOrderResult {
     pub client_order_id: String,
     pub transact_time: u128,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Number,
     pub status: OrderStatus,
     pub time_in_force: TimeInForce,
     #[serde(rename(serialize = "type", deserialize = "type"))]
     pub order_type: OrderType,
     pub side: OrderSide,
     #[serde(default, with = "string_or_float_opt")]
-    pub margin_buy_borrow_amount: Option<f64>,
+    pub margin_buy_borrow_amount: Option<Number>,
     pub margin_buy_borrow_asset: Option<String>,
     pub is_isolated: Option<bool>,
     pub fills: Vec<Fill>,
@@ -1130,21 +1259,21 @@ pub struct MarginOrderResult {
 pub struct MarginOrderState {
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     #[serde(with = "string_or_float")]
-    pub iceberg_qty: f64,
+    pub iceberg_qty: Number,
     pub is_working: bool,
     pub order_id: u64,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     pub side: OrderSide,
     pub status: OrderStatus,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Number,
     pub symbol: String,
     pub is_isolated: Option<bool>,
     pub time: u64,
@@ -1158,9 +1287,9 @@ pub struct MarginOrderState {
 #[serde(rename_all = "camelCase")]
 pub struct OrderSumaryState {
     pub id: u64,
-    pub price: f64,
-    pub qty: f64,
-    pub quote_qty: f64,
+    pub price: Number,
+    pub qty: Number,
+    pub quote_qty: Number,
     pub symbol: String,
     pub time: u128,
 }
@@ -1169,7 +1298,7 @@ pub struct OrderSumaryState {
 #[serde(rename_all = "camelCase")]
 pub struct OwnTradesState {
     #[serde(with = "string_or_float")]
-    pub commission: f64,
+    pub commission: Number,
     pub commission_asset: String,
     pub id: u64,
     pub is_best_match: bool,
@@ -1177,9 +1306,9 @@ pub struct OwnTradesState {
     pub is_maker: bool,
     pub order_id: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
     pub symbol: String,
     pub time: u128,
     pub is_isolated: bool,
@@ -1189,16 +1318,16 @@ pub struct OwnTradesState {
 #[serde(rename_all = "camelCase")]
 pub struct MaxBorrowableAmount {
     #[serde(with = "string_or_float")]
-    pub amount: f64,
+    pub amount: Number,
     #[serde(with = "string_or_float")]
-    pub borrow_limit: f64,
+    pub borrow_limit: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct MaxTransferableAmount {
     #[serde(with = "string_or_float")]
-    pub amount: f64,
+    pub amount: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -1252,6 +1381,8 @@ pub enum OrderStatus {
 pub enum OCOStatus {
     Response,
     ExecStarted,
+    /// A leg of the list was updated, e.g. triggered
+    Updated,
     AllDone,
 }
 
@@ -1285,7 +1416,11 @@ pub struct MarginOCOOrderQuery {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ContingencyType {
+    #[serde(rename = "OCO")]
     OCO,
+    /// OTO and OTOCO order lists
+    #[serde(rename = "OTO")]
+    OTO,
     #[serde(other)]
     Other,
 }
@@ -1318,7 +1453,7 @@ pub enum RateLimitInterval {
     Day,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct RateLimit {
     pub interval: RateLimitInterval,
@@ -1361,27 +1496,203 @@ pub struct InterestRateHistoryQuery {
 pub struct InterestRateAssetHistory {
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub daily_interest_rate: f64,
+    pub daily_interest_rate: Number,
     pub timestamp: u128,
     pub vip_level: u8,
 }
 
 pub type InterestRateHistory = Vec<InterestRateAssetHistory>;
 
+/// A kline, (de)serialized as the array rows of the REST and WebSocket APIs
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-#[serde(rename_all = "camelCase")]
+#[serde(from = "KlineRow", into = "KlineRow")]
 pub struct KlineSummary {
     pub open_time: i64,
-    pub open: f64,
-    pub high: f64,
-    pub low: f64,
-    pub close: f64,
-    pub volume: f64,
+    pub open: Number,
+    pub high: Number,
+    pub low: Number,
+    pub close: Number,
+    pub volume: Number,
     pub close_time: i64,
-    pub quote_asset_volume: f64,
+    pub quote_asset_volume: Number,
     pub number_of_trades: i64,
-    pub taker_buy_base_asset_volume: f64,
-    pub taker_buy_quote_asset_volume: f64,
+    pub taker_buy_base_asset_volume: Number,
+    pub taker_buy_quote_asset_volume: Number,
+    /// Unused column of the rows
+    pub ignore: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KlineRow(
+    i64,
+    #[serde(with = "string_or_float")] Number,
+    #[serde(with = "string_or_float")] Number,
+    #[serde(with = "string_or_float")] Number,
+    #[serde(with = "string_or_float")] Number,
+    #[serde(with = "string_or_float")] Number,
+    i64,
+    #[serde(with = "string_or_float")] Number,
+    i64,
+    #[serde(with = "string_or_float")] Number,
+    #[serde(with = "string_or_float")] Number,
+    #[serde(default)] String,
+);
+
+impl From<KlineRow> for KlineSummary {
+    fn from(row: KlineRow) -> Self {
+        KlineSummary {
+            open_time: row.0,
+            open: row.1,
+            high: row.2,
+            low: row.3,
+            close: row.4,
+            volume: row.5,
+            close_time: row.6,
+            quote_asset_volume: row.7,
+            number_of_trades: row.8,
+            taker_buy_base_asset_volume: row.9,
+            taker_buy_quote_asset_volume: row.10,
+            ignore: row.11,
+        }
+    }
+}
+
+impl From<KlineSummary> for KlineRow {
+    fn from(kline: KlineSummary) -> Self {
+        KlineRow(
+            kline.open_time,
+            kline.open,
+            kline.high,
+            kline.low,
+            kline.close,
+            kline.volume,
+            kline.close_time,
+            kline.quote_asset_volume,
+            kline.number_of_trades,
+            kline.taker_buy_base_asset_volume,
+            kline.taker_buy_quote_asset_volume,
+            kline.ignore,
+        )
+    }
+}
+
+/// Kline and candlestick intervals
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KlineInterval {
+    #[serde(rename = "1s")]
+    OneSecond,
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "3m")]
+    ThreeMinutes,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "15m")]
+    FifteenMinutes,
+    #[serde(rename = "30m")]
+    ThirtyMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "2h")]
+    TwoHours,
+    #[serde(rename = "4h")]
+    FourHours,
+    #[serde(rename = "6h")]
+    SixHours,
+    #[serde(rename = "8h")]
+    EightHours,
+    #[serde(rename = "12h")]
+    TwelveHours,
+    #[serde(rename = "1d")]
+    OneDay,
+    #[serde(rename = "3d")]
+    ThreeDays,
+    #[serde(rename = "1w")]
+    OneWeek,
+    #[serde(rename = "1M")]
+    OneMonth,
+}
+
+impl KlineInterval {
+    pub const ALL: [KlineInterval; 16] = [
+        KlineInterval::OneSecond,
+        KlineInterval::OneMinute,
+        KlineInterval::ThreeMinutes,
+        KlineInterval::FiveMinutes,
+        KlineInterval::FifteenMinutes,
+        KlineInterval::ThirtyMinutes,
+        KlineInterval::OneHour,
+        KlineInterval::TwoHours,
+        KlineInterval::FourHours,
+        KlineInterval::SixHours,
+        KlineInterval::EightHours,
+        KlineInterval::TwelveHours,
+        KlineInterval::OneDay,
+        KlineInterval::ThreeDays,
+        KlineInterval::OneWeek,
+        KlineInterval::OneMonth,
+    ];
+
+    /// The interval as written in requests and stream names, e.g. "1m"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KlineInterval::OneSecond => "1s",
+            KlineInterval::OneMinute => "1m",
+            KlineInterval::ThreeMinutes => "3m",
+            KlineInterval::FiveMinutes => "5m",
+            KlineInterval::FifteenMinutes => "15m",
+            KlineInterval::ThirtyMinutes => "30m",
+            KlineInterval::OneHour => "1h",
+            KlineInterval::TwoHours => "2h",
+            KlineInterval::FourHours => "4h",
+            KlineInterval::SixHours => "6h",
+            KlineInterval::EightHours => "8h",
+            KlineInterval::TwelveHours => "12h",
+            KlineInterval::OneDay => "1d",
+            KlineInterval::ThreeDays => "3d",
+            KlineInterval::OneWeek => "1w",
+            KlineInterval::OneMonth => "1M",
+        }
+    }
+
+    /// The duration of a kline in ms, `None` for months which vary in length
+    pub fn duration_ms(&self) -> Option<u64> {
+        const MINUTE: u64 = 60_000;
+        let minutes = match self {
+            KlineInterval::OneSecond => return Some(1000),
+            KlineInterval::OneMinute => 1,
+            KlineInterval::ThreeMinutes => 3,
+            KlineInterval::FiveMinutes => 5,
+            KlineInterval::FifteenMinutes => 15,
+            KlineInterval::ThirtyMinutes => 30,
+            KlineInterval::OneHour => 60,
+            KlineInterval::TwoHours => 2 * 60,
+            KlineInterval::FourHours => 4 * 60,
+            KlineInterval::SixHours => 6 * 60,
+            KlineInterval::EightHours => 8 * 60,
+            KlineInterval::TwelveHours => 12 * 60,
+            KlineInterval::OneDay => 24 * 60,
+            KlineInterval::ThreeDays => 3 * 24 * 60,
+            KlineInterval::OneWeek => 7 * 24 * 60,
+            KlineInterval::OneMonth => return None,
+        };
+        Some(minutes * MINUTE)
+    }
+}
+
+impl std::fmt::Display for KlineInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl std::str::FromStr for KlineInterval {
+    type Err = crate::errors::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        KlineInterval::ALL
+            .into_iter()
+            .find(|interval| interval.as_str() == s)
+            .ok_or_else(|| crate::errors::Error::InvalidPeriod(s.to_string()))
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -1417,24 +1728,24 @@ pub struct WalletCoinInfo {
     pub coin: String,
     pub deposit_all_enable: bool,
     #[serde(with = "string_or_float")]
-    pub free: f64,
+    pub free: Number,
     #[serde(with = "string_or_float")]
-    pub freeze: f64,
+    pub freeze: Number,
     #[serde(with = "string_or_float")]
-    pub ipoable: f64,
+    pub ipoable: Number,
     #[serde(with = "string_or_float")]
-    pub ipoing: f64,
+    pub ipoing: Number,
     pub is_legal_money: bool,
     #[serde(with = "string_or_float")]
-    pub locked: f64,
+    pub locked: Number,
     pub name: String,
     pub network_list: Vec<CoinNetwork>,
     #[serde(with = "string_or_float")]
-    pub storage: f64,
+    pub storage: Number,
     pub trading: bool,
     pub withdraw_all_enable: bool,
     #[serde(with = "string_or_float")]
-    pub withdrawing: f64,
+    pub withdrawing: Number,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -1457,13 +1768,13 @@ pub struct CoinNetwork {
     pub withdraw_desc: String,
     pub withdraw_enable: bool,
     #[serde(with = "string_or_float")]
-    pub withdraw_fee: f64,
+    pub withdraw_fee: Number,
     #[serde(with = "string_or_float")]
-    pub withdraw_integer_multiple: f64,
+    pub withdraw_integer_multiple: Number,
     #[serde(with = "string_or_float")]
-    pub withdraw_max: f64,
+    pub withdraw_max: Number,
     #[serde(with = "string_or_float")]
-    pub withdraw_min: f64,
+    pub withdraw_min: Number,
     #[serde(default)]
     pub same_address: bool,
 }
@@ -1490,7 +1801,7 @@ pub struct SnapshotVos {
 pub struct SnapshotVosData {
     pub balances: Vec<Balance>,
     #[serde(with = "string_or_float")]
-    pub total_asset_of_btc: f64,
+    pub total_asset_of_btc: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -1521,7 +1832,7 @@ pub struct CoinWithdrawalQuery {
     pub address: String,
     /// Secondary address identifier for coins like XRP,XMR etc.
     pub address_tag: Option<String>,
-    pub amount: f64,
+    pub amount: Number,
     /// When making internal transfer, true for returning the fee to the destination account; false for returning the fee back to the departure account. Default false.
     pub transaction_fee_flag: Option<bool>,
     /// Description of the address. Space in name should be encoded into %20.
@@ -1549,9 +1860,11 @@ pub struct DepositHistoryQuery {
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct DepositRecord {
+    #[serde(default)]
+    pub id: String,
     pub coin: String,
     #[serde(with = "string_or_float")]
-    pub amount: f64,
+    pub amount: Number,
     pub network: String,
     pub status: u8,
     pub address: String,
@@ -1595,7 +1908,7 @@ pub struct RecordHistory<T> {
 pub struct WithdrawalRecord {
     pub address: String,
     #[serde(with = "string_or_float")]
-    pub amount: f64,
+    pub amount: Number,
     pub apply_time: String,
     pub coin: String,
     pub id: String,
@@ -1606,7 +1919,7 @@ pub struct WithdrawalRecord {
     pub transfer_type: u8,
     pub status: u8,
     #[serde(with = "string_or_float")]
-    pub transaction_fee: f64,
+    pub transaction_fee: Number,
     /// // confirm times for withdraw
     pub confirm_no: Option<u64>,
     pub info: Option<String>,
@@ -1683,7 +1996,7 @@ pub enum UniversalTransferType {
 #[serde(rename_all = "camelCase")]
 pub struct UniversalTransfer {
     pub asset: String,
-    pub amount: f64,
+    pub amount: Number,
     pub from_symbol: Option<String>,
     pub to_symbol: Option<String>,
     #[serde(rename(serialize = "type", deserialize = "type"))]
@@ -1718,7 +2031,7 @@ pub enum UniversalTransferStatus {
 pub struct UniversalTransferRecord {
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub amount: f64,
+    pub amount: Number,
     #[serde(rename(serialize = "type", deserialize = "type"))]
     pub transfer_type: UniversalTransferType,
     pub status: UniversalTransferStatus,
@@ -1774,10 +2087,10 @@ pub struct UserAssetDribblet {
     pub operate_time: u64,
     /// Total transfered BNB amount for this exchange.
     #[serde(with = "string_or_float")]
-    pub total_transfered_amount: f64,
+    pub total_transfered_amount: Number,
     ///Total service charge amount for this exchange.
     #[serde(with = "string_or_float")]
-    pub total_service_charge_amount: f64,
+    pub total_service_charge_amount: Number,
     pub trans_id: u64,
     pub user_asset_dribblet_details: Vec<UserAssetDribbletDetail>,
 }
@@ -1787,11 +2100,11 @@ pub struct UserAssetDribblet {
 pub struct UserAssetDribbletDetail {
     pub trans_id: u64,
     #[serde(with = "string_or_float")]
-    pub amount: f64,
+    pub amount: Number,
     #[serde(with = "string_or_float")]
-    pub transfered_amount: f64,
+    pub transfered_amount: Number,
     #[serde(with = "string_or_float")]
-    pub service_charge_amount: f64,
+    pub service_charge_amount: Number,
     pub operate_time: u64,
     pub from_asset: String,
 }
@@ -1802,12 +2115,12 @@ pub struct ConvertibleAssets {
     pub details: Vec<ConvertibleAssetDetails>,
     #[serde(with = "string_or_float")]
     #[serde(rename = "totalTransferBtc")]
-    pub total_transfer_btc: f64,
+    pub total_transfer_btc: Number,
     #[serde(with = "string_or_float")]
     #[serde(rename = "totalTransferBNB")]
-    pub total_transfer_bnb: f64,
+    pub total_transfer_bnb: Number,
     #[serde(with = "string_or_float_opt", default)]
-    pub driblet_percentage: Option<f64>,
+    pub driblet_percentage: Option<Number>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1816,27 +2129,27 @@ pub struct ConvertibleAssetDetails {
     pub asset: String,
     pub asset_full_name: String,
     #[serde(with = "string_or_float")]
-    pub amount_free: f64,
+    pub amount_free: Number,
     #[serde(with = "string_or_float")]
     #[serde(rename = "toBNB")]
-    pub to_bnb: f64,
+    pub to_bnb: Number,
     #[serde(with = "string_or_float")]
     #[serde(rename = "toBTC")]
-    pub to_btc: f64,
+    pub to_btc: Number,
     #[serde(with = "string_or_float")]
     #[serde(rename = "toBNBOffExchange")]
-    pub to_bnb_off_exchange: f64,
+    pub to_bnb_off_exchange: Number,
     #[serde(with = "string_or_float")]
-    pub exchange: f64,
+    pub exchange: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DustTransfer {
     #[serde(with = "string_or_float")]
-    pub total_service_charge: f64,
+    pub total_service_charge: Number,
     #[serde(with = "string_or_float")]
-    pub total_transferred: f64,
+    pub total_transferred: Number,
     pub transfer_result: Vec<DustTransferResult>,
 }
 
@@ -1844,14 +2157,14 @@ pub struct DustTransfer {
 #[serde(rename_all = "camelCase")]
 pub struct DustTransferResult {
     #[serde(with = "string_or_float")]
-    pub amount: f64,
+    pub amount: Number,
     pub from_asset: String,
     pub operate_time: u64,
     #[serde(with = "string_or_float")]
-    pub service_charge_amount: f64,
+    pub service_charge_amount: Number,
     pub tran_id: u64,
     #[serde(with = "string_or_float")]
-    pub transfered_amount: f64,
+    pub transfered_amount: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1859,7 +2172,7 @@ pub struct DustTransferResult {
 pub struct AssetDividend {
     pub id: u64,
     #[serde(with = "string_or_float")]
-    pub amount: f64,
+    pub amount: Number,
     pub asset: String,
     pub div_time: u64,
     pub en_info: String,
@@ -1884,12 +2197,12 @@ pub struct SupportedAssetDetail {
     /// min withdraw amount
     #[serde(with = "string_or_float_opt")]
     #[serde(rename = "minWithdrawAmount")]
-    pub min_withdrawal_amount: Option<f64>,
+    pub min_withdrawal_amount: Option<Number>,
     /// deposit status (false if ALL of networks' are false)
     pub deposit_status: bool,
     /// withdraw fee
     #[serde(with = "string_or_float_opt")]
-    pub withdraw_fee: Option<f64>,
+    pub withdraw_fee: Option<Number>,
     /// withdraw status (false if ALL of networks' are false)
     pub withdraw_status: bool,
     /// reason
@@ -1903,9 +2216,9 @@ pub type TradeFees = Vec<TradeFee>;
 pub struct TradeFee {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub maker_commission: f64,
+    pub maker_commission: Number,
     #[serde(with = "string_or_float")]
-    pub taker_commission: f64,
+    pub taker_commission: Number,
 }
 
 pub type WalletFundings = Vec<WalletFunding>;
@@ -1915,15 +2228,15 @@ pub type WalletFundings = Vec<WalletFunding>;
 pub struct WalletFunding {
     pub asset: String,
     #[serde(with = "string_or_float")]
-    pub free: f64,
+    pub free: Number,
     #[serde(with = "string_or_float")]
-    pub locked: f64,
+    pub locked: Number,
     #[serde(with = "string_or_float")]
-    pub freeze: f64,
+    pub freeze: Number,
     #[serde(with = "string_or_float")]
-    pub withdrawing: f64,
+    pub withdrawing: Number,
     #[serde(with = "string_or_float")]
-    pub btc_valuation: f64,
+    pub btc_valuation: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1955,6 +2268,8 @@ pub mod string_or_float {
 
     use serde::{de, Deserialize, Deserializer, Serializer};
 
+    use super::Number;
+
     pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
     where
         T: fmt::Display,
@@ -1963,7 +2278,7 @@ pub mod string_or_float {
         serializer.collect_str(value)
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<f64, D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Number, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -1975,16 +2290,32 @@ pub mod string_or_float {
         }
 
         match StringOrFloat::deserialize(deserializer)? {
-            StringOrFloat::String(s) => s.parse().map_err(de::Error::custom),
-            StringOrFloat::Float(i) => Ok(i),
+            StringOrFloat::String(s) => parse(&s).map_err(de::Error::custom),
+            StringOrFloat::Float(i) => from_f64(i).map_err(de::Error::custom),
         }
     }
+
+    #[cfg(not(feature = "decimal"))]
+    pub(super) fn parse(s: &str) -> Result<Number, std::num::ParseFloatError> { s.parse() }
+
+    #[cfg(feature = "decimal")]
+    pub(super) fn parse(s: &str) -> Result<Number, rust_decimal::Error> {
+        s.parse().or_else(|_| Number::from_scientific(s))
+    }
+
+    #[cfg(not(feature = "decimal"))]
+    pub(super) fn from_f64(f: f64) -> Result<Number, String> { Ok(f) }
+
+    #[cfg(feature = "decimal")]
+    pub(super) fn from_f64(f: f64) -> Result<Number, rust_decimal::Error> { Number::try_from(f) }
 }
 
 pub(crate) mod string_or_float_opt {
     use std::fmt;
 
-    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use super::string_or_float::{from_f64, parse};
 
     pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -1997,7 +2328,7 @@ pub(crate) mod string_or_float_opt {
         }
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<crate::rest_model::Number>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -2008,7 +2339,13 @@ pub(crate) mod string_or_float_opt {
             Float(f64),
         }
 
-        Ok(Some(crate::rest_model::string_or_float::deserialize(deserializer)?))
+        // Empty strings are sent for values that do not apply
+        match Option::<StringOrFloat>::deserialize(deserializer)? {
+            Some(StringOrFloat::String(s)) if s.is_empty() => Ok(None),
+            Some(StringOrFloat::String(s)) => parse(&s).map(Some).map_err(de::Error::custom),
+            Some(StringOrFloat::Float(f)) => from_f64(f).map(Some).map_err(de::Error::custom),
+            None => Ok(None),
+        }
     }
 }
 
@@ -2043,6 +2380,7 @@ pub mod string_or_u64 {
     }
 }
 
+#[cfg(feature = "futures_api")]
 pub(crate) mod string_or_bool {
     use std::fmt;
 
@@ -2078,7 +2416,8 @@ pub(crate) mod string_or_bool {
 mod test {
     use std::path::PathBuf;
 
-    use crate::rest_model::ExchangeInformation;
+    use crate::rest_model::{ContingencyType, ExchangeInformation, Filters, KlineInterval, KlineSummary, Number,
+                            OrderList, SymbolPrice};
 
     #[test]
     fn exchange_info_serde() {
@@ -2088,4 +2427,74 @@ mod test {
         let result = serde_json::from_str::<ExchangeInformation>(&fc);
         assert!(result.is_ok(), "{result:?}");
     }
+
+    #[test]
+    fn order_list_serde() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("test_data/orderListOco.json");
+        let fc = std::fs::read_to_string(d).unwrap();
+        let result = serde_json::from_str::<OrderList>(&fc);
+        assert!(result.is_ok(), "{result:?}");
+        let order_list = result.unwrap();
+        assert_eq!(order_list.contingency_type, ContingencyType::OCO);
+        assert_eq!(order_list.order_reports.len(), 2);
+    }
+
+    #[test]
+    fn number_serde() {
+        let price: SymbolPrice = serde_json::from_str(r#"{"symbol":"BTCUSDT","price":"0.10000000"}"#).unwrap();
+        assert_eq!(price.price.to_string().trim_end_matches('0'), "0.1");
+        let price: SymbolPrice = serde_json::from_str(r#"{"symbol":"BTCUSDT","price":"1e-8"}"#).unwrap();
+        assert_eq!(price.price, "0.00000001".parse::<Number>().unwrap());
+        assert_eq!(
+            serde_json::to_string(&price).unwrap(),
+            r#"{"symbol":"BTCUSDT","price":"0.00000001"}"#
+        );
+    }
+
+    #[test]
+    fn filters_serde() {
+        let filters: Vec<Filters> = serde_json::from_str(
+            r#"[
+                {"filterType": "TRAILING_DELTA", "minTrailingAboveDelta": 10, "maxTrailingAboveDelta": 2000,
+                 "minTrailingBelowDelta": 10, "maxTrailingBelowDelta": 2000},
+                {"filterType": "MAX_NUM_ORDER_AMENDS", "maxNumOrderAmends": 10},
+                {"filterType": "MAX_NUM_ORDER_LISTS", "maxNumOrderLists": 20},
+                {"filterType": "T_PLUS_SELL", "endTime": null},
+                {"filterType": "EXCHANGE_MAX_NUM_ALGO_ORDERS", "maxNumAlgoOrders": 200},
+                {"filterType": "EXCHANGE_MAX_NUM_ICEBERG_ORDERS", "maxNumIcebergOrders": 10000},
+                {"filterType": "EXCHANGE_MAX_NUM_ORDER_LISTS", "maxNumOrderLists": 20}
+            ]"#,
+        )
+        .unwrap();
+        assert!(!filters.contains(&Filters::Others), "{filters:?}");
+        assert_eq!(filters[0], Filters::TrailingDelta {
+            min_trailing_above_delta: 10,
+            max_trailing_above_delta: 2000,
+            min_trailing_below_delta: 10,
+            max_trailing_below_delta: 2000,
+        });
+    }
+
+    #[test]
+    fn kline_serde() {
+        let row = r#"[1499040000000,"0.01634790","0.80000000","0.01575800","0.01577100","148976.11427815",
+                      1499644799999,"2434.19055334",308,"1756.87402397","28.46694368","0"]"#;
+        let kline: KlineSummary = serde_json::from_str(row).unwrap();
+        assert_eq!(kline.open_time, 1499040000000);
+        assert_eq!(kline.close, "0.015771".parse::<Number>().unwrap());
+        assert_eq!(kline.number_of_trades, 308);
+        assert_eq!(kline.ignore, "0");
+        let back: KlineSummary = serde_json::from_str(&serde_json::to_string(&kline).unwrap()).unwrap();
+        assert_eq!(back, kline);
+        let result = serde_json::from_str::<Vec<KlineSummary>>(r#"[[1499040000000,"0.01634790",null]]"#);
+        assert!(result.is_err(), "{result:?}");
+
+        for interval in KlineInterval::ALL {
+            assert_eq!(interval.to_string().parse::<KlineInterval>().unwrap(), interval);
+            assert_eq!(serde_json::to_string(&interval).unwrap(), format!("\"{interval}\""));
+        }
+        assert!("2m".parse::<KlineInterval>().is_err());
+        assert_eq!(KlineInterval::FifteenMinutes.duration_ms(), Some(900_000));
+    }
 }
@@ -0,0 +1,108 @@
+//! Retries of REST requests.
+//!
+//! Only requests that are safe to repeat are retried: GET requests, and order placement once
+//! the order was verified not to exist through its `newClientOrderId`.
+
+use std::time::Duration;
+
+use crate::errors::*;
+
+/// Binance code of "Internal error; unable to process your request. Please try again."
+pub const DISCONNECTED: i32 = -1001;
+
+/// Retry policy of the REST clients
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled after each failed attempt
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between two attempts
+    pub max_backoff: Duration,
+    /// Retry timeouts and connection errors
+    pub retry_on_timeout: bool,
+    /// Retry `Error::InternalServerError` and `Error::ServiceUnavailable`
+    pub retry_on_server_error: bool,
+    /// Retry `Error::RateLimited`, after the delay given by Binance if any
+    pub retry_on_rate_limited: bool,
+    /// Binance error codes to retry
+    pub retry_codes: Vec<i32>,
+    /// Retry order placement, after checking that the order did not land
+    pub retry_orders: bool,
+}
+
+impl RetryPolicy {
+    /// Whether `error` qualifies for a retry
+    pub fn should_retry(&self, error: &Error) -> bool {
+        match error {
+            Error::ReqError(e) => self.retry_on_timeout && (e.is_timeout() || e.is_connect()),
+            Error::InternalServerError | Error::ServiceUnavailable => self.retry_on_server_error,
+            Error::RateLimited { .. } => self.retry_on_rate_limited,
+            Error::BinanceError { response } => self.retry_codes.contains(&response.code),
+            _ => false,
+        }
+    }
+
+    /// Delay to wait before the `attempt`th attempt (starting at 2)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(2));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+
+    /// Delay before retrying a request that failed with `error` on its `attempt`th attempt, `None` to give up
+    pub(crate) fn retry_delay(&self, attempt: u32, error: &Error) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.should_retry(error) {
+            return None;
+        }
+        match error {
+            Error::RateLimited {
+                retry_after: Some(retry_after),
+            } => Some(*retry_after),
+            _ => Some(self.backoff(attempt + 1)),
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            retry_on_timeout: true,
+            retry_on_server_error: true,
+            retry_on_rate_limited: false,
+            retry_codes: vec![DISCONNECTED],
+            retry_orders: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delays() {
+        let policy = RetryPolicy::default();
+        assert_eq!(
+            policy.retry_delay(1, &Error::ServiceUnavailable),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(
+            policy.retry_delay(2, &Error::InternalServerError),
+            Some(Duration::from_millis(400))
+        );
+        assert_eq!(policy.retry_delay(3, &Error::InternalServerError), None);
+        assert_eq!(policy.retry_delay(1, &Error::Unauthorized), None);
+        let rate_limited = Error::RateLimited {
+            retry_after: Some(Duration::from_secs(3)),
+        };
+        assert_eq!(policy.retry_delay(1, &rate_limited), None);
+        let policy = RetryPolicy {
+            retry_on_rate_limited: true,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.retry_delay(1, &rate_limited), Some(Duration::from_secs(3)));
+    }
+}
@@ -10,24 +10,24 @@ pub struct CoinInfo {
     pub coin: String,
     pub deposit_all_enable: bool,
     #[serde(with = "string_or_float")]
-    pub free: f64,
+    pub free: Number,
     #[serde(with = "string_or_float")]
-    pub freeze: f64,
+    pub freeze: Number,
     #[serde(with = "string_or_float")]
-    pub ipoable: f64,
+    pub ipoable: Number,
     #[serde(with = "string_or_float")]
-    pub ipoing: f64,
+    pub ipoing: Number,
     pub is_legal_money: bool,
     #[serde(with = "string_or_float")]
-    pub locked: f64,
+    pub locked: Number,
     pub name: String,
     pub network_list: Vec<Network>,
     #[serde(with = "string_or_float")]
-    pub storage: f64,
+    pub storage: Number,
     pub trading: bool,
     pub withdraw_all_enable: bool,
     #[serde(with = "string_or_float")]
-    pub withdrawing: f64,
+    pub withdrawing: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -52,9 +52,9 @@ pub struct Network {
     pub withdraw_desc: Option<String>,
     pub withdraw_enable: bool,
     #[serde(with = "string_or_float")]
-    pub withdraw_fee: f64,
+    pub withdraw_fee: Number,
     #[serde(with = "string_or_float")]
-    pub withdraw_min: f64,
+    pub withdraw_min: Number,
     // pub insert_time: Option<u64>, //commented out for now, because they are not inside the actual response (only the api doc example)
     // pub update_time: Option<u64>,
     pub withdraw_integer_multiple: Option<String>,
@@ -64,11 +64,11 @@ pub struct Network {
 #[serde(rename_all = "camelCase")]
 pub struct AssetDetail {
     #[serde(with = "string_or_float")]
-    pub min_withdraw_amount: f64,
+    pub min_withdraw_amount: Number,
     /// false if ALL of networks' are false
     pub deposit_status: bool,
     #[serde(with = "string_or_float")]
-    pub withdraw_fee: f64,
+    pub withdraw_fee: Number,
     /// false if ALL of networks' are false
     pub withdraw_status: bool,
     /// reason
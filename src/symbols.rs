@@ -0,0 +1,375 @@
+//! Shared registry of the symbols of the exchange.
+//!
+//! The exchange information is fetched once and refreshed when older than the TTL of the registry, lookups
+//! are served from memory. Changes observed on refresh are broadcast to subscribers, notably symbols moving
+//! to `BREAK` or `HALT` and filter updates.
+//!
+//! # Examples
+//! ```rust,no_run
+//! use binance::{api::*, general::*, symbols::*};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! tokio_test::block_on(async {
+//!     let general: General = Binance::new(None, None);
+//!     let registry = Arc::new(SymbolRegistry::new(general, Duration::from_secs(300)));
+//!     let mut changes = registry.subscribe();
+//!     registry.clone().spawn_refresh();
+//!     let btcusdt = registry.get("BTCUSDT").await.unwrap();
+//!     println!("{:?}", btcusdt.lot_size());
+//!     while let Ok(change) = changes.recv().await {
+//!         println!("{change:?}");
+//!     }
+//! });
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tokio::task::JoinHandle;
+
+use crate::errors::*;
+use crate::general::General;
+use crate::rest_model::SymbolStatus;
+
+/// Capacity of the change channel, lagging subscribers miss the oldest changes
+const CHANGES_CAPACITY: usize = 256;
+
+/// A symbol of the exchange information
+pub trait RegistrySymbol: Clone + Send + Sync + 'static {
+    fn symbol(&self) -> &str;
+    fn base_asset(&self) -> &str;
+    fn quote_asset(&self) -> &str;
+    fn status(&self) -> SymbolStatus;
+    /// Whether the trading rules of both symbols are the same
+    fn same_filters(&self, other: &Self) -> bool;
+}
+
+impl RegistrySymbol for crate::rest_model::Symbol {
+    fn symbol(&self) -> &str { &self.symbol }
+
+    fn base_asset(&self) -> &str { &self.base_asset }
+
+    fn quote_asset(&self) -> &str { &self.quote_asset }
+
+    fn status(&self) -> SymbolStatus {
+        serde_json::from_value(serde_json::Value::String(self.status.clone())).unwrap_or(SymbolStatus::Other)
+    }
+
+    fn same_filters(&self, other: &Self) -> bool { self.filters == other.filters }
+}
+
+#[cfg(feature = "futures_api")]
+impl RegistrySymbol for crate::futures::rest_model::Symbol {
+    fn symbol(&self) -> &str { &self.symbol }
+
+    fn base_asset(&self) -> &str { &self.base_asset }
+
+    fn quote_asset(&self) -> &str { &self.quote_asset }
+
+    fn status(&self) -> SymbolStatus { self.status.clone() }
+
+    fn same_filters(&self, other: &Self) -> bool { self.filters == other.filters }
+}
+
+/// Where the symbols are fetched from
+pub trait SymbolSource: Send + Sync + 'static {
+    type Symbol: RegistrySymbol;
+
+    fn fetch_symbols(&self) -> BoxFuture<'_, Result<Vec<Self::Symbol>>>;
+}
+
+impl SymbolSource for General {
+    type Symbol = crate::rest_model::Symbol;
+
+    fn fetch_symbols(&self) -> BoxFuture<'_, Result<Vec<Self::Symbol>>> {
+        Box::pin(async move { Ok(self.exchange_info().await?.symbols) })
+    }
+}
+
+#[cfg(feature = "futures_api")]
+impl SymbolSource for crate::futures::general::FuturesGeneral {
+    type Symbol = crate::futures::rest_model::Symbol;
+
+    fn fetch_symbols(&self) -> BoxFuture<'_, Result<Vec<Self::Symbol>>> {
+        Box::pin(async move { Ok(self.exchange_info().await?.symbols) })
+    }
+}
+
+/// A change of the exchange information, observed on refresh
+#[derive(Debug, Clone)]
+pub enum SymbolChange<S> {
+    Added(S),
+    Removed(S),
+    StatusChanged { symbol: S, previous: SymbolStatus },
+    FiltersChanged { symbol: S, previous: S },
+}
+
+impl<S: RegistrySymbol> SymbolChange<S> {
+    /// Whether the symbol stopped trading, moving to `BREAK` or `HALT`
+    pub fn is_trading_stop(&self) -> bool {
+        match self {
+            SymbolChange::StatusChanged { symbol, .. } => {
+                matches!(symbol.status(), SymbolStatus::Break | SymbolStatus::Halt)
+            }
+            _ => false,
+        }
+    }
+}
+
+struct Symbols<S> {
+    by_symbol: HashMap<String, S>,
+    refreshed_at: Option<Instant>,
+}
+
+/// Symbols of the exchange, refreshed when older than the TTL
+pub struct SymbolRegistry<T: SymbolSource> {
+    source: T,
+    ttl: Duration,
+    symbols: RwLock<Symbols<T::Symbol>>,
+    /// Serializes refreshes so that concurrent lookups of a stale registry fetch once
+    refresh_lock: Mutex<()>,
+    changes: broadcast::Sender<SymbolChange<T::Symbol>>,
+}
+
+impl<T: SymbolSource> SymbolRegistry<T> {
+    pub fn new(source: T, ttl: Duration) -> Self {
+        Self {
+            source,
+            ttl,
+            symbols: RwLock::new(Symbols {
+                by_symbol: HashMap::new(),
+                refreshed_at: None,
+            }),
+            refresh_lock: Mutex::new(()),
+            changes: broadcast::channel(CHANGES_CAPACITY).0,
+        }
+    }
+
+    /// Changes observed on the following refreshes
+    pub fn subscribe(&self) -> broadcast::Receiver<SymbolChange<T::Symbol>> { self.changes.subscribe() }
+
+    /// Fetch the symbols, broadcasting the changes since the previous refresh
+    pub async fn refresh(&self) -> Result<()> {
+        let _guard = self.refresh_lock.lock().await;
+        self.fetch().await
+    }
+
+    /// Refresh the symbols every TTL in a background task, errors are retried on the next tick
+    pub fn spawn_refresh(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.ttl);
+            loop {
+                interval.tick().await;
+                // Lookups keep being served from the previous symbols until a refresh succeeds
+                let _ = self.refresh().await;
+            }
+        })
+    }
+
+    /// The symbol named `symbol`, case insensitive
+    pub async fn get(&self, symbol: &str) -> Result<T::Symbol> {
+        self.refresh_if_stale().await?;
+        let upper_symbol = symbol.to_uppercase();
+        self.symbols
+            .read()
+            .await
+            .by_symbol
+            .get(&upper_symbol)
+            .cloned()
+            .ok_or_else(|| Error::UnknownSymbol(symbol.to_string()))
+    }
+
+    /// The symbol trading `base_asset` against `quote_asset`
+    pub async fn by_assets(&self, base_asset: &str, quote_asset: &str) -> Result<T::Symbol> {
+        let is_pair = |s: &T::Symbol| {
+            s.base_asset().eq_ignore_ascii_case(base_asset) && s.quote_asset().eq_ignore_ascii_case(quote_asset)
+        };
+        self.find(is_pair)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::UnknownSymbol(format!("{base_asset}{quote_asset}")))
+    }
+
+    /// Symbols with `asset` as base asset
+    pub async fn by_base_asset(&self, asset: &str) -> Result<Vec<T::Symbol>> {
+        self.find(|s| s.base_asset().eq_ignore_ascii_case(asset)).await
+    }
+
+    /// Symbols with `asset` as quote asset
+    pub async fn by_quote_asset(&self, asset: &str) -> Result<Vec<T::Symbol>> {
+        self.find(|s| s.quote_asset().eq_ignore_ascii_case(asset)).await
+    }
+
+    /// All the symbols of the exchange
+    pub async fn symbols(&self) -> Result<Vec<T::Symbol>> { self.find(|_| true).await }
+
+    async fn find(&self, predicate: impl Fn(&T::Symbol) -> bool) -> Result<Vec<T::Symbol>> {
+        self.refresh_if_stale().await?;
+        let symbols = self.symbols.read().await;
+        let mut found: Vec<T::Symbol> = symbols.by_symbol.values().filter(|s| predicate(s)).cloned().collect();
+        found.sort_by(|a, b| a.symbol().cmp(b.symbol()));
+        Ok(found)
+    }
+
+    async fn refresh_if_stale(&self) -> Result<()> {
+        if !self.is_stale().await {
+            return Ok(());
+        }
+        let _guard = self.refresh_lock.lock().await;
+        // Refreshed by a concurrent lookup while waiting for the lock
+        if !self.is_stale().await {
+            return Ok(());
+        }
+        self.fetch().await
+    }
+
+    async fn is_stale(&self) -> bool {
+        match self.symbols.read().await.refreshed_at {
+            Some(refreshed_at) => refreshed_at.elapsed() >= self.ttl,
+            None => true,
+        }
+    }
+
+    async fn fetch(&self) -> Result<()> {
+        let fetched = self.source.fetch_symbols().await?;
+        let mut symbols = self.symbols.write().await;
+        let mut by_symbol: HashMap<String, T::Symbol> =
+            fetched.into_iter().map(|s| (s.symbol().to_string(), s)).collect();
+        // The first fetch is not a change
+        if symbols.refreshed_at.is_some() {
+            for change in changes(&symbols.by_symbol, &by_symbol) {
+                // Fails only without subscribers
+                let _ = self.changes.send(change);
+            }
+        }
+        std::mem::swap(&mut symbols.by_symbol, &mut by_symbol);
+        symbols.refreshed_at = Some(Instant::now());
+        Ok(())
+    }
+}
+
+fn changes<S: RegistrySymbol>(previous: &HashMap<String, S>, current: &HashMap<String, S>) -> Vec<SymbolChange<S>> {
+    let mut changes = vec![];
+    for (name, symbol) in current {
+        match previous.get(name) {
+            None => changes.push(SymbolChange::Added(symbol.clone())),
+            Some(previous) => {
+                if previous.status() != symbol.status() {
+                    changes.push(SymbolChange::StatusChanged {
+                        symbol: symbol.clone(),
+                        previous: previous.status(),
+                    });
+                }
+                if !previous.same_filters(symbol) {
+                    changes.push(SymbolChange::FiltersChanged {
+                        symbol: symbol.clone(),
+                        previous: previous.clone(),
+                    });
+                }
+            }
+        }
+    }
+    for (name, symbol) in previous {
+        if !current.contains_key(name) {
+            changes.push(SymbolChange::Removed(symbol.clone()));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TestSymbol {
+        symbol: &'static str,
+        status: SymbolStatus,
+        tick_size: u32,
+    }
+
+    impl RegistrySymbol for TestSymbol {
+        fn symbol(&self) -> &str { self.symbol }
+
+        fn base_asset(&self) -> &str { &self.symbol[..3] }
+
+        fn quote_asset(&self) -> &str { &self.symbol[3..] }
+
+        fn status(&self) -> SymbolStatus { self.status.clone() }
+
+        fn same_filters(&self, other: &Self) -> bool { self.tick_size == other.tick_size }
+    }
+
+    /// Serves the snapshots in order, then the last one
+    struct TestSource {
+        snapshots: Vec<Vec<TestSymbol>>,
+        fetches: AtomicUsize,
+    }
+
+    impl SymbolSource for TestSource {
+        type Symbol = TestSymbol;
+
+        fn fetch_symbols(&self) -> BoxFuture<'_, Result<Vec<TestSymbol>>> {
+            let fetch = self.fetches.fetch_add(1, Ordering::SeqCst);
+            let snapshot = self.snapshots[fetch.min(self.snapshots.len() - 1)].clone();
+            Box::pin(async move { Ok(snapshot) })
+        }
+    }
+
+    fn symbol(symbol: &'static str, status: SymbolStatus, tick_size: u32) -> TestSymbol {
+        TestSymbol {
+            symbol,
+            status,
+            tick_size,
+        }
+    }
+
+    #[tokio::test]
+    async fn lookups_and_changes() {
+        let source = TestSource {
+            snapshots: vec![
+                vec![
+                    symbol("BTCUSDT", SymbolStatus::Trading, 1),
+                    symbol("ETHUSDT", SymbolStatus::Trading, 1),
+                    symbol("ETHBTC", SymbolStatus::Trading, 1),
+                ],
+                vec![
+                    symbol("BTCUSDT", SymbolStatus::Halt, 1),
+                    symbol("ETHUSDT", SymbolStatus::Trading, 2),
+                ],
+            ],
+            fetches: AtomicUsize::new(0),
+        };
+        let registry = SymbolRegistry::new(source, Duration::from_secs(60));
+        let mut changes = registry.subscribe();
+
+        assert_eq!(registry.get("btcusdt").await.unwrap().status, SymbolStatus::Trading);
+        assert_eq!(registry.by_assets("ETH", "BTC").await.unwrap().symbol, "ETHBTC");
+        assert_eq!(registry.by_quote_asset("USDT").await.unwrap().len(), 2);
+        assert!(matches!(registry.get("XRPUSDT").await, Err(Error::UnknownSymbol(_))));
+        // Served from memory within the TTL
+        assert_eq!(registry.source.fetches.load(Ordering::SeqCst), 1);
+        assert!(changes.try_recv().is_err());
+
+        registry.refresh().await.unwrap();
+        let mut received = vec![];
+        while let Ok(change) = changes.try_recv() {
+            received.push(change);
+        }
+        assert_eq!(received.len(), 3);
+        assert!(received.iter().any(|c| c.is_trading_stop()));
+        assert!(received
+            .iter()
+            .any(|c| matches!(c, SymbolChange::FiltersChanged { symbol, .. } if symbol.symbol == "ETHUSDT")));
+        assert!(received
+            .iter()
+            .any(|c| matches!(c, SymbolChange::Removed(symbol) if symbol.symbol == "ETHBTC")));
+    }
+}
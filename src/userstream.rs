@@ -1,6 +1,17 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::api::Binance;
 use crate::client::*;
+use crate::config::Config;
 use crate::errors::*;
 use crate::rest_model::*;
+use crate::websockets::{ReconnectEvent, WebSockets};
+use crate::ws_model::WebsocketEvent;
 
 static USER_DATA_STREAM: &str = "/api/v3/userDataStream";
 
@@ -50,3 +61,282 @@ impl UserStream {
         self.client.delete(USER_DATA_STREAM, listen_key, None).await
     }
 }
+
+static SAPI_USER_DATA_STREAM: &str = "/sapi/v1/userDataStream";
+static SAPI_USER_DATA_STREAM_ISOLATED: &str = "/sapi/v1/userDataStream/isolated";
+
+/// Listen keys are valid for 60mn, Binance recommends a keepalive every 30mn
+pub const DEFAULT_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Account the user stream is opened for
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UserStreamKind {
+    Spot,
+    Margin,
+    /// Isolated margin account of the symbol
+    IsolatedMargin(String),
+}
+
+impl UserStreamKind {
+    fn endpoint(&self) -> &'static str {
+        match self {
+            UserStreamKind::Spot => USER_DATA_STREAM,
+            UserStreamKind::Margin => SAPI_USER_DATA_STREAM,
+            UserStreamKind::IsolatedMargin(_) => SAPI_USER_DATA_STREAM_ISOLATED,
+        }
+    }
+
+    fn symbol(&self) -> Option<&str> {
+        match self {
+            UserStreamKind::IsolatedMargin(symbol) => Some(symbol),
+            _ => None,
+        }
+    }
+}
+
+/// A user stream that obtains its listen key, keeps it alive in the background and rotates it
+/// when Binance reports it as expired or invalid, without interrupting the event handler.
+///
+/// # Examples
+/// ```rust,no_run
+/// use binance::{config::*, userstream::*, ws_model::*};
+/// use std::sync::atomic::AtomicBool;
+/// let keep_running = AtomicBool::new(true);
+/// let config = Config::default();
+/// let mut user_stream = ManagedUserStream::new("YOUR_KEY", UserStreamKind::Spot, &config, |event: WebsocketEvent| {
+///     println!("{event:?}");
+///     Ok(())
+/// });
+/// tokio_test::block_on(async {
+///     user_stream.start().await?;
+///     user_stream.event_loop(&keep_running).await?;
+///     user_stream.close().await
+/// }).unwrap();
+/// ```
+pub struct ManagedUserStream<'a> {
+    user_stream: UserStream,
+    kind: UserStreamKind,
+    keep_alive_interval: Duration,
+    web_socket: WebSockets<'a, WebsocketEvent>,
+    listen_key: Option<String>,
+    keep_alive: Option<(JoinHandle<()>, UnboundedReceiver<Error>)>,
+}
+
+impl<'a> ManagedUserStream<'a> {
+    /// New managed stream, `handler` receives the events of every successive listen key
+    pub fn new<Callback>(api_key: &str, kind: UserStreamKind, config: &Config, mut handler: Callback) -> Self
+    where
+        Callback: FnMut(WebsocketEvent) -> Result<()> + 'a + Send,
+    {
+        let handler = move |event: WebsocketEvent| {
+            let expired = match event {
+                WebsocketEvent::ListenKeyExpired(ref e) => Some(e.listen_key.clone().unwrap_or_default()),
+                _ => None,
+            };
+            handler(event)?;
+            match expired {
+                Some(listen_key) => Err(Error::InvalidListenKey(listen_key)),
+                None => Ok(()),
+            }
+        };
+        ManagedUserStream {
+            user_stream: UserStream::new_with_config(Some(api_key.to_string()), None, config),
+            kind,
+            keep_alive_interval: DEFAULT_KEEP_ALIVE_INTERVAL,
+            web_socket: WebSockets::new_with_options(handler, config.clone()),
+            listen_key: None,
+            keep_alive: None,
+        }
+    }
+
+    /// Set the delay between two keepalive requests
+    pub fn set_keep_alive_interval(&mut self, interval: Duration) { self.keep_alive_interval = interval; }
+
+    /// Sets a handler for connection events of the underlying websocket,
+    /// see [`WebSockets::set_reconnect_handler`]
+    pub fn set_reconnect_handler<Callback>(&mut self, handler: Callback)
+    where
+        Callback: FnMut(ReconnectEvent) -> Result<()> + 'a + Send,
+    {
+        self.web_socket.set_reconnect_handler(handler);
+    }
+
+    /// The listen key currently in use
+    pub fn listen_key(&self) -> Option<&str> { self.listen_key.as_deref() }
+
+    /// Obtain a listen key, start the keepalive and connect the websocket
+    pub async fn start(&mut self) -> Result<()> {
+        let listen_key = self
+            .user_stream
+            .client
+            .post::<UserDataStream>(self.kind.endpoint(), self.kind.symbol())
+            .await?
+            .listen_key;
+        self.web_socket.connect(&listen_key).await?;
+        self.keep_alive = Some(self.spawn_keep_alive(listen_key.clone()));
+        self.listen_key = Some(listen_key);
+        Ok(())
+    }
+
+    fn spawn_keep_alive(&self, listen_key: String) -> (JoinHandle<()>, UnboundedReceiver<Error>) {
+        let (tx, rx) = unbounded_channel();
+        let user_stream = self.user_stream.clone();
+        let kind = self.kind.clone();
+        let interval = self.keep_alive_interval;
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval_at(Instant::now() + interval, interval);
+            loop {
+                ticker.tick().await;
+                let keep_alive = user_stream
+                    .client
+                    .put::<Success>(kind.endpoint(), &listen_key, kind.symbol())
+                    .await;
+                // Transient failures are retried on the next tick, the key stays valid for 60mn
+                if let Err(e @ Error::InvalidListenKey(_)) = keep_alive {
+                    let _ = tx.send(e);
+                    return;
+                }
+            }
+        });
+        (handle, rx)
+    }
+
+    fn stop_keep_alive(&mut self) {
+        if let Some((handle, _)) = self.keep_alive.take() {
+            handle.abort();
+        }
+    }
+
+    /// Replace the listen key with a new one and reconnect the websocket
+    async fn rotate(&mut self) -> Result<()> {
+        self.stop_keep_alive();
+        let _ = self.web_socket.disconnect().await;
+        if let Some(listen_key) = self.listen_key.take() {
+            // The old key is most likely already invalid
+            let _ = self
+                .user_stream
+                .client
+                .delete::<Success>(self.kind.endpoint(), &listen_key, self.kind.symbol())
+                .await;
+        }
+        self.start().await
+    }
+
+    /// Process events until `running` is set to false, rotating the listen key when it expires
+    pub async fn event_loop(&mut self, running: &AtomicBool) -> Result<()> {
+        while running.load(Ordering::Relaxed) {
+            let keep_alive_error = match self.keep_alive {
+                Some((_, ref mut rx)) => {
+                    tokio::select! {
+                        result = self.web_socket.event_loop(running) => result.err(),
+                        Some(e) = rx.recv() => Some(e),
+                    }
+                }
+                None => self.web_socket.event_loop(running).await.err(),
+            };
+            match keep_alive_error {
+                Some(Error::InvalidListenKey(_)) => self.rotate().await?,
+                Some(e) => return Err(e),
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Stop the keepalive, disconnect and invalidate the listen key
+    pub async fn close(&mut self) -> Result<()> {
+        self.stop_keep_alive();
+        self.web_socket.disconnect().await?;
+        if let Some(listen_key) = self.listen_key.take() {
+            let _: Success = self
+                .user_stream
+                .client
+                .delete(self.kind.endpoint(), &listen_key, self.kind.symbol())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ManagedUserStream<'_> {
+    fn drop(&mut self) { self.stop_keep_alive(); }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::mock::{Method, MockError, MockServer};
+
+    fn balance_update(asset: &str) -> String {
+        serde_json::json!({"e": "balanceUpdate", "E": 1, "a": asset, "d": "1.0", "T": 1}).to_string()
+    }
+
+    fn listen_key(key: &str) -> String { serde_json::json!({ "listenKey": key }).to_string() }
+
+    #[tokio::test]
+    async fn rotates_listen_keys() {
+        let server = MockServer::start().await.unwrap();
+        server.mock(Method::POST, USER_DATA_STREAM, listen_key("first"));
+        server.mock(Method::PUT, USER_DATA_STREAM, "{}");
+        server.mock(Method::DELETE, USER_DATA_STREAM, "{}");
+        let expired = serde_json::json!({"e": "listenKeyExpired", "E": "1", "listenKey": "first"});
+        server.mock_stream("first", vec![balance_update("A"), expired.to_string()]);
+        server.mock_stream("second", vec![balance_update("B")]);
+        server.mock_stream("third", vec![balance_update("C")]);
+
+        let assets = Mutex::new(vec![]);
+        let handler = |event: WebsocketEvent| {
+            match event {
+                // the next listen key is handed out once Binance reports the first one as expired
+                WebsocketEvent::ListenKeyExpired(_) => {
+                    server.mock(Method::POST, USER_DATA_STREAM, listen_key("second"))
+                }
+                WebsocketEvent::BalanceUpdate(update) => {
+                    assets.lock().unwrap().push(update.asset.clone());
+                    match update.asset.as_str() {
+                        // the keepalive of the second key fails with -1125
+                        "B" => {
+                            server.mock(Method::POST, USER_DATA_STREAM, listen_key("third"));
+                            server.inject_error(USER_DATA_STREAM, MockError::Binance {
+                                code: -1125,
+                                msg: "This listenKey does not exist.".to_string(),
+                            });
+                        }
+                        "C" => return Err(Error::Msg("done".to_string())),
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+            Ok(())
+        };
+        let mut user_stream = ManagedUserStream::new("api_key", UserStreamKind::Spot, &server.config(), handler);
+        user_stream.set_keep_alive_interval(Duration::from_millis(100));
+        user_stream.start().await.unwrap();
+        let result = user_stream.event_loop(&AtomicBool::new(true)).await;
+        assert!(
+            matches!(result, Err(Error::Msg(ref msg)) if msg == "done"),
+            "{result:?}"
+        );
+        assert_eq!(user_stream.listen_key(), Some("third"));
+        drop(user_stream);
+        assert_eq!(*assets.lock().unwrap(), vec!["A", "B", "C"]);
+
+        let requests = server.requests();
+        let posts = requests.iter().filter(|request| request.method == Method::POST).count();
+        assert_eq!(posts, 3);
+        let deleted: Vec<_> = requests
+            .iter()
+            .filter(|request| request.method == Method::DELETE)
+            .filter_map(|request| request.param("listenKey"))
+            .collect();
+        assert_eq!(deleted, vec!["first", "second"]);
+        let kept_alive = requests
+            .iter()
+            .filter(|request| request.method == Method::PUT)
+            .filter_map(|request| request.param("listenKey"));
+        assert!(kept_alive.into_iter().any(|key| key == "second"));
+    }
+}
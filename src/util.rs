@@ -1,4 +1,7 @@
+use std::collections::HashMap;
 use std::ops::Not;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::RwLock;
 
 use boolinator::Boolinator;
 use chrono::{Duration, Utc};
@@ -81,8 +84,44 @@ pub fn to_f64(v: &Value) -> f64 {
     v.as_str().unwrap().parse().unwrap()
 }
 
+/// Replaces the timestamp of a signed request with the current server time of `host`
+pub fn refresh_timestamp(request: &str, host: &str) -> Result<String> {
+    let timestamp = get_server_timestamp(host)?;
+    Ok(request
+        .split('&')
+        .map(|param| match param.strip_prefix("timestamp=") {
+            Some(_) => format!("timestamp={timestamp}"),
+            None => param.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&"))
+}
+
+/// A client order id unique to this process, matching `^[\.A-Z\:/a-z0-9_-]{1,36}$`
+pub fn new_client_order_id() -> Result<String> {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    Ok(format!("rs-{}-{}-{count}", get_timestamp()?, std::process::id()))
+}
+
+lazy_static! {
+    /// Offset in milliseconds between the server clock and the local clock, by host
+    static ref TIME_OFFSETS: RwLock<HashMap<String, i64>> = RwLock::new(HashMap::new());
+}
+
+/// Sets the offset applied to the timestamps of the signed requests sent to `host`,
+/// usually measured by `General::sync_time`
+pub fn set_time_offset(host: &str, offset: i64) { TIME_OFFSETS.write().unwrap().insert(host.to_string(), offset); }
+
+pub fn time_offset(host: &str) -> i64 { TIME_OFFSETS.read().unwrap().get(host).copied().unwrap_or_default() }
+
 pub fn get_timestamp() -> Result<u64> { Ok(Utc::now().timestamp_millis() as u64) }
 
+/// The current server time of `host`, estimated from the local time and the time offset of the host
+pub fn get_server_timestamp(host: &str) -> Result<u64> {
+    Ok((Utc::now().timestamp_millis() + time_offset(host)) as u64)
+}
+
 /// Returns a duration in milliseconds for the `days`
 pub fn days_millis(days: i64) -> i64 { Duration::days(days).num_milliseconds() }
 
@@ -98,3 +137,7 @@ pub fn bool_to_string(b: bool) -> String {
 }
 
 pub fn bool_to_string_some(b: bool) -> Option<String> { Some(bool_to_string(b)) }
+
+/// A [`Number`](crate::rest_model::Number) in tests, whether numbers are floats or decimals
+#[cfg(test)]
+pub(crate) fn num(value: f64) -> crate::rest_model::Number { crate::rest_model::Number::try_from(value).unwrap() }
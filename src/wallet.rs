@@ -1,8 +1,10 @@
 use crate::client::*;
 use crate::errors::*;
+use crate::pagination::*;
 use crate::rest_model::*;
 use chrono::DateTime;
 use chrono::{Duration, Utc};
+use futures::stream::BoxStream;
 use std::collections::HashMap;
 use std::ops::Sub;
 
@@ -152,6 +154,48 @@ impl Wallet {
             .await
     }
 
+    /// Every deposit of the query between `start_time` and `end_time` (inclusive, in ms), in time order
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{api::*, wallet::*, config::*, rest_model::*};
+    /// use futures::TryStreamExt;
+    /// let wallet: Wallet = Binance::new_with_env(&Config::testnet());
+    /// let query = DepositHistoryQuery::default();
+    /// let records = wallet.deposit_history_stream(query, 1_640_995_200_000, 1_672_531_200_000);
+    /// let records: Vec<_> = tokio_test::block_on(records.try_collect()).unwrap();
+    /// ```
+    pub fn deposit_history_stream(
+        &self,
+        query: DepositHistoryQuery,
+        start_time: u64,
+        end_time: u64,
+    ) -> BoxStream<'_, Result<DepositRecord>> {
+        let paging = Paging {
+            max_window: Some(DEFAULT_WALLET_HISTORY_QUERY_INTERVAL_DAYS as u64 * DAY_MS),
+            limit: 1000,
+            by_offset: true,
+        };
+        let fetch = move |page: Page| {
+            let query = DepositHistoryQuery {
+                start_time: Some(page.start_time),
+                end_time: Some(page.end_time),
+                limit: Some(page.limit),
+                offset: Some(page.offset),
+                ..query.clone()
+            };
+            async move { self.deposit_history(&query).await }
+        };
+        paginate(
+            paging,
+            start_time,
+            end_time,
+            fetch,
+            |d| d.insert_time.unwrap_or_default(),
+            |d| d.id.clone(),
+        )
+    }
+
     /// Withdraw History starting at start_from (defaults to now), ranging total_duration (defaults to 90 days), with intervals of 90 days.
     ///
     /// # Examples
@@ -291,13 +335,14 @@ impl Wallet {
     /// ```rust,no_run
     /// use binance::{api::*, wallet::*, config::*, rest_model::*};
     /// let wallet: Wallet = Binance::new_with_env(&Config::testnet());
-    /// let records = tokio_test::block_on(wallet.universal_transfer("BTC".to_string(), 1.0, None, None, UniversalTransferType::FundingMain));
+    /// let amount: Number = "1.0".parse().unwrap();
+    /// let records = tokio_test::block_on(wallet.universal_transfer("BTC".to_string(), amount, None, None, UniversalTransferType::FundingMain));
     /// assert!(records.is_ok(), "{:?}", records);
     /// ```
     pub async fn universal_transfer(
         &self,
         asset: String,
-        amount: f64,
+        amount: Number,
         from_symbol: Option<String>,
         to_symbol: Option<String>,
         transfer_type: UniversalTransferType,
@@ -305,8 +350,8 @@ impl Wallet {
         let transfer = UniversalTransfer {
             asset,
             amount,
-            from_symbol: from_symbol.map(Into::<String>::into),
-            to_symbol: to_symbol.map(Into::<String>::into),
+            from_symbol,
+            to_symbol,
             transfer_type,
         };
         self.client
@@ -516,3 +561,41 @@ impl Wallet {
             .await
     }
 }
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::api::Binance;
+    use crate::mock::{Method, MockServer};
+
+    fn deposit(id: &str, address: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id, "amount": "0.5", "coin": "BTC", "network": "BTC", "status": 1, "address": address,
+            "addressTag": "", "txId": "batch", "insertTime": 1_000, "transferType": 0, "confirmTimes": "2/2",
+            "unlockConfirm": 0, "walletType": 0
+        })
+    }
+
+    #[tokio::test]
+    async fn deposits_of_one_transaction() {
+        let server = MockServer::start().await.unwrap();
+        server.set_credentials("api_key", "secret_key");
+        // a batch transaction paying two addresses of the account
+        let deposits = serde_json::json!([deposit("1", "first"), deposit("2", "second")]);
+        server.mock_signed(Method::GET, SAPI_V1_CAPITAL_DEPOSIT_HISREC, deposits.to_string());
+        let wallet = Wallet::new_with_config(
+            Some("api_key".to_string()),
+            Some("secret_key".to_string()),
+            &server.config(),
+        );
+        let records: Vec<DepositRecord> = wallet
+            .deposit_history_stream(DepositHistoryQuery::default(), 0, 10_000)
+            .try_collect()
+            .await
+            .unwrap();
+        let addresses: Vec<&str> = records.iter().map(|r| r.address.as_str()).collect();
+        assert_eq!(addresses, vec!["first", "second"]);
+    }
+}
@@ -1,8 +1,16 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use futures::StreamExt;
+use futures::stream::BoxStream;
+use futures::{SinkExt, Stream, StreamExt};
 use serde_json::from_str;
 use tokio::net::TcpStream;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
+use tokio::time::{sleep_until, Instant};
 use tokio_tungstenite::tungstenite::handshake::client::Response;
 use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::WebSocketStream;
@@ -11,6 +19,8 @@ use url::Url;
 
 use crate::config::Config;
 use crate::errors::*;
+use crate::rest_model::KlineInterval;
+use crate::ws_model::QueryResult;
 
 pub static STREAM_ENDPOINT: &str = "stream";
 pub static WS_ENDPOINT: &str = "ws";
@@ -31,7 +41,7 @@ pub fn agg_trade_stream(symbol: &str) -> String { format!("{symbol}@aggTrade") }
 
 pub fn trade_stream(symbol: &str) -> String { format!("{symbol}@trade") }
 
-pub fn kline_stream(symbol: &str, interval: &str) -> String { format!("{symbol}@kline_{interval}") }
+pub fn kline_stream(symbol: &str, interval: KlineInterval) -> String { format!("{symbol}@kline_{interval}") }
 
 pub fn book_ticker_stream(symbol: &str) -> String { format!("{symbol}@bookTicker") }
 
@@ -58,10 +68,325 @@ pub fn diff_book_depth_stream(symbol: &str, update_speed: u16) -> String { forma
 
 fn combined_stream(streams: Vec<String>) -> String { streams.join("/") }
 
+/// Reconnection policy, applied when the connection is dropped by the server or the network.
+/// Binance closes every connection after 24h, so long running consumers should set one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Maximum number of consecutive attempts before giving up, unlimited if `None`
+    pub max_attempts: Option<u32>,
+    /// Delay before the first attempt, doubled after each failed attempt
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between two attempts
+    pub max_backoff: Duration,
+    /// Maximum random delay added to each backoff
+    pub jitter: Duration,
+}
+
+impl ReconnectPolicy {
+    /// Delay to wait before the `attempt`th attempt (starting at 1)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+        let backoff = self.initial_backoff.saturating_mul(factor).min(self.max_backoff);
+        let jitter_millis = self.jitter.as_millis() as u64;
+        if jitter_millis == 0 {
+            return backoff;
+        }
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos() as u64)
+            .unwrap_or_default();
+        backoff + Duration::from_millis(seed % (jitter_millis + 1))
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: Some(10),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            jitter: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Connection events, surfaced to the reconnect handler so that consumers can resync their state
+#[derive(Clone, Debug)]
+pub enum ReconnectEvent {
+    /// The connection was lost
+    Disconnected { reason: String },
+    /// A new connection will be attempted after `delay`
+    Reconnecting { attempt: u32, delay: Duration },
+    /// The connection was re-established, events may have been missed in between
+    Reconnected { attempts: u32 },
+}
+
+/// Binance disconnects clients sending more than 5 messages per second
+pub const MAX_MESSAGES_PER_SECOND: usize = 5;
+/// Maximum number of streams of a single connection
+pub const MAX_STREAMS_PER_CONNECTION: usize = 1024;
+
+struct SubscriptionRequest {
+    method: &'static str,
+    params: Vec<String>,
+    /// `None` for the requests sent to restore subscriptions after a reconnection
+    reply: Option<oneshot::Sender<Result<QueryResult>>>,
+}
+
+/// Live subscriptions of a [`WebSockets`] connection.
+/// Requests are processed by [`WebSockets::event_loop`], which has to be running for them to complete.
+#[derive(Clone)]
+pub struct SubscriptionHandle {
+    requests: UnboundedSender<SubscriptionRequest>,
+}
+
+impl SubscriptionHandle {
+    async fn request(&self, method: &'static str, params: Vec<String>) -> Result<QueryResult> {
+        let (reply, response) = oneshot::channel();
+        self.requests
+            .send(SubscriptionRequest {
+                method,
+                params,
+                reply: Some(reply),
+            })
+            .map_err(|_| Error::Msg("The websocket was dropped".to_string()))?;
+        response
+            .await
+            .map_err(|_| Error::Msg("The event loop stopped before a response was received".to_string()))?
+    }
+
+    /// Subscribe to additional streams
+    pub async fn subscribe(&self, streams: Vec<String>) -> Result<()> {
+        self.request("SUBSCRIBE", streams).await.map(|_| ())
+    }
+
+    /// Unsubscribe from streams
+    pub async fn unsubscribe(&self, streams: Vec<String>) -> Result<()> {
+        self.request("UNSUBSCRIBE", streams).await.map(|_| ())
+    }
+
+    /// Streams the connection is subscribed to, as reported by Binance
+    pub async fn list_subscriptions(&self) -> Result<Vec<String>> {
+        match self.request("LIST_SUBSCRIPTIONS", vec![]).await?.result {
+            Some(result) => Ok(serde_json::from_value(result)?),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+/// Subscription state of a connection, requests are queued to respect the message rate limit
+#[derive(Default)]
+struct Subscriptions {
+    /// Streams the connection is subscribed to
+    streams: BTreeSet<String>,
+    /// Streams of the connection url
+    connected: BTreeSet<String>,
+    queue: VecDeque<SubscriptionRequest>,
+    pending: HashMap<i64, SubscriptionRequest>,
+    next_id: i64,
+    sent: VecDeque<Instant>,
+}
+
+impl Subscriptions {
+    fn connected(&mut self, streams: BTreeSet<String>) {
+        self.streams = streams.clone();
+        self.connected = streams;
+        self.queue.clear();
+        self.pending.clear();
+    }
+
+    fn push(&mut self, request: SubscriptionRequest) {
+        if request.method == "SUBSCRIBE" {
+            // Streams of requests not answered yet count towards the limit as well
+            let mut streams: BTreeSet<&String> = self.streams.iter().collect();
+            let requested = self.queue.iter().chain(self.pending.values()).chain([&request]);
+            streams.extend(requested.filter(|r| r.method == "SUBSCRIBE").flat_map(|r| &r.params));
+            if streams.len() > MAX_STREAMS_PER_CONNECTION {
+                if let Some(reply) = request.reply {
+                    let _ = reply.send(Err(Error::Msg(format!(
+                        "A connection is limited to {MAX_STREAMS_PER_CONNECTION} streams"
+                    ))));
+                }
+                return;
+            }
+        }
+        self.queue.push_back(request);
+    }
+
+    /// When the next queued request can be sent
+    fn next_slot(&mut self) -> Option<Instant> {
+        if self.queue.is_empty() {
+            return None;
+        }
+        let now = Instant::now();
+        while self
+            .sent
+            .front()
+            .is_some_and(|sent| *sent + Duration::from_secs(1) <= now)
+        {
+            self.sent.pop_front();
+        }
+        if self.sent.len() < MAX_MESSAGES_PER_SECOND {
+            Some(now)
+        } else {
+            self.sent.front().map(|sent| *sent + Duration::from_secs(1))
+        }
+    }
+
+    fn pop_message(&mut self) -> Option<String> {
+        let request = self.queue.pop_front()?;
+        self.next_id += 1;
+        let message = serde_json::json!({ "method": request.method, "params": request.params, "id": self.next_id });
+        self.sent.push_back(Instant::now());
+        self.pending.insert(self.next_id, request);
+        Some(message.to_string())
+    }
+
+    fn resolve(&mut self, result: QueryResult) {
+        let request = match self.pending.remove(&result.id) {
+            Some(request) => request,
+            None => return,
+        };
+        let reply = match result.error {
+            Some(response) => Err(Error::BinanceError { response }),
+            None => {
+                match request.method {
+                    "SUBSCRIBE" => self.streams.extend(request.params),
+                    "UNSUBSCRIBE" => self.streams.retain(|s| !request.params.contains(s)),
+                    _ => {}
+                }
+                Ok(result)
+            }
+        };
+        if let Some(sender) = request.reply {
+            let _ = sender.send(reply);
+        }
+    }
+
+    /// Restore the subscriptions made since the connection was opened, requests in flight are sent again
+    fn resubscribe(&mut self) {
+        let mut pending: Vec<(i64, SubscriptionRequest)> = self.pending.drain().collect();
+        pending.sort_by_key(|(id, _)| std::cmp::Reverse(*id));
+        for (_, request) in pending {
+            self.queue.push_front(request);
+        }
+        let removed: Vec<String> = self.connected.difference(&self.streams).cloned().collect();
+        if !removed.is_empty() {
+            self.queue.push_front(SubscriptionRequest {
+                method: "UNSUBSCRIBE",
+                params: removed,
+                reply: None,
+            });
+        }
+        let added: Vec<String> = self.streams.difference(&self.connected).cloned().collect();
+        if !added.is_empty() {
+            self.queue.push_front(SubscriptionRequest {
+                method: "SUBSCRIBE",
+                params: added,
+                reply: None,
+            });
+        }
+    }
+}
+
+/// Responses to subscription requests, as opposed to stream events
+fn is_query_response(msg: &str) -> bool {
+    msg.starts_with("{\"result\"") || msg.starts_with("{\"error\"") || msg.starts_with("{\"id\"")
+}
+
+type Socket = (WebSocketStream<MaybeTlsStream<TcpStream>>, Response);
+
+type ReconnectHandler<'a> = Box<dyn FnMut(ReconnectEvent) -> Result<()> + 'a + Send>;
+
+fn stream_url(endpoint: &str, stream: &str) -> Result<Url> {
+    Ok(Url::parse(&format!("{endpoint}/{WS_ENDPOINT}/{stream}"))?)
+}
+
+fn combined_stream_url(endpoint: &str, streams: Vec<String>) -> Result<Url> {
+    if streams.len() > MAX_STREAMS_PER_CONNECTION {
+        return Err(Error::Msg(format!(
+            "A connection is limited to {MAX_STREAMS_PER_CONNECTION} streams"
+        )));
+    }
+    let mut url = Url::parse(endpoint)?;
+    url.path_segments_mut()
+        .map_err(|_| Error::UrlParserError(url::ParseError::RelativeUrlWithoutBase))?
+        .push(STREAM_ENDPOINT);
+    url.set_query(Some(&format!("streams={}", combined_stream(streams))));
+    Ok(url)
+}
+
+async fn open(url: &Url) -> Result<Socket> {
+    connect_async(url.clone())
+        .await
+        .map_err(|e| Error::Msg(format!("Error during handshake {e}")))
+}
+
+/// Wait for the next text frame, control frames are skipped.
+/// Returns the reason of the disconnection if the connection was lost.
+async fn read_text(socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>) -> std::result::Result<String, Error> {
+    loop {
+        return match socket.next().await {
+            Some(Ok(Message::Text(msg))) => Ok(msg),
+            Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Binary(_) | Message::Frame(_))) => continue,
+            Some(Ok(Message::Close(e))) => Err(Error::Msg(format!("Disconnected {e:?}"))),
+            Some(Err(e)) => Err(Error::from(e)),
+            None => Err(Error::Msg("Disconnected".to_string())),
+        };
+    }
+}
+
+fn notify(handler: &mut Option<ReconnectHandler<'_>>, event: ReconnectEvent) -> Result<()> {
+    match handler {
+        Some(ref mut handler) => handler(event),
+        None => Ok(()),
+    }
+}
+
+/// Reconnect to `url` according to `policy`, returns `None` if `running` was set to false in between
+async fn reconnect(
+    policy: &ReconnectPolicy,
+    url: &Url,
+    error: Error,
+    running: &AtomicBool,
+    handler: &mut Option<ReconnectHandler<'_>>,
+) -> Result<Option<Socket>> {
+    notify(handler, ReconnectEvent::Disconnected {
+        reason: error.to_string(),
+    })?;
+    let mut attempt = 0;
+    let mut last_error = error;
+    while running.load(Ordering::Relaxed) {
+        attempt += 1;
+        if policy.max_attempts.is_some_and(|max| attempt > max) {
+            return Err(last_error);
+        }
+        let delay = policy.backoff(attempt);
+        notify(handler, ReconnectEvent::Reconnecting { attempt, delay })?;
+        tokio::time::sleep(delay).await;
+        match open(url).await {
+            Ok(socket) => {
+                notify(handler, ReconnectEvent::Reconnected { attempts: attempt })?;
+                return Ok(Some(socket));
+            }
+            Err(e) => last_error = e,
+        }
+    }
+    Ok(None)
+}
+
 pub struct WebSockets<'a, WE> {
     pub socket: Option<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)>,
     handler: Box<dyn FnMut(WE) -> Result<()> + 'a + Send>,
+    reconnect_handler: Option<ReconnectHandler<'a>>,
+    url: Option<Url>,
+    endpoint: String,
     conf: Config,
+    subscriptions: Subscriptions,
+    requests: (
+        UnboundedSender<SubscriptionRequest>,
+        UnboundedReceiver<SubscriptionRequest>,
+    ),
 }
 
 impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
@@ -75,6 +400,30 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
         Self::new_with_options(handler, Config::default())
     }
 
+    /// New websocket holder connecting to the futures endpoint with default configuration
+    /// # Examples
+    /// see examples/binance_futures.rs
+    pub fn new_futures<Callback>(handler: Callback) -> WebSockets<'a, WE>
+    where
+        Callback: FnMut(WE) -> Result<()> + 'a + Send,
+    {
+        Self::new_futures_with_options(handler, Config::default())
+    }
+
+    /// New websocket holder connecting to [`Config::futures_ws_endpoint`]
+    /// # Examples
+    /// see examples/binance_futures.rs
+    pub fn new_futures_with_options<Callback>(handler: Callback, conf: Config) -> WebSockets<'a, WE>
+    where
+        Callback: FnMut(WE) -> Result<()> + 'a + Send,
+    {
+        let endpoint = conf.futures_ws_endpoint.clone();
+        WebSockets {
+            endpoint,
+            ..Self::new_with_options(handler, conf)
+        }
+    }
+
     /// New websocket holder with provided configuration
     /// # Examples
     /// see examples/binance_websockets.rs
@@ -85,38 +434,85 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
         WebSockets {
             socket: None,
             handler: Box::new(handler),
+            reconnect_handler: None,
+            url: None,
+            endpoint: conf.ws_endpoint.clone(),
             conf,
+            subscriptions: Subscriptions::default(),
+            requests: unbounded_channel(),
         }
     }
 
+    /// Handle to subscribe and unsubscribe from streams while the event loop is running
+    /// # Examples
+    /// ```rust,no_run
+    /// use binance::{websockets::*, ws_model::*};
+    /// use std::sync::atomic::AtomicBool;
+    ///
+    /// tokio_test::block_on(async {
+    ///     let mut web_socket: WebSockets<'_, CombinedStreamEvent<WebsocketEvent>> =
+    ///         WebSockets::new(|event: CombinedStreamEvent<WebsocketEvent>| {
+    ///             println!("{:?}", event.data);
+    ///             Ok(())
+    ///         });
+    ///     web_socket.connect_multiple(vec![trade_stream("btcusdt")]).await.unwrap();
+    ///     let subscriptions = web_socket.subscriptions();
+    ///     tokio::spawn(async move {
+    ///         subscriptions.subscribe(vec![trade_stream("ethusdt")]).await.unwrap();
+    ///         println!("{:?}", subscriptions.list_subscriptions().await);
+    ///     });
+    ///     web_socket.event_loop(&AtomicBool::new(true)).await.unwrap();
+    /// });
+    /// ```
+    pub fn subscriptions(&self) -> SubscriptionHandle {
+        SubscriptionHandle {
+            requests: self.requests.0.clone(),
+        }
+    }
+
+    /// Handler called on connection events when a reconnect policy is configured,
+    /// see [`Config::set_ws_reconnect_policy`]
+    pub fn set_reconnect_handler<Callback>(&mut self, handler: Callback)
+    where
+        Callback: FnMut(ReconnectEvent) -> Result<()> + 'a + Send,
+    {
+        self.reconnect_handler = Some(Box::new(handler));
+    }
+
     /// Connect to multiple websocket endpoints
     /// N.B: WE has to be CombinedStreamEvent
     pub async fn connect_multiple(&mut self, endpoints: Vec<String>) -> Result<()> {
-        let mut url = Url::parse(&self.conf.ws_endpoint)?;
-        url.path_segments_mut()
-            .map_err(|_| Error::UrlParserError(url::ParseError::RelativeUrlWithoutBase))?
-            .push(STREAM_ENDPOINT);
-        url.set_query(Some(&format!("streams={}", combined_stream(endpoints))));
-
-        self.handle_connect(url).await
+        let url = combined_stream_url(&self.endpoint, endpoints.clone())?;
+        self.handle_connect(url).await?;
+        self.subscriptions.connected(endpoints.into_iter().collect());
+        Ok(())
     }
 
     /// Connect to a websocket endpoint
     pub async fn connect(&mut self, endpoint: &str) -> Result<()> {
-        let wss: String = format!("{}/{}/{}", self.conf.ws_endpoint, WS_ENDPOINT, endpoint);
-        let url = Url::parse(&wss)?;
-
-        self.handle_connect(url).await
+        let url = stream_url(&self.endpoint, endpoint)?;
+        self.handle_connect(url).await?;
+        self.subscriptions.connected(BTreeSet::from([endpoint.to_string()]));
+        Ok(())
     }
 
     async fn handle_connect(&mut self, url: Url) -> Result<()> {
-        match connect_async(url).await {
-            Ok(answer) => {
-                self.socket = Some(answer);
-                Ok(())
-            }
-            Err(e) => Err(Error::Msg(format!("Error during handshake {e}"))),
-        }
+        self.socket = Some(open(&url).await?);
+        self.url = Some(url);
+        Ok(())
+    }
+
+    /// Reconnect to the last endpoint according to the reconnect policy,
+    /// returns the disconnection error if there is no policy or if all attempts failed
+    async fn reconnect(&mut self, error: Error, running: &AtomicBool) -> Result<()> {
+        let (policy, url) = match (self.conf.ws_reconnect_policy.clone(), self.url.clone()) {
+            (Some(policy), Some(url)) => (policy, url),
+            _ => return Err(error),
+        };
+        self.socket = None;
+        self.socket = reconnect(&policy, &url, error, running, &mut self.reconnect_handler).await?;
+        self.subscriptions.resubscribe();
+        Ok(())
     }
 
     /// Disconnect from the endpoint
@@ -131,27 +527,267 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
 
     pub fn socket(&self) -> &Option<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)> { &self.socket }
 
+    /// Process events until `running` is set to false or the connection is lost.
+    /// If a reconnect policy is configured, the connection is transparently re-established
+    /// and the handler keeps receiving events.
     pub async fn event_loop(&mut self, running: &AtomicBool) -> Result<()> {
         while running.load(Ordering::Relaxed) {
             if let Some((ref mut socket, _)) = self.socket {
-                // TODO: return error instead of panic?
-                let message = socket.next().await.unwrap()?;
-
-                match message {
-                    Message::Text(msg) => {
-                        if msg.is_empty() {
-                            return Ok(());
+                let next_slot = self.subscriptions.next_slot();
+                let error = tokio::select! {
+                    msg = read_text(socket) => match msg {
+                        Ok(msg) => {
+                            if msg.is_empty() {
+                                return Ok(());
+                            }
+                            if is_query_response(&msg) {
+                                self.subscriptions.resolve(from_str(msg.as_str())?);
+                                continue;
+                            }
+                            let event: WE = from_str(msg.as_str())?;
+                            (self.handler)(event)?;
+                            continue;
                         }
-                        let event: WE = from_str(msg.as_str())?;
-                        (self.handler)(event)?;
+                        Err(e) => e,
+                    },
+                    Some(request) = self.requests.1.recv() => {
+                        self.subscriptions.push(request);
+                        continue;
                     }
-                    Message::Ping(_) | Message::Pong(_) | Message::Binary(_) | Message::Frame(_) => {}
-                    Message::Close(e) => {
-                        return Err(Error::Msg(format!("Disconnected {e:?}")));
+                    _ = sleep_until(next_slot.unwrap_or_else(Instant::now)), if next_slot.is_some() => {
+                        match self.subscriptions.pop_message() {
+                            Some(message) => match socket.send(Message::Text(message)).await {
+                                Ok(()) => continue,
+                                Err(e) => Error::from(e),
+                            },
+                            None => continue,
+                        }
                     }
-                }
+                };
+                self.reconnect(error, running).await?;
             }
         }
         Ok(())
     }
 }
+
+/// Websocket events as a [`Stream`], an alternative to the callback based [`WebSockets`]
+/// that lets events be processed with async code, combinators or `select!`.
+///
+/// The connection is handed over to the stream on the first poll, the reconnect policy of the
+/// configuration applies. Deserialization errors are yielded without ending the stream, the stream
+/// ends after yielding a connection error.
+///
+/// # Examples
+/// ```rust,no_run
+/// use binance::{config::*, websockets::*, ws_model::*};
+/// use futures::StreamExt;
+///
+/// tokio_test::block_on(async {
+///     let mut events: WebsocketStream<'_, WebsocketEvent> = WebsocketStream::new(Config::default());
+///     events.connect(&agg_trade_stream("btcusdt")).await?;
+///     while let Some(event) = events.next().await {
+///         println!("{:?}", event?);
+///     }
+///     Ok::<(), binance::errors::Error>(())
+/// });
+/// ```
+pub struct WebsocketStream<'a, WE> {
+    socket: Option<Socket>,
+    url: Option<Url>,
+    reconnect_handler: Option<ReconnectHandler<'a>>,
+    endpoint: String,
+    conf: Config,
+    events: Option<BoxStream<'a, Result<WE>>>,
+}
+
+impl<'a, WE: serde::de::DeserializeOwned + Send + 'a> WebsocketStream<'a, WE> {
+    /// New stream connecting to [`Config::ws_endpoint`]
+    pub fn new(conf: Config) -> Self {
+        WebsocketStream {
+            socket: None,
+            url: None,
+            reconnect_handler: None,
+            endpoint: conf.ws_endpoint.clone(),
+            conf,
+            events: None,
+        }
+    }
+
+    /// New stream connecting to [`Config::futures_ws_endpoint`]
+    pub fn new_futures(conf: Config) -> Self {
+        WebsocketStream {
+            endpoint: conf.futures_ws_endpoint.clone(),
+            ..Self::new(conf)
+        }
+    }
+
+    /// Handler called on connection events when a reconnect policy is configured,
+    /// has to be set before the stream is polled
+    pub fn set_reconnect_handler<Callback>(&mut self, handler: Callback)
+    where
+        Callback: FnMut(ReconnectEvent) -> Result<()> + 'a + Send,
+    {
+        self.reconnect_handler = Some(Box::new(handler));
+    }
+
+    /// Connect to multiple websocket endpoints
+    /// N.B: WE has to be CombinedStreamEvent
+    pub async fn connect_multiple(&mut self, endpoints: Vec<String>) -> Result<()> {
+        let url = combined_stream_url(&self.endpoint, endpoints)?;
+        self.handle_connect(url).await
+    }
+
+    /// Connect to a websocket endpoint
+    pub async fn connect(&mut self, endpoint: &str) -> Result<()> {
+        let url = stream_url(&self.endpoint, endpoint)?;
+        self.handle_connect(url).await
+    }
+
+    async fn handle_connect(&mut self, url: Url) -> Result<()> {
+        self.socket = Some(open(&url).await?);
+        self.url = Some(url);
+        self.events = None;
+        Ok(())
+    }
+
+    fn events(&mut self) -> Option<BoxStream<'a, Result<WE>>> {
+        let (socket, url) = (self.socket.take()?, self.url.clone()?);
+        let policy = self.conf.ws_reconnect_policy.clone();
+        let handler = self.reconnect_handler.take();
+        let events = futures::stream::unfold(Some((socket, handler)), move |state| {
+            let policy = policy.clone();
+            let url = url.clone();
+            async move {
+                let (mut socket, mut handler) = state?;
+                loop {
+                    let error = match read_text(&mut socket.0).await {
+                        Ok(msg) if msg.is_empty() => return None,
+                        Ok(msg) => return Some((from_str(&msg).map_err(Error::from), Some((socket, handler)))),
+                        Err(e) => e,
+                    };
+                    let policy = match policy {
+                        Some(ref policy) => policy,
+                        None => return Some((Err(error), None)),
+                    };
+                    match reconnect(policy, &url, error, &AtomicBool::new(true), &mut handler).await {
+                        Ok(Some(reconnected)) => socket = reconnected,
+                        Ok(None) => return None,
+                        Err(e) => return Some((Err(e), None)),
+                    }
+                }
+            }
+        });
+        Some(events.boxed())
+    }
+}
+
+impl<'a, WE: serde::de::DeserializeOwned + Send + 'a> Stream for WebsocketStream<'a, WE> {
+    type Item = Result<WE>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.events.is_none() {
+            this.events = this.events();
+        }
+        match this.events {
+            Some(ref mut events) => events.poll_next_unpin(cx),
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn request(method: &'static str, params: &[&str]) -> SubscriptionRequest {
+        SubscriptionRequest {
+            method,
+            params: params.iter().map(|s| s.to_string()).collect(),
+            reply: None,
+        }
+    }
+
+    #[test]
+    fn subscriptions() {
+        let mut subscriptions = Subscriptions::default();
+        subscriptions.connected(BTreeSet::from(["btcusdt@trade".to_string()]));
+        subscriptions.push(request("SUBSCRIBE", &["ethusdt@trade"]));
+        subscriptions.push(request("UNSUBSCRIBE", &["btcusdt@trade"]));
+        for i in 0..MAX_MESSAGES_PER_SECOND {
+            subscriptions.push(request("LIST_SUBSCRIPTIONS", &[]));
+            assert!(subscriptions.next_slot().unwrap() <= Instant::now(), "message {i}");
+            subscriptions.pop_message().unwrap();
+        }
+        assert!(subscriptions.next_slot().unwrap() > Instant::now());
+
+        for id in [1, 2] {
+            subscriptions.resolve(QueryResult {
+                result: None,
+                error: None,
+                id,
+            });
+        }
+        assert_eq!(subscriptions.streams, BTreeSet::from(["ethusdt@trade".to_string()]));
+
+        subscriptions.queue.clear();
+        subscriptions.resubscribe();
+        let methods: Vec<_> = subscriptions
+            .queue
+            .iter()
+            .map(|r| (r.method, r.params.clone()))
+            .collect();
+        assert_eq!(methods[0], ("SUBSCRIBE", vec!["ethusdt@trade".to_string()]));
+        assert_eq!(methods[1], ("UNSUBSCRIBE", vec!["btcusdt@trade".to_string()]));
+        // requests in flight are sent again
+        assert_eq!(methods.len(), 2 + MAX_MESSAGES_PER_SECOND - 2);
+    }
+
+    #[test]
+    fn stream_limit_counts_requests_in_flight() {
+        let mut subscriptions = Subscriptions::default();
+        let streams: Vec<String> = (0..MAX_STREAMS_PER_CONNECTION).map(|i| format!("s{i}@trade")).collect();
+        let (first, second) = streams.split_at(MAX_STREAMS_PER_CONNECTION / 2);
+        subscriptions.connected(first.iter().cloned().collect());
+        subscriptions.push(request("SUBSCRIBE", &[second[0].as_str()]));
+        subscriptions.pop_message().unwrap();
+        let queued: Vec<&str> = second[1..].iter().map(String::as_str).collect();
+        subscriptions.push(request("SUBSCRIBE", &queued));
+        assert_eq!(subscriptions.queue.len(), 1);
+
+        // one stream over the limit once the pending and queued requests are counted
+        let (tx, mut rx) = oneshot::channel();
+        subscriptions.push(SubscriptionRequest {
+            method: "SUBSCRIBE",
+            params: vec!["ethusdt@trade".to_string()],
+            reply: Some(tx),
+        });
+        assert_eq!(subscriptions.queue.len(), 1);
+        assert!(matches!(rx.try_recv(), Ok(Err(Error::Msg(_)))));
+        // streams already requested do not count twice
+        subscriptions.push(request("SUBSCRIBE", &[second[0].as_str()]));
+        assert_eq!(subscriptions.queue.len(), 2);
+    }
+
+    #[test]
+    fn reconnect_backoff() {
+        let policy = ReconnectPolicy {
+            jitter: Duration::ZERO,
+            ..ReconnectPolicy::default()
+        };
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(7), Duration::from_secs(60));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(60));
+        let policy = ReconnectPolicy::default();
+        for attempt in 1..10 {
+            let backoff = policy.backoff(attempt);
+            let base = Duration::from_secs(2_u64.pow(attempt - 1)).min(policy.max_backoff);
+            assert!(
+                backoff >= base && backoff <= base + policy.jitter,
+                "{attempt}: {backoff:?}"
+            );
+        }
+    }
+}
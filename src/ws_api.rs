@@ -0,0 +1,450 @@
+//! Binance WebSocket API, to place orders and query the exchange over a single connection
+//! instead of a HTTPS request each time.
+//!
+//! Requests are correlated with their response by id, so that they can be sent concurrently.
+//! Once logged in with [`WsApi::logon`], which requires an Ed25519 key, signed requests only carry a timestamp.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use futures::{SinkExt, StreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+use url::{form_urlencoded, Url};
+
+use crate::account::{CancelReplaceRequest, OrderCancellation, OrderRequest, OrderStatusRequest};
+use crate::client::handle_content_error;
+use crate::config::Config;
+use crate::errors::*;
+use crate::rest_model::*;
+use crate::signer::{self, Signer};
+use crate::util::{build_request_p, get_server_timestamp};
+use crate::ws_model::{RateLimitStatus, SessionStatus};
+
+type PendingRequests = Arc<Mutex<HashMap<String, oneshot::Sender<Result<Value>>>>>;
+
+/// How long to wait for a response when the config has no timeout
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsApiResponse {
+    id: Option<String>,
+    status: u16,
+    result: Option<Value>,
+    error: Option<WsApiError>,
+    rate_limits: Option<Vec<RateLimitStatus>>,
+}
+
+#[derive(Deserialize)]
+struct WsApiError {
+    #[serde(flatten)]
+    error: BinanceContentError,
+    data: Option<WsApiErrorData>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsApiErrorData {
+    /// Timestamp in milliseconds after which requests are accepted again
+    retry_after: Option<u64>,
+}
+
+impl WsApiResponse {
+    fn into_result(self) -> Result<Value> {
+        let error = match (self.status, self.error) {
+            (200, _) => return Ok(self.result.unwrap_or_default()),
+            (status, None) => return Err(Error::Msg(format!("status {status}"))),
+            (_, Some(error)) => error,
+        };
+        let retry_after = error.data.and_then(|data| data.retry_after);
+        Err(match self.status {
+            429 => Error::RateLimited {
+                retry_after: retry_after
+                    .map(|until| Duration::from_millis(until.saturating_sub(Utc::now().timestamp_millis() as u64))),
+            },
+            418 => Error::IpBanned { until: retry_after },
+            _ => handle_content_error(error.error),
+        })
+    }
+}
+
+/// Client of the WebSocket API, requests are sent by a background task until the client is dropped
+pub struct WsApi {
+    api_key: String,
+    signer: Arc<dyn Signer>,
+    recv_window: u64,
+    /// Spot REST API host, whose clock offset applies to the timestamps
+    rest_api_endpoint: String,
+    messages: UnboundedSender<Message>,
+    pending: PendingRequests,
+    next_id: AtomicU64,
+    logged_on: AtomicBool,
+    rate_limits: Arc<Mutex<Vec<RateLimitStatus>>>,
+    unparsed_frame: Arc<Mutex<Option<String>>>,
+    request_timeout: Duration,
+    connection: JoinHandle<()>,
+}
+
+impl WsApi {
+    /// Connects to the WebSocket API endpoint of the config.
+    /// The secret key is read according to the signature type of the config, like for the REST APIs,
+    /// and an invalid key fails with [`Error::InvalidSigningKey`] before connecting.
+    pub async fn connect(api_key: Option<String>, secret_key: Option<String>, config: &Config) -> Result<Self> {
+        let signer = signer::try_configured_signer(config, secret_key.as_deref())?;
+        let url = Url::parse(&config.ws_api_endpoint)?;
+        let (socket, _) = connect_async(url)
+            .await
+            .map_err(|e| Error::Msg(format!("Error during handshake {e}")))?;
+        let (mut sink, mut stream) = socket.split();
+        let (messages, mut outgoing) = unbounded_channel::<Message>();
+        let pending = PendingRequests::default();
+        let rate_limits = Arc::new(Mutex::new(vec![]));
+        let unparsed_frame = Arc::new(Mutex::new(None));
+
+        let connection = {
+            let pending = pending.clone();
+            let rate_limits = rate_limits.clone();
+            let unparsed_frame = unparsed_frame.clone();
+            tokio::spawn(async move {
+                let reason = loop {
+                    tokio::select! {
+                        message = outgoing.recv() => match message {
+                            Some(message) => {
+                                if let Err(e) = sink.send(message).await {
+                                    break e.to_string();
+                                }
+                            }
+                            None => break "The client was dropped".to_string(),
+                        },
+                        message = stream.next() => match message {
+                            Some(Ok(Message::Text(text))) => dispatch(&text, &pending, &rate_limits, &unparsed_frame),
+                            Some(Ok(Message::Close(e))) => break format!("Disconnected {e:?}"),
+                            Some(Ok(_)) => {}
+                            Some(Err(e)) => break e.to_string(),
+                            None => break "Disconnected".to_string(),
+                        },
+                    }
+                };
+                let _ = sink.close().await;
+                for (_, reply) in pending.lock().unwrap().drain() {
+                    let _ = reply.send(Err(Error::Msg(reason.clone())));
+                }
+            })
+        };
+
+        Ok(Self {
+            api_key: api_key.unwrap_or_default(),
+            signer,
+            recv_window: config.recv_window,
+            rest_api_endpoint: config.rest_api_endpoint.clone(),
+            messages,
+            pending,
+            next_id: AtomicU64::new(1),
+            logged_on: AtomicBool::new(false),
+            rate_limits,
+            unparsed_frame,
+            request_timeout: config.timeout.map_or(DEFAULT_REQUEST_TIMEOUT, Duration::from_secs),
+            connection,
+        })
+    }
+
+    /// Usage of the rate limits, as returned with the last response
+    pub fn rate_limits(&self) -> Vec<RateLimitStatus> { self.rate_limits.lock().unwrap().clone() }
+
+    /// The last frame received that was not a response to a request, for troubleshooting
+    pub fn last_unparsed_frame(&self) -> Option<String> { self.unparsed_frame.lock().unwrap().clone() }
+
+    /// Whether the connection is still open
+    pub fn is_connected(&self) -> bool { !self.connection.is_finished() }
+
+    /// Sends a request and waits for its response, for up to the timeout of the config
+    pub async fn request<T: DeserializeOwned>(&self, method: &str, params: Map<String, Value>) -> Result<T> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed).to_string();
+        let (reply, response) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id.clone(), reply);
+        let request = if params.is_empty() {
+            json!({ "id": id, "method": method })
+        } else {
+            json!({ "id": id, "method": method, "params": params })
+        };
+        if self.messages.send(Message::Text(request.to_string())).is_err() {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(Error::Msg("The WebSocket API connection is closed".to_string()));
+        }
+        let result = match tokio::time::timeout(self.request_timeout, response).await {
+            Ok(result) => result.map_err(|_| Error::Msg("The WebSocket API connection is closed".to_string()))??,
+            Err(_) => {
+                self.pending.lock().unwrap().remove(&id);
+                return Err(Error::Msg(format!(
+                    "No response to the WebSocket API request {method} within {:?}",
+                    self.request_timeout
+                )));
+            }
+        };
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Sends a request with the api key, a timestamp and a signature.
+    /// Once the session is logged in, the api key and signature are not needed anymore.
+    pub async fn signed_request<T: DeserializeOwned>(&self, method: &str, mut params: Map<String, Value>) -> Result<T> {
+        if self.recv_window > 0 && !params.contains_key("recvWindow") {
+            params.insert("recvWindow".to_string(), self.recv_window.into());
+        }
+        params.insert("timestamp".to_string(), self.timestamp()?.into());
+        if !self.logged_on.load(Ordering::Relaxed) {
+            self.sign(&mut params)?;
+        }
+        self.request(method, params).await
+    }
+
+    fn timestamp(&self) -> Result<u64> { get_server_timestamp(&self.rest_api_endpoint) }
+
+    fn sign(&self, params: &mut Map<String, Value>) -> Result<()> {
+        params.insert("apiKey".to_string(), self.api_key.clone().into());
+        // The payload is made of all the parameters sorted by name
+        let sorted: BTreeMap<_, _> = params.iter().collect();
+        let payload = sorted
+            .into_iter()
+            .map(|(name, value)| match value {
+                Value::String(value) => format!("{name}={value}"),
+                value => format!("{name}={value}"),
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        params.insert("signature".to_string(), self.signer.sign(&payload)?.into());
+        Ok(())
+    }
+
+    /// Authenticates the connection with the api key, requires an Ed25519 key
+    pub async fn logon(&self) -> Result<SessionStatus> {
+        let mut params = Map::new();
+        params.insert("timestamp".to_string(), self.timestamp()?.into());
+        self.sign(&mut params)?;
+        let status = self.request("session.logon", params).await?;
+        self.logged_on.store(true, Ordering::Relaxed);
+        Ok(status)
+    }
+
+    pub async fn session_status(&self) -> Result<SessionStatus> { self.request("session.status", Map::new()).await }
+
+    /// Forgets the api key of the connection, signed requests are signed again afterwards
+    pub async fn logout(&self) -> Result<SessionStatus> {
+        let status = self.request("session.logout", Map::new()).await?;
+        self.logged_on.store(false, Ordering::Relaxed);
+        Ok(status)
+    }
+
+    pub async fn ping(&self) -> Result<()> {
+        let _: Value = self.request("ping", Map::new()).await?;
+        Ok(())
+    }
+
+    pub async fn server_time(&self) -> Result<ServerTime> { self.request("time", Map::new()).await }
+
+    pub async fn depth(&self, symbol: &str, limit: Option<u16>) -> Result<OrderBook> {
+        let mut params = params(&[("symbol", symbol)]);
+        if let Some(limit) = limit {
+            params.insert("limit".to_string(), limit.into());
+        }
+        self.request("depth", params).await
+    }
+
+    pub async fn klines(
+        &self,
+        symbol: &str,
+        interval: KlineInterval,
+        limit: Option<u16>,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+    ) -> Result<Vec<KlineSummary>> {
+        let mut params = params(&[("symbol", symbol), ("interval", interval.as_str())]);
+        let optional = [
+            ("limit", limit.map(u64::from)),
+            ("startTime", start_time),
+            ("endTime", end_time),
+        ];
+        for (name, value) in optional {
+            if let Some(value) = value {
+                params.insert(name.to_string(), value.into());
+            }
+        }
+        self.request("klines", params).await
+    }
+
+    pub async fn account_status(&self) -> Result<AccountInformation> {
+        self.signed_request("account.status", Map::new()).await
+    }
+
+    /// Open orders of `symbol`, or of all symbols
+    pub async fn open_orders(&self, symbol: Option<&str>) -> Result<Vec<Order>> {
+        let params = symbol.map(|symbol| params(&[("symbol", symbol)])).unwrap_or_default();
+        self.signed_request("openOrders.status", params).await
+    }
+
+    pub async fn order_status(&self, query: OrderStatusRequest) -> Result<Order> {
+        self.signed_request("order.status", payload_params(&query)?).await
+    }
+
+    /// Place an order, validated like [`crate::account::Account::place_order`]
+    pub async fn place_order(&self, order: OrderRequest) -> Result<Transaction> {
+        order.valid()?;
+        self.signed_request("order.place", payload_params(&order)?).await
+    }
+
+    pub async fn cancel_order(&self, cancellation: OrderCancellation) -> Result<OrderCanceled> {
+        self.signed_request("order.cancel", payload_params(&cancellation)?)
+            .await
+    }
+
+    pub async fn cancel_replace_order(&self, order: CancelReplaceRequest) -> Result<OrderCanceledReplaced> {
+        order.valid()?;
+        self.signed_request("order.cancelReplace", payload_params(&order)?)
+            .await
+    }
+}
+
+impl Drop for WsApi {
+    fn drop(&mut self) { self.connection.abort() }
+}
+
+/// Replies to the request of a response. A frame that cannot be parsed fails the request it answers
+/// if its id can be read, and is kept as the last unparsed frame otherwise.
+fn dispatch(
+    text: &str,
+    pending: &PendingRequests,
+    rate_limits: &Mutex<Vec<RateLimitStatus>>,
+    unparsed_frame: &Mutex<Option<String>>,
+) {
+    let response: WsApiResponse = match serde_json::from_str(text) {
+        Ok(response) => response,
+        Err(e) => {
+            let id = serde_json::from_str::<Value>(text)
+                .ok()
+                .and_then(|frame| frame.get("id")?.as_str().map(str::to_string));
+            match id.and_then(|id| pending.lock().unwrap().remove(&id)) {
+                Some(reply) => {
+                    let _ = reply.send(Err(e.into()));
+                }
+                None => *unparsed_frame.lock().unwrap() = Some(text.to_string()),
+            }
+            return;
+        }
+    };
+    if let Some(limits) = response.rate_limits.clone() {
+        *rate_limits.lock().unwrap() = limits;
+    }
+    let reply = response.id.as_ref().and_then(|id| pending.lock().unwrap().remove(id));
+    if let Some(reply) = reply {
+        let _ = reply.send(response.into_result());
+    }
+}
+
+fn params(params: &[(&str, &str)]) -> Map<String, Value> {
+    params
+        .iter()
+        .map(|(name, value)| (name.to_string(), Value::String(value.to_string())))
+        .collect()
+}
+
+/// Parameters of a REST payload, formatted like query strings
+fn payload_params<S: serde::Serialize>(payload: &S) -> Result<Map<String, Value>> {
+    let query = build_request_p(payload)?;
+    Ok(form_urlencoded::parse(query.as_bytes())
+        .map(|(name, value)| (name.into_owned(), Value::String(value.into_owned())))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_responses() {
+        let response: WsApiResponse = serde_json::from_str(
+            r#"{"id":"1","status":400,"error":{"code":-2010,"msg":"Account has insufficient balance."}}"#,
+        )
+        .unwrap();
+        assert!(matches!(response.into_result(), Err(Error::BinanceError { response }) if response.code == -2010));
+        let response: WsApiResponse = serde_json::from_str(concat!(
+            r#"{"id":"2","status":418,"#,
+            r#""error":{"code":-1003,"msg":"banned","data":{"serverTime":1,"retryAfter":1659775054000}},"#,
+            r#""rateLimits":[{"rateLimitType":"REQUEST_WEIGHT","interval":"MINUTE","#,
+            r#""intervalNum":1,"limit":6000,"count":7}]}"#
+        ))
+        .unwrap();
+        assert_eq!(response.rate_limits.as_ref().unwrap()[0].count, 7);
+        assert!(matches!(
+            response.into_result(),
+            Err(Error::IpBanned {
+                until: Some(1659775054000)
+            })
+        ));
+        let response: WsApiResponse = serde_json::from_str(r#"{"id":"3","status":500}"#).unwrap();
+        assert!(matches!(response.into_result(), Err(Error::Msg(msg)) if msg == "status 500"));
+    }
+
+    #[test]
+    fn unparsed_frames() {
+        let pending = PendingRequests::default();
+        let (reply, mut response) = oneshot::channel();
+        pending.lock().unwrap().insert("1".to_string(), reply);
+        let rate_limits = Mutex::new(vec![]);
+        let unparsed_frame = Mutex::new(None);
+        dispatch(r#"{"id":"1","status":"OK"}"#, &pending, &rate_limits, &unparsed_frame);
+        assert!(matches!(response.try_recv(), Ok(Err(Error::Json(_)))));
+        assert!(pending.lock().unwrap().is_empty());
+        dispatch(
+            r#"{"event":{"e":"eventStreamTerminated"}}"#,
+            &pending,
+            &rate_limits,
+            &unparsed_frame,
+        );
+        assert_eq!(
+            unparsed_frame.lock().unwrap().as_deref(),
+            Some(r#"{"event":{"e":"eventStreamTerminated"}}"#)
+        );
+    }
+
+    #[tokio::test]
+    async fn request_timeout() {
+        // A server that never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while socket.next().await.is_some() {}
+        });
+        let config = Config {
+            ws_api_endpoint: format!("ws://{address}"),
+            ..Config::default()
+        }
+        .set_timeout(1);
+        let api = WsApi::connect(None, None, &config).await.unwrap();
+        let result = api.request::<Value>("ping", Map::new()).await;
+        assert!(matches!(result, Err(Error::Msg(msg)) if msg.contains("No response")));
+        assert!(api.pending.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn order_params() {
+        let order = OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            quantity: Some("0.5".parse().unwrap()),
+            ..OrderRequest::default()
+        };
+        let params = payload_params(&order).unwrap();
+        assert_eq!(params["symbol"], "BTCUSDT");
+        assert_eq!(params["quantity"], "0.5");
+        assert!(!params.contains_key("price"));
+    }
+}
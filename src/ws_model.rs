@@ -1,4 +1,39 @@
-use crate::rest_model::{string_or_float, Asks, Bids, OrderBook, OrderSide, OrderStatus, OrderType, TimeInForce};
+use crate::errors::BinanceContentError;
+use crate::rest_model::{string_or_float, string_or_u64, Asks, Bids, ContingencyType, KlineInterval, KlineSummary,
+                        Number, OCOOrderStatus, OCOStatus, OrderBook, OrderSide, OrderStatus, OrderType, RateLimit,
+                        TimeInForce};
+
+/// Prices and quantities of trade and ticker events, `rust_decimal::Decimal` with the `decimal` feature and the
+/// string sent by Binance otherwise
+#[cfg(not(feature = "decimal"))]
+pub type WsNumber = String;
+/// Prices and quantities of trade and ticker events, `rust_decimal::Decimal` with the `decimal` feature and the
+/// string sent by Binance otherwise
+#[cfg(feature = "decimal")]
+pub type WsNumber = Number;
+
+#[cfg(not(feature = "decimal"))]
+mod ws_number {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "decimal")]
+use crate::rest_model::string_or_float as ws_number;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "e")]
@@ -23,11 +58,15 @@ pub enum WebsocketEvent {
     OrderUpdate(Box<OrderUpdate>),
     #[serde(alias = "listStatus")]
     ListOrderUpdate(Box<OrderListUpdate>),
+    #[serde(alias = "listenKeyExpired")]
+    ListenKeyExpired(Box<ListenKeyExpired>),
 }
 
+/// Response to a subscription request
 #[derive(Serialize, Deserialize, Debug)]
 pub struct QueryResult {
-    pub result: Option<String>,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<BinanceContentError>,
     pub id: i64,
 }
 
@@ -43,11 +82,11 @@ pub struct TradesEvent {
     #[serde(rename = "a")]
     pub aggregated_trade_id: u64,
 
-    #[serde(rename = "p")]
-    pub price: String,
+    #[serde(rename = "p", with = "ws_number")]
+    pub price: WsNumber,
 
-    #[serde(rename = "q")]
-    pub qty: String,
+    #[serde(rename = "q", with = "ws_number")]
+    pub qty: WsNumber,
 
     #[serde(rename = "f")]
     pub first_break_trade_id: u64,
@@ -77,11 +116,11 @@ pub struct TradeEvent {
     #[serde(rename = "t")]
     pub trade_id: u64,
 
-    #[serde(rename = "p")]
-    pub price: String,
+    #[serde(rename = "p", with = "ws_number")]
+    pub price: WsNumber,
 
-    #[serde(rename = "q")]
-    pub qty: String,
+    #[serde(rename = "q", with = "ws_number")]
+    pub qty: WsNumber,
 
     #[serde(rename = "b")]
     pub buyer_order_id: u64,
@@ -106,36 +145,36 @@ pub struct DayTickerEvent {
     pub event_time: u64,
     #[serde(rename = "s")]
     pub symbol: String,
-    #[serde(rename = "p")]
-    pub price_change: String,
-    #[serde(rename = "P")]
-    pub price_change_percent: String,
-    #[serde(rename = "w")]
-    pub average_price: String,
-    #[serde(rename = "x")]
-    pub prev_close: String,
-    #[serde(rename = "c")]
-    pub current_close: String,
-    #[serde(rename = "Q")]
-    pub current_close_qty: String,
-    #[serde(rename = "b")]
-    pub best_bid: String,
-    #[serde(rename = "B")]
-    pub best_bid_qty: String,
-    #[serde(rename = "a")]
-    pub best_ask: String,
-    #[serde(rename = "A")]
-    pub best_ask_qty: String,
-    #[serde(rename = "o")]
-    pub open: String,
-    #[serde(rename = "h")]
-    pub high: String,
-    #[serde(rename = "l")]
-    pub low: String,
-    #[serde(rename = "v")]
-    pub volume: String,
-    #[serde(rename = "q")]
-    pub quote_volume: String,
+    #[serde(rename = "p", with = "ws_number")]
+    pub price_change: WsNumber,
+    #[serde(rename = "P", with = "ws_number")]
+    pub price_change_percent: WsNumber,
+    #[serde(rename = "w", with = "ws_number")]
+    pub average_price: WsNumber,
+    #[serde(rename = "x", with = "ws_number")]
+    pub prev_close: WsNumber,
+    #[serde(rename = "c", with = "ws_number")]
+    pub current_close: WsNumber,
+    #[serde(rename = "Q", with = "ws_number")]
+    pub current_close_qty: WsNumber,
+    #[serde(rename = "b", with = "ws_number")]
+    pub best_bid: WsNumber,
+    #[serde(rename = "B", with = "ws_number")]
+    pub best_bid_qty: WsNumber,
+    #[serde(rename = "a", with = "ws_number")]
+    pub best_ask: WsNumber,
+    #[serde(rename = "A", with = "ws_number")]
+    pub best_ask_qty: WsNumber,
+    #[serde(rename = "o", with = "ws_number")]
+    pub open: WsNumber,
+    #[serde(rename = "h", with = "ws_number")]
+    pub high: WsNumber,
+    #[serde(rename = "l", with = "ws_number")]
+    pub low: WsNumber,
+    #[serde(rename = "v", with = "ws_number")]
+    pub volume: WsNumber,
+    #[serde(rename = "q", with = "ws_number")]
+    pub quote_volume: WsNumber,
     #[serde(rename = "O")]
     pub open_time: u64,
     #[serde(rename = "C")]
@@ -155,18 +194,18 @@ pub struct MiniDayTickerEvent {
     pub event_time: u64,
     #[serde(rename = "s")]
     pub symbol: String,
-    #[serde(rename = "c")]
-    pub current_close: String,
-    #[serde(rename = "o")]
-    pub open: String,
-    #[serde(rename = "h")]
-    pub high: String,
-    #[serde(rename = "l")]
-    pub low: String,
-    #[serde(rename = "v")]
-    pub volume: String,
-    #[serde(rename = "q")]
-    pub quote_volume: String,
+    #[serde(rename = "c", with = "ws_number")]
+    pub current_close: WsNumber,
+    #[serde(rename = "o", with = "ws_number")]
+    pub open: WsNumber,
+    #[serde(rename = "h", with = "ws_number")]
+    pub high: WsNumber,
+    #[serde(rename = "l", with = "ws_number")]
+    pub low: WsNumber,
+    #[serde(rename = "v", with = "ws_number")]
+    pub volume: WsNumber,
+    #[serde(rename = "q", with = "ws_number")]
+    pub quote_volume: WsNumber,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -190,43 +229,62 @@ pub struct Kline {
     #[serde(rename = "s")]
     pub symbol: String,
     #[serde(rename = "i")]
-    pub interval: String,
+    pub interval: KlineInterval,
     #[serde(rename = "f")]
     pub first_trade_id: i64,
     #[serde(rename = "L")]
     pub last_trade_id: i64,
     #[serde(with = "string_or_float")]
     #[serde(rename = "o")]
-    pub open: f64,
+    pub open: Number,
     #[serde(with = "string_or_float")]
     #[serde(rename = "c")]
-    pub close: f64,
+    pub close: Number,
     #[serde(with = "string_or_float")]
     #[serde(rename = "h")]
-    pub high: f64,
+    pub high: Number,
     #[serde(with = "string_or_float")]
     #[serde(rename = "l")]
-    pub low: f64,
+    pub low: Number,
     #[serde(with = "string_or_float")]
     #[serde(rename = "v")]
-    pub volume: f64,
+    pub volume: Number,
     #[serde(rename = "n")]
     pub number_of_trades: i64,
     #[serde(rename = "x")]
     pub is_final_bar: bool,
     #[serde(with = "string_or_float")]
     #[serde(rename = "q")]
-    pub quote_volume: f64,
+    pub quote_volume: Number,
     #[serde(with = "string_or_float")]
     #[serde(rename = "V")]
-    pub active_buy_volume: f64,
+    pub active_buy_volume: Number,
     #[serde(with = "string_or_float")]
     #[serde(rename = "Q")]
-    pub active_volume_buy_quote: f64,
+    pub active_volume_buy_quote: Number,
     #[serde(skip, rename = "B")]
     pub ignore_me: String,
 }
 
+impl From<&Kline> for KlineSummary {
+    fn from(kline: &Kline) -> Self {
+        KlineSummary {
+            open_time: kline.start_time,
+            open: kline.open,
+            high: kline.high,
+            low: kline.low,
+            close: kline.close,
+            volume: kline.volume,
+            close_time: kline.end_time,
+            quote_asset_volume: kline.quote_volume,
+            number_of_trades: kline.number_of_trades,
+            taker_buy_base_asset_volume: kline.active_buy_volume,
+            taker_buy_quote_asset_volume: kline.active_volume_buy_quote,
+            ignore: kline.ignore_me.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DepthOrderBookEvent {
@@ -254,16 +312,16 @@ pub struct BookTickerEvent {
     pub symbol: String,
 
     #[serde(rename = "b", with = "string_or_float")]
-    pub best_bid: f64,
+    pub best_bid: Number,
 
     #[serde(rename = "B", with = "string_or_float")]
-    pub best_bid_qty: f64,
+    pub best_bid_qty: Number,
 
     #[serde(rename = "a", with = "string_or_float")]
-    pub best_ask: f64,
+    pub best_ask: Number,
 
     #[serde(rename = "A", with = "string_or_float")]
-    pub best_ask_qty: f64,
+    pub best_ask_qty: Number,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -272,7 +330,6 @@ pub struct CombinedStreamEvent<T> {
     pub data: T,
 }
 
-///
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum WebsocketEventUntag {
@@ -293,7 +350,7 @@ impl<T> CombinedStreamEvent<T> {
     }
 }
 
-/// User Stream related events
+// User Stream related events
 
 /// Account position update
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -346,10 +403,20 @@ pub struct EventBalance {
     pub asset: String,
     #[serde(rename = "f")]
     #[serde(with = "string_or_float")]
-    pub free: f64,
+    pub free: Number,
     #[serde(rename = "l")]
     #[serde(with = "string_or_float")]
-    pub locked: f64,
+    pub locked: Number,
+}
+
+/// Sent when the listen key of the user stream expired, a new key has to be requested
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenKeyExpired {
+    /// Sent as a string by the spot user stream
+    #[serde(rename = "E", with = "string_or_u64")]
+    pub event_time: u64,
+    pub listen_key: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -363,7 +430,7 @@ pub struct BalanceUpdate {
 
     #[serde(rename = "d")]
     #[serde(with = "string_or_float")]
-    pub delta: f64,
+    pub delta: Number,
 
     #[serde(alias = "T")]
     pub clear_time: u64,
@@ -386,16 +453,16 @@ pub struct OrderUpdate {
     pub time_in_force: TimeInForce,
     #[serde(rename = "q")]
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
     #[serde(rename = "p")]
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(rename = "P")]
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Number,
     #[serde(rename = "F")]
     #[serde(with = "string_or_float")]
-    pub iceberg_qty: f64,
+    pub iceberg_qty: Number,
     #[serde(rename = "g")]
     pub order_list_id: i64,
     #[serde(rename = "C")]
@@ -410,16 +477,16 @@ pub struct OrderUpdate {
     pub order_id: u64,
     #[serde(rename = "l")]
     #[serde(with = "string_or_float")]
-    pub qty_last_executed: f64,
+    pub qty_last_executed: Number,
     #[serde(rename = "z")]
     #[serde(with = "string_or_float")]
-    pub cumulative_filled_qty: f64,
+    pub cumulative_filled_qty: Number,
     #[serde(rename = "L")]
     #[serde(with = "string_or_float")]
-    pub last_executed_price: f64,
+    pub last_executed_price: Number,
     #[serde(rename = "n")]
     #[serde(with = "string_or_float")]
-    pub commission: f64,
+    pub commission: Number,
     #[serde(rename = "N")]
     pub commission_asset: Option<String>,
     #[serde(rename = "T")]
@@ -438,14 +505,14 @@ pub struct OrderUpdate {
     pub order_creation_time: u64,
     #[serde(rename = "Z")]
     #[serde(with = "string_or_float")]
-    pub cumulative_quote_asset_transacted_qty: f64,
+    pub cumulative_quote_asset_transacted_qty: Number,
     /// (i.e. lastPrice * lastQty)
     #[serde(rename = "Y")]
     #[serde(with = "string_or_float")]
-    pub last_quote_asset_transacted_qty: f64,
+    pub last_quote_asset_transacted_qty: Number,
     #[serde(rename = "Q")]
     #[serde(with = "string_or_float")]
-    pub quote_order_qty: f64,
+    pub quote_order_qty: Number,
 }
 
 /// For OCO Events
@@ -457,17 +524,18 @@ pub struct OrderListUpdate {
     #[serde(rename = "s")]
     pub symbol: String,
     #[serde(rename = "g")]
-    order_list_id: i64,
+    pub order_list_id: u64,
     #[serde(rename = "c")]
-    contingency_type: String,
+    pub contingency_type: ContingencyType,
     #[serde(rename = "l")]
-    list_status_type: String,
+    pub list_status_type: OCOStatus,
     #[serde(rename = "L")]
-    list_order_status: String,
+    pub list_order_status: OCOOrderStatus,
+    /// "NONE" unless the list was rejected
     #[serde(rename = "r")]
-    list_reject_reason: String,
+    pub list_reject_reason: String,
     #[serde(rename = "C")]
-    list_client_order_id: String,
+    pub list_client_order_id: String,
     #[serde(rename = "T")]
     pub transaction_time: u64,
     #[serde(rename = "O")]
@@ -480,7 +548,62 @@ pub struct OrderListTransaction {
     #[serde(rename = "s")]
     pub symbol: String,
     #[serde(rename = "i")]
-    pub order_id: i64,
+    pub order_id: u64,
     #[serde(rename = "c")]
     pub client_order_id: String,
 }
+
+/// Authentication status of a WebSocket API session
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatus {
+    /// The api key the session is logged in with, if any
+    pub api_key: Option<String>,
+    pub authorized_since: Option<u64>,
+    pub connected_since: u64,
+    pub return_rate_limits: bool,
+    pub server_time: u64,
+}
+
+/// Usage of a rate limit, returned along with each WebSocket API response
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitStatus {
+    #[serde(flatten)]
+    pub rate_limit: RateLimit,
+    pub count: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listen_key_expired() {
+        for event_time in [serde_json::json!("1699596037418"), serde_json::json!(1699596037418u64)] {
+            let event = serde_json::json!({
+                "e": "listenKeyExpired",
+                "E": event_time,
+                "listenKey": "OfYGbUzi3PraNagEkdKuFwUHn48brFsItTdsuiIXrucEvD0rhRXZ7I6URWfE8YE8"
+            });
+            let WebsocketEvent::ListenKeyExpired(expired) = serde_json::from_value(event).unwrap() else {
+                panic!("listenKeyExpired")
+            };
+            assert_eq!(expired.event_time, 1699596037418);
+        }
+    }
+
+    #[test]
+    fn trade_prices_are_exact() {
+        let event = serde_json::json!({
+            "e": "trade", "E": 123456789, "s": "BNBBTC", "t": 12345, "p": "0.001000010000000001", "q": "100.00000000",
+            "b": 88, "a": 50, "T": 123456785, "m": true, "M": true
+        });
+        let WebsocketEvent::Trade(trade) = serde_json::from_value(event).unwrap() else {
+            panic!("trade")
+        };
+        assert_eq!(trade.price.to_string(), "0.001000010000000001");
+        let value = serde_json::to_value(WebsocketEvent::Trade(trade)).unwrap();
+        assert_eq!(value["p"], "0.001000010000000001");
+    }
+}